@@ -0,0 +1,150 @@
+//! Compares `#[safe_math]` functions with the equivalent hand-written `checked_*` chains.
+//!
+//! Both versions of every shape should report the same time: any gap means the macro
+//! expansion added work (an extra temporary, branch or call) that the optimizer could
+//! not remove. Inputs go through `black_box` so that nothing is constant-folded.
+//!
+//! To check a change for regressions, save a baseline before it and compare after:
+//!
+//! ```text
+//! cargo bench --bench overhead -- --save-baseline before
+//! cargo bench --bench overhead -- --baseline before
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use safe_math::{safe_math, SafeMathError};
+
+macro_rules! shapes {
+    ($($t:ident),*) => {
+        $(
+            mod $t {
+                use super::*;
+
+                /// Single operation: the helper's own overhead, without any chaining.
+                #[safe_math]
+                pub fn single_macro(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    Ok(a + b)
+                }
+
+                pub fn single_checked(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    a.checked_add(b).ok_or(SafeMathError::Overflow)
+                }
+
+                /// Linear chain: every operation depends on the previous one.
+                #[safe_math]
+                pub fn chain_macro(a: $t, b: $t, c: $t, d: $t) -> Result<$t, SafeMathError> {
+                    Ok((a + b) * c - d / b + a % c)
+                }
+
+                pub fn chain_checked(a: $t, b: $t, c: $t, d: $t) -> Result<$t, SafeMathError> {
+                    let sum = a.checked_add(b).ok_or(SafeMathError::Overflow)?;
+                    let prod = sum.checked_mul(c).ok_or(SafeMathError::Overflow)?;
+                    let quot = d.checked_div(b).ok_or(SafeMathError::DivisionByZero)?;
+                    let diff = prod.checked_sub(quot).ok_or(SafeMathError::Overflow)?;
+                    let rem = a.checked_rem(c).ok_or(SafeMathError::DivisionByZero)?;
+                    diff.checked_add(rem).ok_or(SafeMathError::Overflow)
+                }
+
+                /// Balanced tree: independent subexpressions combined at the end.
+                #[safe_math]
+                pub fn tree_macro(a: $t, b: $t, c: $t, d: $t) -> Result<$t, SafeMathError> {
+                    Ok((a * b + c * d) + (a + d) * (b + c))
+                }
+
+                pub fn tree_checked(a: $t, b: $t, c: $t, d: $t) -> Result<$t, SafeMathError> {
+                    let ab = a.checked_mul(b).ok_or(SafeMathError::Overflow)?;
+                    let cd = c.checked_mul(d).ok_or(SafeMathError::Overflow)?;
+                    let left = ab.checked_add(cd).ok_or(SafeMathError::Overflow)?;
+                    let ad = a.checked_add(d).ok_or(SafeMathError::Overflow)?;
+                    let bc = b.checked_add(c).ok_or(SafeMathError::Overflow)?;
+                    let right = ad.checked_mul(bc).ok_or(SafeMathError::Overflow)?;
+                    left.checked_add(right).ok_or(SafeMathError::Overflow)
+                }
+
+                /// Compound assignments in a loop, as in accumulators.
+                #[safe_math]
+                pub fn compound_macro(values: &[$t], k: $t) -> Result<$t, SafeMathError> {
+                    let mut acc = 0;
+                    for &v in values {
+                        acc += v;
+                        acc *= k;
+                        acc -= v;
+                    }
+                    Ok(acc)
+                }
+
+                pub fn compound_checked(values: &[$t], k: $t) -> Result<$t, SafeMathError> {
+                    let mut acc: $t = 0;
+                    for &v in values {
+                        acc = acc.checked_add(v).ok_or(SafeMathError::Overflow)?;
+                        acc = acc.checked_mul(k).ok_or(SafeMathError::Overflow)?;
+                        acc = acc.checked_sub(v).ok_or(SafeMathError::Overflow)?;
+                    }
+                    Ok(acc)
+                }
+            }
+        )*
+
+        fn bench_shapes(c: &mut Criterion) {
+            $(
+                let ty = stringify!($t);
+                let (a, b, c_, d): ($t, $t, $t, $t) = (7, 3, 5, 11);
+                // `k = 1` keeps the accumulator small for any slice length.
+                let values: Vec<$t> = (1..=16).collect();
+
+                // About a quarter of the sums overflow, so that both branches are measured.
+                let pairs: Vec<($t, $t)> = (0..64).map(|i| ($t::MAX - i % 4, i % 3)).collect();
+                let mut group = c.benchmark_group(format!("single/{ty}"));
+                group.bench_function("safe_math", |bench| {
+                    bench.iter(|| {
+                        black_box(&pairs)
+                            .iter()
+                            .filter(|&&(a, b)| $t::single_macro(a, b).is_ok())
+                            .count()
+                    })
+                });
+                group.bench_function("checked", |bench| {
+                    bench.iter(|| {
+                        black_box(&pairs)
+                            .iter()
+                            .filter(|&&(a, b)| $t::single_checked(a, b).is_ok())
+                            .count()
+                    })
+                });
+                group.finish();
+
+                let mut group = c.benchmark_group(format!("chain/{ty}"));
+                group.bench_function("safe_math", |bench| {
+                    bench.iter(|| $t::chain_macro(black_box(a), black_box(b), black_box(c_), black_box(d)))
+                });
+                group.bench_function("checked", |bench| {
+                    bench.iter(|| $t::chain_checked(black_box(a), black_box(b), black_box(c_), black_box(d)))
+                });
+                group.finish();
+
+                let mut group = c.benchmark_group(format!("tree/{ty}"));
+                group.bench_function("safe_math", |bench| {
+                    bench.iter(|| $t::tree_macro(black_box(a), black_box(b), black_box(c_), black_box(d)))
+                });
+                group.bench_function("checked", |bench| {
+                    bench.iter(|| $t::tree_checked(black_box(a), black_box(b), black_box(c_), black_box(d)))
+                });
+                group.finish();
+
+                let mut group = c.benchmark_group(format!("compound/{ty}"));
+                group.bench_function("safe_math", |bench| {
+                    bench.iter(|| $t::compound_macro(black_box(&values), black_box(1)))
+                });
+                group.bench_function("checked", |bench| {
+                    bench.iter(|| $t::compound_checked(black_box(&values), black_box(1)))
+                });
+                group.finish();
+            )*
+        }
+    };
+}
+
+shapes!(u8, u32, u64, u128, i32, i64);
+
+criterion_group!(benches, bench_shapes);
+criterion_main!(benches);
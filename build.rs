@@ -0,0 +1,28 @@
+//! Detects toolchain support for optional code paths.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(has_strict_ops)");
+
+    // The `strict_*` integer methods used by `mode = strict` were stabilized in Rust 1.91.
+    if rustc_minor_version().is_some_and(|minor| minor >= 91) {
+        println!("cargo:rustc-cfg=has_strict_ops");
+    }
+}
+
+/// Returns `y` in the `rustc 1.y.z` version of the compiler building this crate.
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    version
+        .split_whitespace()
+        .nth(1)?
+        .split('.')
+        .nth(1)?
+        .parse()
+        .ok()
+}
@@ -1,22 +1,22 @@
-use safe_math::safe_math;
+use safe_math::{safe_math, SafeMathError};
 
 #[safe_math]
-fn add(a: u8, b: u8) -> Result<u8, ()> {
+fn add(a: u8, b: u8) -> Result<u8, SafeMathError> {
     Ok(a + b)
 }
 
 #[safe_math]
-fn sub(a: u8, b: u8) -> Result<u8, ()> {
+fn sub(a: u8, b: u8) -> Result<u8, SafeMathError> {
     Ok(a - b)
 }
 
 #[safe_math]
-fn mul(a: u8, b: u8) -> Result<u8, ()> {
+fn mul(a: u8, b: u8) -> Result<u8, SafeMathError> {
     Ok(a * b)
 }
 
 #[safe_math]
-fn div(a: u8, b: u8) -> Result<u8, ()> {
+fn div(a: u8, b: u8) -> Result<u8, SafeMathError> {
     Ok(a / b)
 }
 
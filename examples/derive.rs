@@ -1,5 +1,5 @@
 use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
-use safe_math::{safe_math, SafeMathOps};
+use safe_math::{safe_math, SafeMathError, SafeMathOps};
 use std::ops::{Add, Div, Mul, Sub};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default, SafeMathOps)]
@@ -55,22 +55,22 @@ impl CheckedDiv for CustomNumber {
 }
 
 #[safe_math]
-fn add(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, ()> {
+fn add(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, SafeMathError> {
     Ok(a + b)
 }
 
 #[safe_math]
-fn sub(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, ()> {
+fn sub(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, SafeMathError> {
     Ok(a - b)
 }
 
 #[safe_math]
-fn mul(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, ()> {
+fn mul(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, SafeMathError> {
     Ok(a * b)
 }
 
 #[safe_math]
-fn div(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, ()> {
+fn div(a: CustomNumber, b: CustomNumber) -> Result<CustomNumber, SafeMathError> {
     Ok(a / b)
 }
 
@@ -100,27 +100,27 @@ fn main() {
     // Test cases with overflow
     assert_eq!(
         add(CustomNumber { a: i32::MAX }, CustomNumber { a: 1 }),
-        Err(()),
-        "add(CustomNumber {{ a: i32::MAX }}, CustomNumber {{ a: 1 }}) should be Err(())"
+        Err(SafeMathError::Overflow),
+        "add(CustomNumber {{ a: i32::MAX }}, CustomNumber {{ a: 1 }}) should be Err(Overflow)"
     );
     assert_eq!(
         sub(CustomNumber { a: i32::MIN }, CustomNumber { a: 1 }),
-        Err(()),
-        "sub(CustomNumber {{ a: i32::MIN }}, CustomNumber {{ a: 1 }}) should be Err(())"
+        Err(SafeMathError::Overflow),
+        "sub(CustomNumber {{ a: i32::MIN }}, CustomNumber {{ a: 1 }}) should be Err(Overflow)"
     );
     assert_eq!(
         mul(CustomNumber { a: i32::MAX }, CustomNumber { a: 2 }),
-        Err(()),
-        "mul(CustomNumber {{ a: i32::MAX }}, CustomNumber {{ a: 2 }}) should be Err(())"
+        Err(SafeMathError::Overflow),
+        "mul(CustomNumber {{ a: i32::MAX }}, CustomNumber {{ a: 2 }}) should be Err(Overflow)"
     );
     assert_eq!(
         div(CustomNumber { a: 1 }, CustomNumber { a: 0 }),
-        Err(()),
-        "div(CustomNumber {{ a: 1 }}, CustomNumber {{ a: 0 }}) should be Err(())"
+        Err(SafeMathError::DivisionByZero),
+        "div(CustomNumber {{ a: 1 }}, CustomNumber {{ a: 0 }}) should be Err(DivisionByZero)"
     );
     assert_eq!(
         div(CustomNumber { a: i32::MIN }, CustomNumber { a: -1 }),
-        Err(()),
-        "div(CustomNumber {{ a: i32::MIN }}, CustomNumber {{ a: -1 }}) should be Err(())"
+        Err(SafeMathError::Overflow),
+        "div(CustomNumber {{ a: i32::MIN }}, CustomNumber {{ a: -1 }}) should be Err(Overflow)"
     );
 }
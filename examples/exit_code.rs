@@ -0,0 +1,32 @@
+//! Exits with the code of the `SafeMathError`, e.g. `2` for `cargo run --example exit_code -- 10 0`.
+
+use safe_math::{safe_math, SafeMathError};
+use std::process::ExitCode;
+
+#[safe_math]
+fn average(total: u32, count: u32) -> Result<u32, SafeMathError> {
+    Ok(total / count)
+}
+
+fn parse_args() -> Result<(u32, u32), SafeMathError> {
+    let mut args = std::env::args().skip(1);
+    let mut next = || {
+        args.next()
+            .ok_or(SafeMathError::ParseError)
+            .and_then(|arg| safe_math::safe_parse(&arg, 10))
+    };
+    Ok((next()?, next()?))
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(|(total, count)| average(total, count)) {
+        Ok(avg) => {
+            println!("{avg}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            err.into()
+        }
+    }
+}
@@ -1,13 +1,28 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
-use std::collections::HashSet;
-use syn::{parse_macro_input, DeriveInput, Meta};
+use std::collections::{HashMap, HashSet};
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Member, Meta};
 
 const SAFE_MATH_OPS_ATTRIBUTE_NAME: &str = "SafeMathOps";
 /// List of operations that can be specified inside the `#[SafeMathOps(...)]` attribute.
 /// Keep this in sync with the match arms below.
-const ALLOWED_OPS: &[&str] = &["add", "sub", "mul", "div", "rem"];
+const ALLOWED_OPS: &[&str] = &[
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "rem",
+    "checked_sum",
+    "scale",
+    "cmp",
+    "saturating",
+    "wrapping",
+];
+/// The arithmetic policies that `saturating` and `wrapping` generate `num_traits` impls for.
+const POLICIES: [&str; 2] = ["saturating", "wrapping"];
+/// The operations with a saturating and a wrapping `num_traits` trait.
+const POLICY_OPS: [&str; 3] = ["add", "sub", "mul"];
 
 pub(crate) fn derive_safe_math_ops(tokens: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as DeriveInput);
@@ -30,34 +45,44 @@ macro_rules! gen_impl {
 
 // Macro to generate extra_impls TokenStream2 based on checked operations
 macro_rules! gen_extra_impls {
-    ( $checked_ops:expr, $name_ident:ident, $( ($op_lit:literal, $trait:ident, $checked_method:ident, $use_or_else:expr, $err_expr:expr, $checked_trait:expr) ),* $(,)? ) => {{
+    ( $checked_ops:expr, $overrides:expr, $generics:expr, $name_ident:ident, $( ($op_lit:literal, $trait:ident, $checked_method:ident, $use_or_else:expr, $err_expr:expr, $checked_trait:ident, $extra_bounds:expr) ),* $(,)? ) => {{
         let mut impls = TokenStream2::new();
+        let (impl_generics, ty_generics, where_clause) = $generics.split_for_impl();
+        let predicates: Vec<_> = where_clause
+            .map(|w| w.predicates.iter().collect())
+            .unwrap_or_default();
         $(
             if $checked_ops.contains($op_lit) {
                 let fn_ident = format_ident!("safe_{}", $op_lit);
                 let trait_ident = syn::Ident::new(stringify!($trait), proc_macro2::Span::call_site());
                 let method_ident = syn::Ident::new(stringify!($checked_method), proc_macro2::Span::call_site());
-                if $use_or_else {
-                    impls.extend(quote! {
-                        #[diagnostic::do_not_recommend]
-                        impl ::safe_math::#trait_ident for #$name_ident where #$name_ident: $checked_trait {
-                            #[inline(always)]
-                            fn #fn_ident(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                                self.#method_ident(&rhs).ok_or_else(|| { $err_expr })
-                            }
-                        }
-                    });
+                let bounds = $extra_bounds;
+                // An overridden method replaces the `num_traits` trait entirely.
+                let (call, checked_bound) = match $overrides.get($op_lit) {
+                    Some(method) => (override_call(method), TokenStream2::new()),
+                    None => (
+                        quote! { <Self as ::safe_math::num_traits::$checked_trait>::#method_ident(&self, &rhs) },
+                        quote! { ::safe_math::num_traits::$checked_trait + },
+                    ),
+                };
+                let error = if $use_or_else {
+                    quote! { .ok_or_else(|| { $err_expr }) }
                 } else {
-                    impls.extend(quote! {
-                        #[diagnostic::do_not_recommend]
-                        impl ::safe_math::#trait_ident for #$name_ident where #$name_ident: $checked_trait {
-                            #[inline(always)]
-                            fn #fn_ident(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                                self.#method_ident(&rhs).ok_or({ $err_expr })
-                            }
+                    quote! { .ok_or({ $err_expr }) }
+                };
+                impls.extend(quote! {
+                    #[diagnostic::do_not_recommend]
+                    impl #impl_generics ::safe_math::#trait_ident for #$name_ident #ty_generics
+                    where
+                        #(#predicates,)*
+                        Self: #checked_bound #bounds
+                    {
+                        #[inline(always)]
+                        fn #fn_ident(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                            #call #error
                         }
-                    });
-                }
+                    }
+                });
             }
         )*
         impls
@@ -66,22 +91,98 @@ macro_rules! gen_extra_impls {
 
 fn expand_derive_safe_math_ops(input: DeriveInput) -> syn::Result<TokenStream2> {
     let mut checked_ops: HashSet<String> = HashSet::new();
+    let mut error_ty: Option<syn::Path> = None;
+    let mut by_ref = false;
+    let mut float = false;
+    let mut overrides: HashMap<String, syn::Path> = HashMap::new();
 
     for attr in &input.attrs {
         if attr.path().is_ident(SAFE_MATH_OPS_ATTRIBUTE_NAME) {
             match &attr.meta {
                 // Expect the form `#[SafeMathOps(add, sub, ...)]`
                 Meta::List(_) => {
-                    // Parse the comma-separated list of paths inside the attribute.
+                    // Parse the comma-separated list of operations (and `error = path`)
+                    // inside the attribute.
                     let parsed_args = attr.parse_args_with(
-                        syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+                        syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
                     )?;
 
-                    for arg in parsed_args {
+                    for meta in parsed_args {
+                        let arg = match meta {
+                            Meta::Path(path) => path,
+                            Meta::NameValue(nv) if nv.path.is_ident("error") => {
+                                let Expr::Path(path) = &nv.value else {
+                                    return Err(syn::Error::new_spanned(
+                                        &nv.value,
+                                        "Expected a type path, e.g. `error = crate::MyError`",
+                                    ));
+                                };
+                                if error_ty.replace(path.path.clone()).is_some() {
+                                    return Err(syn::Error::new_spanned(
+                                        nv,
+                                        "Duplicate `error` in `#[SafeMathOps]` attribute",
+                                    ));
+                                }
+                                continue;
+                            }
+                            Meta::NameValue(nv)
+                                if ["add", "sub", "mul", "div", "rem"]
+                                    .iter()
+                                    .any(|op| nv.path.is_ident(op)) =>
+                            {
+                                let Expr::Path(method) = &nv.value else {
+                                    return Err(syn::Error::new_spanned(
+                                        &nv.value,
+                                        "Expected the checked method, e.g. `add = checked_addition`",
+                                    ));
+                                };
+                                let op = nv.path.get_ident().unwrap().to_string();
+                                if !checked_ops.insert(op.clone()) {
+                                    return Err(syn::Error::new_spanned(
+                                        nv,
+                                        format!(
+                                            "Duplicate operation '{}' in `#[SafeMathOps]` attribute. \
+                                             Each operation should be listed only once.",
+                                            op
+                                        ),
+                                    ));
+                                }
+                                overrides.insert(op, method.path.clone());
+                                continue;
+                            }
+                            other => {
+                                return Err(syn::Error::new_spanned(
+                                    other,
+                                    "Expected a simple identifier (e.g. `add`), `op = method` or \
+                                     `error = path` inside `#[SafeMathOps]` attribute",
+                                ));
+                            }
+                        };
+                        if arg.is_ident("by_ref") {
+                            if by_ref {
+                                return Err(syn::Error::new_spanned(
+                                    arg,
+                                    "Duplicate `by_ref` in `#[SafeMathOps]` attribute",
+                                ));
+                            }
+                            by_ref = true;
+                            continue;
+                        }
+                        if arg.is_ident("float") {
+                            if float {
+                                return Err(syn::Error::new_spanned(
+                                    arg,
+                                    "Duplicate `float` in `#[SafeMathOps]` attribute",
+                                ));
+                            }
+                            float = true;
+                            continue;
+                        }
                         if let Some(ident) = arg.get_ident() {
                             let ident_str = ident.to_string();
                             match ident_str.as_str() {
-                                "add" | "sub" | "mul" | "div" | "rem" => {
+                                "add" | "sub" | "mul" | "div" | "rem" | "checked_sum" | "scale"
+                                | "cmp" | "saturating" | "wrapping" => {
                                     if !checked_ops.insert(ident_str.clone()) {
                                         return Err(syn::Error::new_spanned(
                                             arg,
@@ -132,6 +233,65 @@ fn expand_derive_safe_math_ops(input: DeriveInput) -> syn::Result<TokenStream2>
         ));
     }
 
+    if checked_ops.contains("checked_sum") && !checked_ops.contains("add") {
+        return Err(syn::Error::new_spanned(
+            &input,
+            "`checked_sum` in `#[SafeMathOps]` requires `add`, e.g. `#[SafeMathOps(add, checked_sum)]`",
+        ));
+    }
+
+    for policy in POLICIES {
+        if checked_ops.contains(policy) && !POLICY_OPS.iter().any(|op| checked_ops.contains(*op)) {
+            return Err(syn::Error::new_spanned(
+                &input,
+                format!(
+                    "`{policy}` in `#[SafeMathOps]` requires `add`, `sub` or `mul`, e.g. \
+                     `#[SafeMathOps(add, {policy})]`"
+                ),
+            ));
+        }
+    }
+
+    // Comparing borrows both values, so it is available with `by_ref` too.
+    let cmp_impl = if checked_ops.contains("cmp") {
+        expand_cmp(&input)?
+    } else {
+        TokenStream2::new()
+    };
+
+    if float {
+        if by_ref {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`float` in `#[SafeMathOps]` cannot be combined with `by_ref`",
+            ));
+        }
+        if !overrides.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`op = method` in `#[SafeMathOps]` cannot be combined with `float`",
+            ));
+        }
+        if let Some(policy) = POLICIES.into_iter().find(|p| checked_ops.contains(*p)) {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("`{policy}` in `#[SafeMathOps]` cannot be combined with `float`"),
+            ));
+        }
+    }
+
+    if by_ref {
+        if !overrides.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`op = method` in `#[SafeMathOps]` cannot be combined with `by_ref`",
+            ));
+        }
+        let mut tokens = expand_by_ref(&input, &checked_ops, error_ty.is_some())?;
+        tokens.extend(cmp_impl);
+        return Ok(tokens);
+    }
+
     let name = &input.ident;
 
     gen_impl!(
@@ -143,85 +303,553 @@ fn expand_derive_safe_math_ops(input: DeriveInput) -> syn::Result<TokenStream2>
         (rem_impl, safe_rem, SafeRem),
     );
     // Use macro to generate extra_impls
-    let extra_impls = gen_extra_impls!(
-        checked_ops,
-        name,
+    let extra_impls = if float {
+        expand_float_ops(&input, &checked_ops)?
+    } else {
+        gen_extra_impls!(
+            checked_ops,
+            overrides,
+            input.generics,
+            name,
+            (
+                "add",
+                SafeAdd,
+                checked_add,
+                false,
+                ::safe_math::SafeMathError::Overflow,
+                CheckedAdd,
+                quote! { ::core::marker::Copy + ::core::ops::Add<Output = Self> }
+            ),
+            (
+                "sub",
+                SafeSub,
+                checked_sub,
+                false,
+                ::safe_math::SafeMathError::Overflow,
+                CheckedSub,
+                quote! { ::core::marker::Copy + ::core::ops::Sub<Output = Self> }
+            ),
+            (
+                "mul",
+                SafeMul,
+                checked_mul,
+                false,
+                ::safe_math::SafeMathError::Overflow,
+                CheckedMul,
+                quote! { ::core::marker::Copy + ::core::ops::Mul<Output = Self> }
+            ),
+            (
+                "div",
+                SafeDiv,
+                checked_div,
+                true,
+                {
+                    if rhs == Self::default() {
+                        ::safe_math::SafeMathError::DivisionByZero
+                    } else {
+                        ::safe_math::SafeMathError::Overflow
+                    }
+                },
+                CheckedDiv,
+                quote! {
+                    ::core::marker::Copy
+                        + ::core::ops::Div<Output = Self>
+                        + ::core::default::Default
+                        + ::core::cmp::PartialEq
+                }
+            ),
+            (
+                "rem",
+                SafeRem,
+                checked_rem,
+                false,
+                ::safe_math::SafeMathError::DivisionByZero,
+                CheckedRem,
+                quote! { ::core::marker::Copy + ::core::ops::Rem<Output = Self> }
+            ),
+        )
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause
+        .map(|w| w.predicates.iter().collect())
+        .unwrap_or_default();
+
+    let sum_impl = if checked_ops.contains("checked_sum") {
+        let vis = &input.vis;
+        quote! {
+            impl #impl_generics #name #ty_generics
+            where
+                #(#predicates,)*
+                Self: ::safe_math::SafeAdd + ::safe_math::num_traits::Zero
+            {
+                /// Sums the items of `iter` starting from zero, stopping at the first overflow.
+                #[inline]
+                #vis fn checked_sum<I: ::core::iter::IntoIterator<Item = Self>>(
+                    iter: I,
+                ) -> Result<Self, ::safe_math::SafeMathError> {
+                    iter.into_iter()
+                        .try_fold(<Self as ::safe_math::num_traits::Zero>::zero(), ::safe_math::safe_add)
+                }
+            }
+        }
+    } else {
+        TokenStream2::new()
+    };
+
+    let error_impl = match &error_ty {
+        Some(error) => {
+            let vis = &input.vis;
+            let methods = [
+                ("add", "SafeAdd"),
+                ("sub", "SafeSub"),
+                ("mul", "SafeMul"),
+                ("div", "SafeDiv"),
+                ("rem", "SafeRem"),
+            ]
+            .into_iter()
+            .filter(|(op, _)| checked_ops.contains(*op))
+            .map(|(op, trait_name)| {
+                let method = format_ident!("safe_{}", op);
+                let trait_ident = format_ident!("{}", trait_name);
+                let doc = format!(
+                    "Like [`{trait_name}::{method}`](::safe_math::{trait_name}), \
+                     with the error converted into `{}`.",
+                    quote!(#error).to_string().replace(' ', "")
+                );
+                quote! {
+                    #[doc = #doc]
+                    #[inline(always)]
+                    #vis fn #method(self, rhs: Self) -> Result<Self, #error>
+                    where
+                        Self: ::safe_math::#trait_ident,
+                    {
+                        <Self as ::safe_math::#trait_ident>::#method(self, rhs)
+                            .map_err(::core::convert::Into::into)
+                    }
+                }
+            });
+            quote! {
+                impl #impl_generics #name #ty_generics
+                where
+                    #(#predicates,)*
+                    #error: ::core::convert::From<::safe_math::SafeMathError>
+                {
+                    #(#methods)*
+                }
+            }
+        }
+        None => TokenStream2::new(),
+    };
+
+    let scale_impl = if checked_ops.contains("scale") {
+        expand_scale(&input)?
+    } else {
+        TokenStream2::new()
+    };
+
+    let policy_impls = expand_policies(&input, &checked_ops)?;
+
+    let op_bounds = [
+        ("add", quote! { ::safe_math::SafeAdd }),
+        ("sub", quote! { ::safe_math::SafeSub }),
+        ("mul", quote! { ::safe_math::SafeMul }),
+        ("div", quote! { ::safe_math::SafeDiv }),
+        ("rem", quote! { ::safe_math::SafeRem }),
+    ]
+    .into_iter()
+    .filter(|(op, _)| checked_ops.contains(*op))
+    .map(|(_, bound)| bound);
+
+    // With all five operations, the blanket `SafeMathOps` impl of `safe_math` applies,
+    // and a second impl would conflict with it.
+    let all_ops = ["add", "sub", "mul", "div", "rem"]
+        .iter()
+        .all(|op| checked_ops.contains(*op));
+    let ops_impl = if all_ops {
+        TokenStream2::new()
+    } else {
+        quote! {
+            #[diagnostic::do_not_recommend]
+            impl #impl_generics ::safe_math::SafeMathOps for #name #ty_generics
+            where
+                #(#predicates,)*
+                Self: ::core::marker::Copy #(+ #op_bounds)*
+            {
+                #[inline(always)]
+                fn safe_add(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                    #add_impl
+                }
+
+                #[inline(always)]
+                fn safe_sub(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                    #sub_impl
+                }
+
+                #[inline(always)]
+                fn safe_mul(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                    #mul_impl
+                }
+
+                #[inline(always)]
+                fn safe_div(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                    #div_impl
+                }
+
+                #[inline(always)]
+                fn safe_rem(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                    #rem_impl
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #ops_impl
+        #extra_impls
+        #sum_impl
+        #scale_impl
+        #policy_impls
+        #cmp_impl
+        #error_impl
+    })
+}
+
+/// Calls the checked method given as `op = method`, which returns `Option<Self>`.
+///
+/// A bare identifier is called as a method, `self.method(rhs)`, so that it may take
+/// `self` by value or by reference. A path such as `Trait::method` is called as
+/// `Trait::method(self, rhs)`.
+fn override_call(method: &syn::Path) -> TokenStream2 {
+    match method.get_ident() {
+        Some(ident) => quote! { self.#ident(rhs) },
+        None => quote! { #method(self, rhs) },
+    }
+}
+
+/// Returns the first field of the struct `input`, which the `option` of `#[SafeMathOps]`
+/// (e.g. `scale`) expects to be `value`, with the number of fields.
+fn first_field<'a>(
+    input: &'a DeriveInput,
+    option: &str,
+    value: &str,
+) -> syn::Result<(Member, &'a syn::Type, usize)> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("`{option}` in `#[SafeMathOps]` is only supported on structs"),
+        ));
+    };
+    let Some(field) = data.fields.iter().next() else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!(
+                "`{option}` in `#[SafeMathOps]` requires a struct whose first field is {value}"
+            ),
+        ));
+    };
+    let member = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(0.into()),
+    };
+    Ok((member, &field.ty, data.fields.len()))
+}
+
+/// Generates the `Safe*` impls for `#[SafeMathOps(..., float)]`, for a struct whose
+/// first field is a float.
+///
+/// Floats have no `checked_*` methods: like the built-in float impls, the operation is
+/// performed with the standard operator, and fails if the first field of the result is
+/// infinite or NaN.
+fn expand_float_ops(
+    input: &DeriveInput,
+    checked_ops: &HashSet<String>,
+) -> syn::Result<TokenStream2> {
+    let (member, ty, _) = first_field(input, "float", "the float")?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause
+        .map(|w| w.predicates.iter().collect())
+        .unwrap_or_default();
+
+    let impls = [
+        ("add", "SafeAdd", "Add"),
+        ("sub", "SafeSub", "Sub"),
+        ("mul", "SafeMul", "Mul"),
+        ("div", "SafeDiv", "Div"),
+        ("rem", "SafeRem", "Rem"),
+    ]
+    .into_iter()
+    .filter(|(op, ..)| checked_ops.contains(*op))
+    .map(|(op, trait_name, op_trait)| {
+        let fn_ident = format_ident!("safe_{}", op);
+        let trait_ident = format_ident!("{}", trait_name);
+        let op_trait = format_ident!("{}", op_trait);
+        let op_method = format_ident!("{}", op);
+        quote! {
+            #[diagnostic::do_not_recommend]
+            impl #impl_generics ::safe_math::#trait_ident for #name #ty_generics
+            where
+                #(#predicates,)*
+                Self: ::core::marker::Copy + ::core::ops::#op_trait<Output = Self>,
+                #ty: ::safe_math::num_traits::Float
+            {
+                #[inline(always)]
+                fn #fn_ident(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
+                    let result = <Self as ::core::ops::#op_trait>::#op_method(self, rhs);
+                    if ::safe_math::num_traits::Float::is_finite(result.#member) {
+                        Ok(result)
+                    } else {
+                        Err(::safe_math::SafeMathError::InfiniteOrNaN)
+                    }
+                }
+            }
+        }
+    });
+    Ok(quote! { #(#impls)* })
+}
+
+/// Generates `SafeScale<F>` for a struct whose first field has type `F`.
+///
+/// The first field is multiplied by the scalar and any other fields (such as
+/// `PhantomData` unit markers) are copied over unchanged.
+fn expand_scale(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let (member, ty, len) = first_field(input, "scale", "the value to scale")?;
+    let name = &input.ident;
+    let rest = (len > 1).then(|| quote! { ..self });
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause
+        .map(|w| w.predicates.iter().collect())
+        .unwrap_or_default();
+
+    Ok(quote! {
+        impl #impl_generics ::safe_math::SafeScale<#ty> for #name #ty_generics
+        where
+            #(#predicates,)*
+            Self: ::core::marker::Copy,
+            #ty: ::safe_math::SafeMul
+        {
+            #[inline(always)]
+            fn safe_mul_scalar(self, k: #ty) -> Result<Self, ::safe_math::SafeMathError> {
+                Ok(Self {
+                    #member: ::safe_math::safe_mul(self.#member, k)?,
+                    #rest
+                })
+            }
+        }
+    })
+}
+
+/// Generates the `num_traits` `Saturating*`/`Wrapping*` impls of the listed `add`, `sub`
+/// and `mul` for `#[SafeMathOps(..., saturating)]` and `wrapping`, for a struct whose
+/// first field has such impls, so that the type also works inside `saturating!(..)` and
+/// `wrapping!(..)`.
+///
+/// Like `scale`, the operation is performed on the first field and any other fields are
+/// copied over unchanged.
+fn expand_policies(
+    input: &DeriveInput,
+    checked_ops: &HashSet<String>,
+) -> syn::Result<TokenStream2> {
+    let Some(policy) = POLICIES.into_iter().find(|p| checked_ops.contains(*p)) else {
+        return Ok(TokenStream2::new());
+    };
+    let (member, ty, len) = first_field(input, policy, "the value to operate on")?;
+    let name = &input.ident;
+    let rest = (len > 1).then(|| quote! { ..*self });
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause
+        .map(|w| w.predicates.iter().collect())
+        .unwrap_or_default();
+
+    let impls = POLICIES
+        .into_iter()
+        .filter(|policy| checked_ops.contains(*policy))
+        .flat_map(|policy| {
+            POLICY_OPS
+                .into_iter()
+                .zip(["Add", "Sub", "Mul"])
+                .filter(|(op, _)| checked_ops.contains(*op))
+                .map(move |(op, op_trait)| (policy, op, op_trait))
+        })
+        .map(|(policy, op, op_trait)| {
+            let trait_ident = match policy {
+                "saturating" => format_ident!("Saturating{}", op_trait),
+                _ => format_ident!("Wrapping{}", op_trait),
+            };
+            let method = format_ident!("{}_{}", policy, op);
+            let op_trait = format_ident!("{}", op_trait);
+            quote! {
+                impl #impl_generics ::safe_math::num_traits::#trait_ident for #name #ty_generics
+                where
+                    #(#predicates,)*
+                    Self: ::core::marker::Copy + ::core::ops::#op_trait<Output = Self>,
+                    #ty: ::safe_math::num_traits::#trait_ident
+                {
+                    #[inline(always)]
+                    fn #method(&self, v: &Self) -> Self {
+                        Self {
+                            #member: <#ty as ::safe_math::num_traits::#trait_ident>::#method(
+                                &self.#member,
+                                &v.#member,
+                            ),
+                            #rest
+                        }
+                    }
+                }
+            }
+        });
+    Ok(quote! { #(#impls)* })
+}
+
+/// Generates an inherent `safe_cmp` for `#[SafeMathOps(..., cmp)]`.
+///
+/// The fields are compared with `Ord::cmp` in declaration order, like a derived `Ord`,
+/// so that the comparison is total and never panics.
+fn expand_cmp(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`cmp` in `#[SafeMathOps]` is only supported on structs",
+        ));
+    };
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let members = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        });
+    let field_types = data.fields.iter().map(|field| &field.ty);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause
+        .map(|w| w.predicates.iter().collect())
+        .unwrap_or_default();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics
+        where
+            #(#predicates,)*
+            #(#field_types: ::core::cmp::Ord,)*
+        {
+            /// Compares the fields of `self` and `other` in declaration order.
+            #[inline]
+            #vis fn safe_cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ordering::Equal
+                    #(.then_with(|| ::core::cmp::Ord::cmp(&self.#members, &other.#members)))*
+            }
+        }
+    })
+}
+
+/// Generates `SafeMathOpsRef` for `#[SafeMathOps(..., by_ref)]`, which doesn't require `Copy`.
+///
+/// Only the operations themselves are supported: `checked_sum`, `scale`, `saturating`,
+/// `wrapping` and `error` generate by-value code.
+fn expand_by_ref(
+    input: &DeriveInput,
+    checked_ops: &HashSet<String>,
+    has_error: bool,
+) -> syn::Result<TokenStream2> {
+    if let Some(op) = ["checked_sum", "scale", "saturating", "wrapping"]
+        .into_iter()
+        .find(|op| checked_ops.contains(*op))
+    {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("`{op}` in `#[SafeMathOps]` cannot be combined with `by_ref`"),
+        ));
+    }
+    if has_error {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`error` in `#[SafeMathOps]` cannot be combined with `by_ref`",
+        ));
+    }
+
+    let ops = [
         (
             "add",
-            SafeAdd,
-            checked_add,
-            false,
-            ::safe_math::SafeMathError::Overflow,
-            ::num_traits::ops::checked::CheckedAdd
+            "CheckedAdd",
+            quote! { ::safe_math::SafeMathError::Overflow },
         ),
         (
             "sub",
-            SafeSub,
-            checked_sub,
-            false,
-            ::safe_math::SafeMathError::Overflow,
-            ::num_traits::ops::checked::CheckedSub
+            "CheckedSub",
+            quote! { ::safe_math::SafeMathError::Overflow },
         ),
         (
             "mul",
-            SafeMul,
-            checked_mul,
-            false,
-            ::safe_math::SafeMathError::Overflow,
-            ::num_traits::ops::checked::CheckedMul
+            "CheckedMul",
+            quote! { ::safe_math::SafeMathError::Overflow },
         ),
         (
             "div",
-            SafeDiv,
-            checked_div,
-            true,
-            {
-                if rhs == Self::default() {
+            "CheckedDiv",
+            quote! {
+                if *rhs == <Self as ::core::default::Default>::default() {
                     ::safe_math::SafeMathError::DivisionByZero
                 } else {
                     ::safe_math::SafeMathError::Overflow
                 }
             },
-            ::num_traits::ops::checked::CheckedDiv
         ),
         (
             "rem",
-            SafeRem,
-            checked_rem,
-            false,
-            ::safe_math::SafeMathError::DivisionByZero,
-            ::num_traits::ops::checked::CheckedRem
+            "CheckedRem",
+            quote! {
+                if *rhs == <Self as ::core::default::Default>::default() {
+                    ::safe_math::SafeMathError::DivisionByZero
+                } else {
+                    ::safe_math::SafeMathError::Overflow
+                }
+            },
         ),
-    );
-
-    Ok(quote! {
-        #[diagnostic::do_not_recommend]
-        impl ::safe_math::SafeMathOps for #name {
-            #[inline(always)]
-            fn safe_add(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                #add_impl
+    ];
+    let mut bounds = Vec::new();
+    let methods = ops.into_iter().map(|(op, checked_trait, err)| {
+        let method = format_ident!("safe_{}_ref", op);
+        let body = if checked_ops.contains(op) {
+            let checked_trait = format_ident!("{}", checked_trait);
+            let checked_method = format_ident!("checked_{}", op);
+            bounds.push(quote! { ::safe_math::num_traits::#checked_trait });
+            if op == "div" || op == "rem" {
+                bounds.push(quote! { ::core::default::Default + ::core::cmp::PartialEq });
             }
-
-            #[inline(always)]
-            fn safe_sub(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                #sub_impl
+            quote! {
+                <Self as ::safe_math::num_traits::#checked_trait>::#checked_method(self, rhs)
+                    .ok_or_else(|| #err)
             }
-
+        } else {
+            quote! { Err(::safe_math::SafeMathError::NotImplemented) }
+        };
+        quote! {
             #[inline(always)]
-            fn safe_mul(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                #mul_impl
+            fn #method(&self, rhs: &Self) -> Result<Self, ::safe_math::SafeMathError> {
+                #body
             }
+        }
+    });
+    let methods: Vec<_> = methods.collect();
 
-            #[inline(always)]
-            fn safe_div(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                #div_impl
-            }
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates: Vec<_> = where_clause
+        .map(|w| w.predicates.iter().collect())
+        .unwrap_or_default();
 
-            #[inline(always)]
-            fn safe_rem(self, rhs: Self) -> Result<Self, ::safe_math::SafeMathError> {
-                #rem_impl
-            }
+    Ok(quote! {
+        #[diagnostic::do_not_recommend]
+        impl #impl_generics ::safe_math::SafeMathOpsRef for #name #ty_generics
+        where
+            #(#predicates,)*
+            Self: ::core::marker::Sized #(+ #bounds)*
+        {
+            #(#methods)*
         }
-        #extra_impls
     })
 }
@@ -1,212 +1,404 @@
 #![forbid(unsafe_code)]
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use rewrite::{
+    rewrite_block, rewrite_block_macro, rewrite_expr, rewrite_typed_expr, safe_fn_for, Flags, Mode,
+};
 use syn::{
-    fold::{self, Fold},
-    parse_macro_input,
-    spanned::Spanned,
-    BinOp, Expr, ExprBinary, ItemFn,
+    parse::Parser, punctuated::Punctuated, spanned::Spanned, Attribute, Block, Expr, ImplItem,
+    Item, ItemFn, ItemMod, Meta, Signature, Stmt, Token, TraitItem, TraitItemFn,
 };
 #[cfg(feature = "derive")]
 mod derive;
-
-// Global counter for generating unique variable names
-static TEMP_VAR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+mod rewrite;
 
 #[proc_macro_attribute]
-pub fn safe_math(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut input_fn = parse_macro_input!(item as ItemFn);
-    let orig_block = *input_fn.block;
+pub fn safe_math(attr: TokenStream, item: TokenStream) -> TokenStream {
+    SafeMathArgs::parse(attr.into())
+        .and_then(|args| expand_safe_math(&args, item.into()))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    // ensure that the fn has a return type
-    let return_type = match &input_fn.sig.output {
-        syn::ReturnType::Type(_, ty) => ty,
-        syn::ReturnType::Default => {
-            return syn::Error::new(input_fn.sig.output.span(), "Function must return a Result")
-                .to_compile_error()
-                .into();
-        }
-    };
+fn expand_safe_math(args: &SafeMathArgs, item: TokenStream2) -> syn::Result<TokenStream2> {
+    if let Ok(mut input_fn) = syn::parse2::<ItemFn>(item.clone()) {
+        check_not_const(&input_fn.sig)?;
+        *input_fn.block = rewrite_fn(&input_fn.sig, *input_fn.block, args)?;
+        return Ok(quote! { #input_fn });
+    }
 
-    // ensure that the return type is a Result
-    let is_result = match &**return_type {
-        syn::Type::Path(type_path) => {
-            let segments = &type_path.path.segments;
-            segments
-                .last()
-                .map(|seg| seg.ident == "Result")
-                .unwrap_or(false)
+    if let Ok(mut input_mod) = syn::parse2::<ItemMod>(item.clone()) {
+        if let Some((_, items)) = &mut input_mod.content {
+            rewrite_items(items, args)?;
         }
-        _ => false,
-    };
+        return Ok(quote! { #input_mod });
+    }
 
-    if !is_result {
-        return syn::Error::new(return_type.span(), "Function must return a Result")
-            .to_compile_error()
-            .into();
+    // Methods declared inside a trait may come without a body, which `ItemFn`
+    // rejects. Only provided (default) methods have anything to rewrite.
+    let mut trait_fn: TraitItemFn = syn::parse2(item)?;
+    if let Some(block) = trait_fn.default.take() {
+        trait_fn.default = Some(rewrite_fn(&trait_fn.sig, block, args)?);
     }
+    Ok(quote! { #trait_fn })
+}
 
-    let new_block = MathRewriter.fold_block(orig_block);
-    input_fn.block = Box::new(new_block);
-    TokenStream::from(quote! { #input_fn })
+fn rewrite_fn(sig: &Signature, block: Block, args: &SafeMathArgs) -> syn::Result<Block> {
+    let mode = effective_mode(sig, args.mode);
+    check_return_type(sig, &block, mode)?;
+    rewrite_block(block, mode, args.flags)
 }
 
-#[proc_macro]
-pub fn safe_math_block(input: TokenStream) -> TokenStream {
-    let expression = parse_macro_input!(input as syn::Expr);
-    let rewritten_expr = MathRewriter.fold_expr(expression);
-    TokenStream::from(quote! { #rewritten_expr })
-}
-
-/// Generates a unique variable name that is extremely unlikely to collide
-/// with user-defined variables
-fn generate_unique_temp_var() -> syn::Ident {
-    let counter = TEMP_VAR_COUNTER.fetch_add(1, Ordering::SeqCst);
-    // Use a very distinctive prefix that users are unlikely to use
-    // Include the counter to ensure uniqueness across multiple macro invocations
-    format_ident!(
-        "__safe_math_temp_ref_{}_{}",
-        std::process::id(), // Process ID for uniqueness across processes
-        counter             // Counter for uniqueness within process
-    )
-}
-
-pub(crate) struct MathRewriter;
-
-impl Fold for MathRewriter {
-    fn fold_expr(&mut self, expr: Expr) -> Expr {
-        match expr {
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::Add(_),
-                right,
-                ..
-            }) => {
-                let left = self.fold_expr(*left);
-                let right = self.fold_expr(*right);
-                syn::parse_quote! { ::safe_math::safe_add(#left, #right)? }
-            }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::Sub(_),
-                right,
-                ..
-            }) => {
-                let left = self.fold_expr(*left);
-                let right = self.fold_expr(*right);
-                syn::parse_quote! { ::safe_math::safe_sub(#left, #right)? }
-            }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::Mul(_),
-                right,
-                ..
-            }) => {
-                let left = self.fold_expr(*left);
-                let right = self.fold_expr(*right);
-                syn::parse_quote! { ::safe_math::safe_mul(#left, #right)? }
-            }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::Div(_),
-                right,
-                ..
-            }) => {
-                let left = self.fold_expr(*left);
-                let right = self.fold_expr(*right);
-                syn::parse_quote! { ::safe_math::safe_div(#left, #right)? }
+/// Rewrites the functions of an inline module: free functions, methods of `impl` blocks,
+/// provided trait methods, and those of nested inline modules.
+///
+/// `const fn`s can't be rewritten and are left alone, as are functions with a
+/// `#[safe_math]` attribute of their own, which is expanded with its own arguments.
+/// The content of `mod foo;` declarations isn't part of the input, so it can't be reached.
+fn rewrite_items(items: &mut [Item], args: &SafeMathArgs) -> syn::Result<()> {
+    for item in items {
+        match item {
+            Item::Fn(item_fn) => {
+                rewrite_module_fn(&item_fn.attrs, &item_fn.sig, &mut item_fn.block, args)?
             }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::Rem(_),
-                right,
-                ..
-            }) => {
-                let left = self.fold_expr(*left);
-                let right = self.fold_expr(*right);
-                syn::parse_quote! { ::safe_math::safe_rem(#left, #right)? }
-            }
-            // Handle compound assignments by transforming them to regular assignments
-            // to avoid double evaluation of the left-hand side
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::AddAssign(_),
-                right,
-                ..
-            }) => {
-                let right = self.fold_expr(*right);
-                let temp_var = generate_unique_temp_var();
-                syn::parse_quote! {
-                    {
-                        let #temp_var = &mut #left;
-                        *#temp_var = ::safe_math::safe_add(*#temp_var, #right)?;
+            Item::Impl(item_impl) => {
+                for impl_item in &mut item_impl.items {
+                    if let ImplItem::Fn(method) = impl_item {
+                        rewrite_module_fn(&method.attrs, &method.sig, &mut method.block, args)?;
                     }
                 }
             }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::SubAssign(_),
-                right,
-                ..
-            }) => {
-                let right = self.fold_expr(*right);
-                let temp_var = generate_unique_temp_var();
-                syn::parse_quote! {
+            Item::Trait(item_trait) => {
+                for trait_item in &mut item_trait.items {
+                    if let TraitItem::Fn(TraitItemFn {
+                        attrs,
+                        sig,
+                        default: Some(block),
+                        ..
+                    }) = trait_item
                     {
-                        let #temp_var = &mut #left;
-                        *#temp_var = ::safe_math::safe_sub(*#temp_var, #right)?;
+                        rewrite_module_fn(attrs, sig, block, args)?;
                     }
                 }
             }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::MulAssign(_),
-                right,
+            Item::Mod(ItemMod {
+                content: Some((_, items)),
                 ..
-            }) => {
-                let right = self.fold_expr(*right);
-                let temp_var = generate_unique_temp_var();
-                syn::parse_quote! {
-                    {
-                        let #temp_var = &mut #left;
-                        *#temp_var = ::safe_math::safe_mul(*#temp_var, #right)?;
-                    }
+            }) => rewrite_items(items, args)?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites a function of a `#[safe_math]` module, unless it is skipped (see
+/// [`rewrite_items`]).
+///
+/// In the checked mode, functions that can't report an error, such as `#[test]`s or
+/// helpers returning a plain value, are left alone rather than rejected.
+fn rewrite_module_fn(
+    attrs: &[Attribute],
+    sig: &Signature,
+    block: &mut Block,
+    args: &SafeMathArgs,
+) -> syn::Result<()> {
+    if sig.constness.is_some() || has_safe_math_attr(attrs) {
+        return Ok(());
+    }
+    if effective_mode(sig, args.mode) == Mode::Checked && !returns_result(sig) {
+        return Ok(());
+    }
+    *block = rewrite_fn(sig, block.clone(), args)?;
+    Ok(())
+}
+
+/// Returns whether `sig` returns a `Result`, or an alias named like one (`io::Result`).
+fn returns_result(sig: &Signature) -> bool {
+    fn is_result(ty: &syn::Type) -> bool {
+        match ty {
+            syn::Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident.to_string().ends_with("Result")),
+            syn::Type::Paren(paren) => is_result(&paren.elem),
+            _ => false,
+        }
+    }
+    matches!(&sig.output, syn::ReturnType::Type(_, ty) if is_result(ty))
+}
+
+fn has_safe_math_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "safe_math")
+    })
+}
+
+/// Rejects `const fn`, whose body can't call the (generic, trait-based) `safe_*`
+/// helpers: the errors reported after the rewrite would point at the generated code.
+fn check_not_const(sig: &Signature) -> syn::Result<()> {
+    match &sig.constness {
+        Some(constness) => Err(syn::Error::new(
+            constness.span(),
+            "`#[safe_math]` can't be used on a `const fn`, as the checked operations aren't \
+             `const`: call the `const fn` helpers of `safe_math::konst` instead, e.g. \
+             `konst::u32::safe_add(a, b)`",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// In the checked mode, functions returning an `Option` report failures as `None`.
+fn effective_mode(sig: &Signature, mode: Mode) -> Mode {
+    match &sig.output {
+        syn::ReturnType::Type(_, ty) if mode == Mode::Checked && is_option(ty) => Mode::Optional,
+        _ => mode,
+    }
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => {
+            type_path.qself.is_none()
+                && type_path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Option")
+        }
+        syn::Type::Paren(paren) => is_option(&paren.elem),
+        _ => false,
+    }
+}
+
+fn check_return_type(sig: &Signature, block: &Block, mode: Mode) -> syn::Result<()> {
+    // Only the checked modes propagate errors with `?`, so only they need a `Result`
+    // (or an `Option`, as detected by `effective_mode`)
+    match mode {
+        Mode::Checked => {}
+        Mode::Optional => return check_tail_expr(block, mode),
+        _ => return Ok(()),
+    }
+
+    // FFI entry points usually can't return a `Result`, so point at the modes that
+    // don't need one instead
+    let message = if sig.abi.is_some() {
+        "Function must return a Result, which `extern` functions usually can't: \
+         use `#[safe_math(mode = panic)]`, or `safe_math_block!(saturating!(..))` in a \
+         function without `#[safe_math]`"
+    } else {
+        "Function must return a Result"
+    };
+
+    // ensure that the fn has a return type
+    let return_type = match &sig.output {
+        syn::ReturnType::Type(_, ty) => ty,
+        syn::ReturnType::Default => {
+            return Err(syn::Error::new(sig.output.span(), message));
+        }
+    };
+
+    // Only reject return types that can never carry an error. Anything else
+    // (`Result` aliases, `impl Future<Output = Result<..>>`, ...) is accepted and
+    // the generated `?` is left to type-check against what the body returns.
+    if !can_hold_result(return_type) {
+        return Err(syn::Error::new(return_type.span(), message));
+    }
+    check_tail_expr(block, mode)
+}
+
+/// Rejects arithmetic as the tail expression of the body: it is the return value,
+/// which must be a `Result` (or an `Option`), and the type mismatch reported after the
+/// rewrite would point at the generated code rather than at the expression.
+fn check_tail_expr(block: &Block, mode: Mode) -> syn::Result<()> {
+    let Some(Stmt::Expr(Expr::Binary(binary), None)) = block.stmts.last() else {
+        return Ok(());
+    };
+    let message = match (safe_fn_for(&binary.op), mode) {
+        (Some((_, false)), Mode::Optional) => {
+            "The return value must be an Option, wrap this expression in `Some(..)`"
+        }
+        (Some((_, false)), _) => {
+            "The return value must be a Result, wrap this expression in `Ok(..)`"
+        }
+        (Some((_, true)), Mode::Optional) => {
+            "This assignment evaluates to `()`, not to an Option: add `;` and return `Some(())`"
+        }
+        (Some((_, true)), _) => {
+            "This assignment evaluates to `()`, not to a Result: add `;` and return `Ok(())`"
+        }
+        (None, _) => return Ok(()),
+    };
+    Err(syn::Error::new_spanned(binary, message))
+}
+
+fn can_hold_result(ty: &syn::Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64", "bool", "char", "str", "String",
+    ];
+    match ty {
+        syn::Type::Path(type_path) => !type_path
+            .path
+            .get_ident()
+            .is_some_and(|ident| PRIMITIVES.iter().any(|p| ident == p)),
+        syn::Type::Paren(paren) => can_hold_result(&paren.elem),
+        syn::Type::Tuple(_) | syn::Type::Never(_) => false,
+        _ => true,
+    }
+}
+
+#[proc_macro]
+pub fn safe_math_block(input: TokenStream) -> TokenStream {
+    rewrite_block_macro(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Evaluates an expression with checked operations like `safe_math_block!`, performing
+/// every operation in the type given after the `;`: `safe!(a + b; u32)`.
+#[proc_macro]
+pub fn safe(input: TokenStream) -> TokenStream {
+    rewrite_typed_expr(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Evaluates an expression like `#[safe_math(mode = panic)]`: it yields the value of
+/// the expression, and panics naming the failing operation otherwise.
+#[proc_macro]
+pub fn safe_assert_no_overflow(input: TokenStream) -> TokenStream {
+    rewrite_expr(input.into(), Mode::Panic)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Arguments accepted by `#[safe_math(...)]`.
+struct SafeMathArgs {
+    mode: Mode,
+    /// `signed_sub`, `checked_narrowing`, `float_rem_by_zero` and `debug_wrap`.
+    flags: Flags,
+}
+
+/// Names of the modes accepted by `mode = ..`, given the enabled features.
+fn supported_modes() -> Vec<&'static str> {
+    let mut modes = vec!["checked", "panic", "panic_verbose"];
+    if cfg!(feature = "analysis") {
+        modes.push("analysis");
+    }
+    if cfg!(feature = "strict") {
+        modes.push("strict");
+    }
+    modes
+}
+
+impl SafeMathArgs {
+    fn parse(attr: TokenStream2) -> syn::Result<Self> {
+        let mut args = SafeMathArgs {
+            mode: Mode::Checked,
+            flags: Flags::default(),
+        };
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(attr)?;
+        for meta in metas {
+            match &meta {
+                Meta::NameValue(nv) if nv.path.is_ident("mode") => {
+                    let mode = match &nv.value {
+                        Expr::Path(p) => p.path.get_ident().map(|ident| ident.to_string()),
+                        _ => None,
+                    };
+                    args.mode =
+                        match mode.as_deref() {
+                            Some("checked") => Mode::Checked,
+                            Some("panic") => Mode::Panic,
+                            Some("panic_verbose") => Mode::PanicVerbose,
+                            #[cfg(feature = "analysis")]
+                            Some("analysis") => Mode::Analysis,
+                            #[cfg(not(feature = "analysis"))]
+                            Some("analysis") => return Err(syn::Error::new_spanned(
+                                &nv.value,
+                                "`mode = analysis` requires the `analysis` feature of `safe_math`",
+                            )),
+                            #[cfg(feature = "strict")]
+                            Some("strict") => Mode::Strict,
+                            #[cfg(not(feature = "strict"))]
+                            Some("strict") => {
+                                return Err(syn::Error::new_spanned(
+                                    &nv.value,
+                                    "`mode = strict` requires the `strict` feature of `safe_math`",
+                                ))
+                            }
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    &nv.value,
+                                    format!(
+                                        "Unknown `#[safe_math]` mode. Supported modes are: {}.",
+                                        supported_modes().join(", ")
+                                    ),
+                                ))
+                            }
+                        };
                 }
-            }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::DivAssign(_),
-                right,
-                ..
-            }) => {
-                let right = self.fold_expr(*right);
-                let temp_var = generate_unique_temp_var();
-                syn::parse_quote! {
-                    {
-                        let #temp_var = &mut #left;
-                        *#temp_var = ::safe_math::safe_div(*#temp_var, #right)?;
-                    }
+                Meta::Path(path) if path.is_ident("signed_sub") => args.flags.signed_sub = true,
+                Meta::Path(path) if path.is_ident("checked_narrowing") => {
+                    args.flags.checked_narrowing = true
                 }
-            }
-            Expr::Binary(ExprBinary {
-                left,
-                op: BinOp::RemAssign(_),
-                right,
-                ..
-            }) => {
-                let right = self.fold_expr(*right);
-                let temp_var = generate_unique_temp_var();
-                syn::parse_quote! {
-                    {
-                        let #temp_var = &mut #left;
-                        *#temp_var = ::safe_math::safe_rem(*#temp_var, #right)?;
-                    }
+                Meta::Path(path) if path.is_ident("float_rem_by_zero") => {
+                    args.flags.float_rem_by_zero = true
+                }
+                Meta::Path(path) if path.is_ident("debug_wrap") => args.flags.debug_wrap = true,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &meta,
+                        "Unknown `#[safe_math]` argument, expected e.g. `mode = panic`",
+                    ))
                 }
             }
-            _ => fold::fold_expr(self, expr),
         }
+        // There is no analysis or strict counterpart of `safe_sub_signed`, `safe_cast`
+        // or `safe_rem_nonzero`, and those modes never report an error to carry the
+        // `debug_wrap` value in.
+        const CHECKED_AND_PANIC: &[Mode] = &[Mode::Checked, Mode::Panic, Mode::PanicVerbose];
+        let flags = [
+            (args.flags.signed_sub, "signed_sub", CHECKED_AND_PANIC),
+            (
+                args.flags.checked_narrowing,
+                "checked_narrowing",
+                CHECKED_AND_PANIC,
+            ),
+            (
+                args.flags.float_rem_by_zero,
+                "float_rem_by_zero",
+                CHECKED_AND_PANIC,
+            ),
+            (args.flags.debug_wrap, "debug_wrap", CHECKED_AND_PANIC),
+        ];
+        for (set, name, allowed_modes) in flags {
+            if set && !allowed_modes.contains(&args.mode) {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!("`{name}` is only supported in the checked and panic modes"),
+                ));
+            }
+        }
+        // `debug_wrap` only has wrapping counterparts of the basic helpers.
+        let conflicts = [
+            (args.flags.signed_sub, "signed_sub"),
+            (args.flags.float_rem_by_zero, "float_rem_by_zero"),
+        ];
+        for (set, name) in conflicts {
+            if args.flags.debug_wrap && set {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    format!("`debug_wrap` cannot be combined with `{name}`"),
+                ));
+            }
+        }
+        Ok(args)
     }
 }
 
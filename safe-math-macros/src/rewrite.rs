@@ -0,0 +1,1185 @@
+//! The arithmetic rewrite shared by `#[safe_math]` and `safe_math_block!`.
+//!
+//! Everything here works on `proc_macro2`/`syn` types rather than on
+//! `proc_macro::TokenStream`, so the rewrite can be exercised (and its output
+//! compared token by token) outside of a macro expansion.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use syn::{
+    fold::{self, Fold},
+    parse::{ParseStream, Parser},
+    punctuated::{Pair, Punctuated},
+    spanned::Spanned,
+    BinOp, Block, Expr, ExprBinary, ExprMacro, ExprRepeat, GenericArgument, ItemConst, ItemStatic,
+    Lit, LitInt, Local, Macro, Pat, Stmt, StmtMacro, Token, Type,
+};
+
+// Global counter for generating unique variable names
+static TEMP_VAR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How a failing operation is reported by the rewritten code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    /// Propagate the `SafeMathError` with `?` (default).
+    Checked,
+    /// Propagate failures as `None` with `.ok()?`: the checked mode of functions
+    /// returning an `Option`.
+    Optional,
+    /// Panic at the location of the failing operation (`mode = panic`).
+    Panic,
+    /// Panic like `Panic`, with the operands in the message (`mode = panic_verbose`).
+    PanicVerbose,
+    /// Count the failure and carry on with the unchecked result (`mode = analysis`).
+    #[cfg(feature = "analysis")]
+    Analysis,
+    /// Panic through the `strict_*` integer methods (`mode = strict`).
+    #[cfg(feature = "strict")]
+    Strict,
+}
+
+/// Arithmetic policy selected for a subtree by a `saturating!(..)`/`wrapping!(..)` marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Policy {
+    Saturating,
+    Wrapping,
+}
+
+impl Policy {
+    /// Returns the policy selected by `mac`, if it is one of the marker macros.
+    fn from_marker(mac: &syn::Macro) -> Option<Self> {
+        if mac.path.is_ident("saturating") {
+            Some(Policy::Saturating)
+        } else if mac.path.is_ident("wrapping") {
+            Some(Policy::Wrapping)
+        } else {
+            None
+        }
+    }
+
+    /// Prefix of the `safe_math` helpers implementing the policy.
+    fn prefix(self) -> &'static str {
+        match self {
+            Policy::Saturating => "saturating",
+            Policy::Wrapping => "wrapping",
+        }
+    }
+}
+
+/// Returns whether `mac` is the `unchecked!(..)` marker, whose contents are left as is.
+fn is_unchecked_marker(mac: &syn::Macro) -> bool {
+    mac.path.is_ident("unchecked")
+}
+
+/// Standard macros whose arguments are a comma-separated list of expressions, and
+/// are therefore rewritten. Other macros are left alone, as their input need not be
+/// made of runtime expressions (e.g. `stringify!`).
+const EXPR_LIST_MACROS: &[&str] = &[
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "eprint",
+    "eprintln",
+    "format",
+    "panic",
+    "print",
+    "println",
+    "vec",
+    "write",
+    "writeln",
+];
+
+/// Parses the arguments of `vec![elem; n]`.
+fn parse_repeat(input: ParseStream) -> syn::Result<(Expr, Token![;], Expr)> {
+    Ok((input.parse()?, input.parse()?, input.parse()?))
+}
+
+/// Optional rewrites enabled by the flags of `#[safe_math(...)]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Flags {
+    /// Whether `a - b` is rewritten to `safe_sub_signed(a, b)`, which yields the
+    /// difference of two unsigned operands in the wider signed type (`signed_sub`).
+    /// Compound `-=` keeps the regular subtraction, as it can't change the type of
+    /// its place.
+    pub(crate) signed_sub: bool,
+    /// Whether the initializer of `let x: u8 = ..;` (any primitive integer type) is
+    /// passed through `safe_cast`, so that a wider value fails instead of needing a
+    /// truncating `as` (`checked_narrowing`).
+    pub(crate) checked_narrowing: bool,
+    /// Whether `%` and `%=` call `safe_rem_nonzero`, which reports a zero float
+    /// divisor as `DivisionByZero` rather than `InfiniteOrNaN` (`float_rem_by_zero`).
+    pub(crate) float_rem_by_zero: bool,
+    /// Whether the operations call the `debug_wrap_*` helpers, whose overflow errors
+    /// carry the wrapped result (`debug_wrap`).
+    pub(crate) debug_wrap: bool,
+}
+
+/// Rewrites every arithmetic operation in `block`, with the optional rewrites of `flags`.
+pub(crate) fn rewrite_block(block: Block, mode: Mode, flags: Flags) -> syn::Result<Block> {
+    let mut rewriter = MathRewriter::new(mode);
+    rewriter.flags = flags;
+    let block = rewriter.fold_block(block);
+    rewriter.finish(block)
+}
+
+/// Parses `tokens` as an expression and rewrites every arithmetic operation in it.
+pub(crate) fn rewrite_expr(tokens: TokenStream2, mode: Mode) -> syn::Result<TokenStream2> {
+    let expr: Expr = syn::parse2(tokens)?;
+    let mut rewriter = MathRewriter::new(mode);
+    let expr = rewriter.fold_expr(expr);
+    rewriter.finish(expr.into_token_stream())
+}
+
+/// Rewrites the input of `safe_math_block!`: an expression, optionally preceded by a
+/// policy applying to the whole expression, as in `safe_math_block!(saturating, { .. })`.
+pub(crate) fn rewrite_block_macro(tokens: TokenStream2) -> syn::Result<TokenStream2> {
+    let parser = |input: ParseStream| {
+        let policy = if input.peek(syn::Ident) && input.peek2(Token![,]) {
+            let ident: syn::Ident = input.parse()?;
+            let policy =
+                match ident.to_string().as_str() {
+                    "saturating" => Policy::Saturating,
+                    "wrapping" => Policy::Wrapping,
+                    _ => return Err(syn::Error::new_spanned(
+                        ident,
+                        "Unknown `safe_math_block!` policy, expected `saturating` or `wrapping`",
+                    )),
+                };
+            input.parse::<Token![,]>()?;
+            Some(policy)
+        } else {
+            None
+        };
+        Ok((policy, input.parse::<Expr>()?))
+    };
+    let (policy, expr) = parser.parse2(tokens)?;
+    let mut rewriter = MathRewriter::new(Mode::Checked);
+    rewriter.policy = policy;
+    let expr = rewriter.fold_expr(expr);
+    // Like a marker, in parentheses (see `fold_marker`). They also keep `unused_braces`
+    // from flagging the braces of `safe_math_block!(saturating, { a + b })`.
+    let output = match policy {
+        Some(_) => quote! { (#expr) },
+        None => expr.into_token_stream(),
+    };
+    rewriter.finish(output)
+}
+
+/// Rewrites the input of `safe!`: an expression and, after a `;`, the type every
+/// operation in it is performed in, as in `safe!(a + b; u32)`.
+pub(crate) fn rewrite_typed_expr(tokens: TokenStream2) -> syn::Result<TokenStream2> {
+    let (expr, _, ty) = parse_typed_expr.parse2(tokens)?;
+    let mut rewriter = MathRewriter::new(Mode::Checked);
+    rewriter.ty = Some(ty);
+    let expr = rewriter.fold_expr(expr);
+    rewriter.finish(expr.into_token_stream())
+}
+
+fn parse_typed_expr(input: ParseStream) -> syn::Result<(Expr, Token![;], Type)> {
+    Ok((input.parse()?, input.parse()?, input.parse()?))
+}
+
+/// Generates a unique variable name that is extremely unlikely to collide
+/// with user-defined variables
+fn generate_unique_temp_var() -> syn::Ident {
+    let counter = TEMP_VAR_COUNTER.fetch_add(1, Ordering::SeqCst);
+    // Use a very distinctive prefix that users are unlikely to use
+    // Include the counter to ensure uniqueness across multiple macro invocations
+    format_ident!(
+        "__safe_math_temp_ref_{}_{}",
+        std::process::id(), // Process ID for uniqueness across processes
+        counter             // Counter for uniqueness within process
+    )
+}
+
+/// Maps an operator to the `safe_math` helper implementing it and whether it is
+/// a compound assignment (`+=`, `-=`, ...).
+pub(crate) fn safe_fn_for(op: &BinOp) -> Option<(&'static str, bool)> {
+    match op {
+        BinOp::Add(_) => Some(("safe_add", false)),
+        BinOp::Sub(_) => Some(("safe_sub", false)),
+        BinOp::Mul(_) => Some(("safe_mul", false)),
+        BinOp::Div(_) => Some(("safe_div", false)),
+        BinOp::Rem(_) => Some(("safe_rem", false)),
+        BinOp::AddAssign(_) => Some(("safe_add", true)),
+        BinOp::SubAssign(_) => Some(("safe_sub", true)),
+        BinOp::MulAssign(_) => Some(("safe_mul", true)),
+        BinOp::DivAssign(_) => Some(("safe_div", true)),
+        BinOp::RemAssign(_) => Some(("safe_rem", true)),
+        _ => None,
+    }
+}
+
+/// Returns the range of an integer type given by its literal suffix, as
+/// `(is_signed, min, max)`.
+///
+/// `usize`/`isize` use their 64-bit range: anything overflowing it overflows on
+/// 32-bit targets as well. A result that fits it may still overflow there, so
+/// `fold_literals` keeps those in the 32-bit range.
+fn int_range(suffix: &str) -> Option<(bool, i128, u128)> {
+    Some(match suffix {
+        "u8" => (false, 0, u8::MAX as u128),
+        "u16" => (false, 0, u16::MAX as u128),
+        "u32" => (false, 0, u32::MAX as u128),
+        "u64" | "usize" => (false, 0, u64::MAX as u128),
+        "u128" => (false, 0, u128::MAX),
+        "i8" => (true, i8::MIN as i128, i8::MAX as u128),
+        "i16" => (true, i16::MIN as i128, i16::MAX as u128),
+        "i32" => (true, i32::MIN as i128, i32::MAX as u128),
+        "i64" | "isize" => (true, i64::MIN as i128, i64::MAX as u128),
+        "i128" => (true, i128::MIN, i128::MAX as u128),
+        _ => return None,
+    })
+}
+
+/// Returns whether `expr` is made of number literals only, e.g. `1 << 40` or `-(2 + 3)`.
+///
+/// Its type is inferred from the context, which `safe_cast` would turn into the `i32`
+/// fallback, and rustc already rejects such a value if it doesn't fit.
+fn is_literal_only(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(lit) => matches!(lit.lit, Lit::Int(_) | Lit::Float(_)),
+        Expr::Unary(unary) => is_literal_only(&unary.expr),
+        Expr::Paren(paren) => is_literal_only(&paren.expr),
+        Expr::Group(group) => is_literal_only(&group.expr),
+        Expr::Binary(binary) => is_literal_only(&binary.left) && is_literal_only(&binary.right),
+        _ => false,
+    }
+}
+
+fn int_lit(expr: &Expr) -> Option<&LitInt> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => Some(int),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Evaluates an operation on two integer literals whose type is known from a suffix,
+/// such as `60u32 * 60`. Anything else is left to the runtime check.
+///
+/// Returns the suffix, and the decimal result or why the operation always fails.
+fn eval_literals(binary: &ExprBinary) -> Option<(String, Result<String, String>)> {
+    let (a, b) = (int_lit(&binary.left)?, int_lit(&binary.right)?);
+    let suffix = match (a.suffix(), b.suffix()) {
+        ("", "") => return None,
+        (s, "") | ("", s) => s.to_owned(),
+        (s1, s2) if s1 == s2 => s1.to_owned(),
+        // Mismatched types are already a type error.
+        _ => return None,
+    };
+    let (signed, min, max) = int_range(&suffix)?;
+    let (a, b) = (
+        a.base10_parse::<u128>().ok()?,
+        b.base10_parse::<u128>().ok()?,
+    );
+    if a > max || b > max {
+        // Out-of-range literals are reported by rustc itself.
+        return None;
+    }
+
+    if b == 0 && matches!(binary.op, BinOp::Div(_) | BinOp::Rem(_)) {
+        return Some((suffix, Err("division by zero".to_owned())));
+    }
+    // Both operands are non-negative, so `/` and `%` can't overflow.
+    let result = if signed {
+        let (a, b) = (a as i128, b as i128);
+        let result = match binary.op {
+            BinOp::Add(_) => a.checked_add(b),
+            BinOp::Sub(_) => a.checked_sub(b),
+            BinOp::Mul(_) => a.checked_mul(b),
+            BinOp::Div(_) => Some(a / b),
+            BinOp::Rem(_) => Some(a % b),
+            _ => return None,
+        };
+        result
+            .filter(|&r| r >= min && r <= max as i128)
+            .map(|r| r.to_string())
+    } else {
+        let result = match binary.op {
+            BinOp::Add(_) => a.checked_add(b),
+            BinOp::Sub(_) => a.checked_sub(b),
+            BinOp::Mul(_) => a.checked_mul(b),
+            BinOp::Div(_) => Some(a / b),
+            BinOp::Rem(_) => Some(a % b),
+            _ => return None,
+        };
+        result.filter(|&r| r <= max).map(|r| r.to_string())
+    };
+    let result = result.ok_or_else(|| format!("the result overflows `{suffix}`"));
+    Some((suffix, result))
+}
+
+/// Detects operations on two integer literals that fail whatever the inputs,
+/// such as `255u8 + 1u8` or `1u8 / 0`.
+///
+/// Only literal pairs whose type is known from a suffix are checked; anything
+/// else is left to the runtime check. Returns the error to report at compile time.
+fn literal_overflow(binary: &ExprBinary) -> Option<syn::Error> {
+    let (_, Err(reason)) = eval_literals(binary)? else {
+        return None;
+    };
+    let text = binary.to_token_stream().to_string();
+    Some(syn::Error::new_spanned(
+        binary,
+        format!("`{text}` always fails: {reason}"),
+    ))
+}
+
+/// Folds an operation on two integer literals that can't fail, such as `60u32 * 60`,
+/// into the literal of its result (see `eval_literals`).
+fn fold_literals(binary: &ExprBinary) -> Option<Expr> {
+    let (suffix, Ok(value)) = eval_literals(binary)? else {
+        return None;
+    };
+    // Only fold what fits on every target.
+    let fits = match suffix.as_str() {
+        "usize" => value.parse::<u32>().is_ok(),
+        "isize" => value.parse::<i32>().is_ok(),
+        _ => true,
+    };
+    if !fits {
+        return None;
+    }
+    let span = binary.span();
+    let literal = LitInt::new(&format!("{}{suffix}", value.trim_start_matches('-')), span);
+    Some(if value.starts_with('-') {
+        syn::parse_quote_spanned! {span=> -#literal }
+    } else {
+        syn::parse_quote_spanned! {span=> #literal }
+    })
+}
+
+/// Returns the other operand if the operation `func` does nothing and can't fail, i.e.
+/// adds the integer literal `0` or multiplies by `1` (on either side), such as `x + 0`.
+fn identity_operand(func: &str, binary: &ExprBinary) -> Option<Expr> {
+    let identity = match func {
+        "safe_add" => "0",
+        "safe_mul" => "1",
+        _ => return None,
+    };
+    let is_identity =
+        |operand: &Expr| int_lit(operand).is_some_and(|lit| lit.base10_digits() == identity);
+    if is_identity(&binary.right) {
+        Some(*binary.left.clone())
+    } else if is_identity(&binary.left) {
+        Some(*binary.right.clone())
+    } else {
+        None
+    }
+}
+
+/// Rewrites arithmetic operators into calls to the `safe_math` helpers.
+///
+/// In the default mode every rewritten operation ends with `?`, so a failure returns
+/// from the innermost enclosing function, closure or `async` block. In particular an
+/// error inside a `for`/`while`/`loop` body (or in a range bound) leaves the whole
+/// function rather than skipping to the next iteration.
+///
+/// A compound assignment `place op= rhs` evaluates `rhs` with its own checks, then
+/// `place` once, and only then the checked `op`: an error in `rhs` is reported before
+/// (and instead of) one in `op`, and `place` is left unchanged on any error.
+///
+/// Index expressions are rewritten like any other operand, so in `v[i + 1]` the
+/// checked `i + 1` fails with `Overflow` before the indexing (and its bounds check)
+/// takes place.
+///
+/// Within a `saturating!(..)` or `wrapping!(..)` marker, `+`, `-` and `*` (and their
+/// compound assignments) use that policy instead and cannot fail, while `/` and `%`
+/// keep the behavior of the mode. The contents of an `unchecked!(..)` marker are not
+/// rewritten at all. The marker itself is removed in both cases.
+///
+/// The arguments of the standard formatting, assertion and `vec!` macros (see
+/// `EXPR_LIST_MACROS`) are rewritten too. Other macros are left unchanged.
+///
+/// Const contexts are left unchanged, as neither `?` nor the helpers can be used
+/// there: types (including array lengths such as `[u8; N + 1]`), const generic
+/// arguments, the length of `[x; N + 1]`, `const { .. }` blocks and `const`/`static`
+/// items.
+///
+/// Operations on integer literals that can only fail (see `literal_overflow`) are
+/// collected as errors and reported by [`MathRewriter::finish`]. Those that can't fail
+/// are folded into the literal of their result (see `fold_literals`), and `x + 0` or
+/// `x * 1` into `x` (see `identity_operand`), without any check.
+pub(crate) struct MathRewriter {
+    mode: Mode,
+    /// Policy of the innermost enclosing marker, if any.
+    policy: Option<Policy>,
+    flags: Flags,
+    /// Type the helpers are called with, as in `safe_add::<u32>(a, b)`, for `safe!`.
+    ty: Option<Type>,
+    errors: Option<syn::Error>,
+}
+
+impl MathRewriter {
+    pub(crate) fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            policy: None,
+            flags: Flags::default(),
+            ty: None,
+            errors: None,
+        }
+    }
+
+    /// Returns `output`, or the errors found while rewriting it.
+    pub(crate) fn finish<T>(self, output: T) -> syn::Result<T> {
+        match self.errors {
+            Some(errors) => Err(errors),
+            None => Ok(output),
+        }
+    }
+
+    fn push_error(&mut self, error: syn::Error) {
+        match &mut self.errors {
+            Some(errors) => errors.combine(error),
+            None => self.errors = Some(error),
+        }
+    }
+
+    /// Rewrites an operand of a rewritten operation. Its parentheses become redundant once
+    /// it is passed as an argument of a `safe_*` call, and would trip `unused_parens`.
+    fn fold_operand(&mut self, operand: Expr) -> Expr {
+        match self.fold_expr(operand) {
+            Expr::Paren(paren) if paren.attrs.is_empty() => *paren.expr,
+            operand => operand,
+        }
+    }
+
+    /// Rewrites the contents of a `saturating!(..)`/`wrapping!(..)` marker with `policy`,
+    /// dropping the marker itself.
+    fn fold_marker(&mut self, policy: Policy, marker: ExprMacro) -> Expr {
+        let inner: Expr = match marker.mac.parse_body() {
+            Ok(inner) => inner,
+            Err(error) => {
+                self.push_error(error);
+                return Expr::Macro(marker);
+            }
+        };
+        let outer = self.policy.replace(policy);
+        let inner = self.fold_expr(inner);
+        self.policy = outer;
+        syn::parse_quote! { (#inner) }
+    }
+
+    /// Strips an `unchecked!(..)` marker, leaving its contents unchanged.
+    fn unwrap_unchecked(&mut self, marker: ExprMacro) -> Expr {
+        match marker.mac.parse_body::<Expr>() {
+            Ok(inner) => syn::parse_quote! { (#inner) },
+            Err(error) => {
+                self.push_error(error);
+                Expr::Macro(marker)
+            }
+        }
+    }
+
+    /// Returns the policy of the enclosing marker if it applies to the helper `func`.
+    fn policy_for(&self, func: &str) -> Option<Policy> {
+        self.policy
+            .filter(|_| matches!(func, "safe_add" | "safe_sub" | "safe_mul"))
+    }
+
+    /// Returns the turbofish pinning the type of a helper call, if any.
+    fn turbofish(&self) -> TokenStream2 {
+        match &self.ty {
+            Some(ty) => quote! { ::<#ty> },
+            None => TokenStream2::new(),
+        }
+    }
+
+    /// Turns the operation `source`, performed by the `safe_*` helper `func` on `left`
+    /// and `right`, into the value expression used in its place.
+    fn checked_call(
+        &self,
+        func: &str,
+        left: TokenStream2,
+        right: TokenStream2,
+        source: &ExprBinary,
+    ) -> TokenStream2 {
+        if let Some(policy) = self.policy_for(func) {
+            // `safe_add` -> `saturating_add`, which cannot fail.
+            let func = format_ident!("{}_{}", policy.prefix(), func.trim_start_matches("safe_"));
+            let turbofish = self.turbofish();
+            return quote! { ::safe_math::#func #turbofish(#left, #right) };
+        }
+        let turbofish = self.turbofish();
+        let call = |func: syn::Ident| {
+            if self.mode == Mode::PanicVerbose {
+                // The operands are passed separately, to be shown if the helper fails.
+                let span = source.to_token_stream().span();
+                let text = source.to_token_stream().to_string();
+                let name = func.to_string();
+                return quote_spanned! {span=>
+                    ::safe_math::safe_expect_verbose(
+                        ::safe_math::#func #turbofish, #name, #left, #right, #text
+                    )
+                };
+            }
+            self.fallible(
+                quote! { ::safe_math::#func #turbofish(#left, #right) },
+                source,
+            )
+        };
+        match self.mode {
+            // `safe_add` -> `debug_wrap_add`, whose overflow error holds the wrapped result.
+            Mode::Checked | Mode::Optional | Mode::Panic | Mode::PanicVerbose
+                if self.flags.debug_wrap =>
+            {
+                call(format_ident!(
+                    "debug_wrap_{}",
+                    func.trim_start_matches("safe_")
+                ))
+            }
+            Mode::Checked | Mode::Optional | Mode::Panic | Mode::PanicVerbose => {
+                call(format_ident!("{}", func))
+            }
+            #[cfg(feature = "analysis")]
+            // `safe_add` -> `analyze_add`, which counts the failure instead of returning it.
+            Mode::Analysis => {
+                let func = format_ident!("analyze_{}", func.trim_start_matches("safe_"));
+                quote! { ::safe_math::#func #turbofish(#left, #right) }
+            }
+            #[cfg(feature = "strict")]
+            Mode::Strict => {
+                // `safe_add` -> `strict_add`, spanned like in the panic mode below.
+                let func = format_ident!("strict_{}", func.trim_start_matches("safe_"));
+                quote_spanned! {source.span()=> ::safe_math::#func #turbofish(#left, #right) }
+            }
+        }
+    }
+
+    /// Reports a failure of `call`, returning a `Result`, in the checked, optional or
+    /// panic modes. `source` is the code `call` stands for.
+    ///
+    /// Calls with a single operand, such as `safe_cast`, panic without showing it in
+    /// the verbose panic mode.
+    fn fallible(&self, call: TokenStream2, source: &dyn ToTokens) -> TokenStream2 {
+        match self.mode {
+            Mode::Checked => quote! { #call? },
+            Mode::Optional => quote! { #call.ok()? },
+            Mode::Panic | Mode::PanicVerbose => {
+                // Span the call to the original operation so that `#[track_caller]`
+                // reports the location of the overflowing operator.
+                let span = source.to_token_stream().span();
+                let text = source.to_token_stream().to_string();
+                quote_spanned! {span=> ::safe_math::safe_expect(#call, #text) }
+            }
+            #[cfg(any(feature = "analysis", feature = "strict"))]
+            _ => unreachable!("no fallible calls in the {:?} mode", self.mode),
+        }
+    }
+}
+
+/// Returns whether `ty` is a primitive integer type, e.g. `u8` or `isize`.
+fn is_int_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => {
+            type_path.qself.is_none()
+                && type_path
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| int_range(&ident.to_string()).is_some())
+        }
+        Type::Paren(paren) => is_int_type(&paren.elem),
+        Type::Group(group) => is_int_type(&group.elem),
+        _ => false,
+    }
+}
+
+impl Fold for MathRewriter {
+    // Types only hold const expressions, e.g. array lengths.
+    fn fold_type(&mut self, ty: Type) -> Type {
+        ty
+    }
+
+    fn fold_generic_argument(&mut self, arg: GenericArgument) -> GenericArgument {
+        arg
+    }
+
+    fn fold_macro(&mut self, mac: Macro) -> Macro {
+        let is_expr_list = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| EXPR_LIST_MACROS.iter().any(|name| segment.ident == name));
+        if !is_expr_list {
+            return mac;
+        }
+        let tokens = if let Ok(args) =
+            mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        {
+            let args: Punctuated<Expr, Token![,]> = args
+                .into_pairs()
+                .map(|pair| {
+                    let (arg, comma) = pair.into_tuple();
+                    Pair::new(self.fold_expr(arg), comma)
+                })
+                .collect();
+            args.into_token_stream()
+        } else if let Ok((elem, semi, len)) = mac.parse_body_with(parse_repeat) {
+            let (elem, len) = (self.fold_expr(elem), self.fold_expr(len));
+            quote! { #elem #semi #len }
+        } else {
+            return mac;
+        };
+        Macro { tokens, ..mac }
+    }
+
+    fn fold_item_const(&mut self, item: ItemConst) -> ItemConst {
+        item
+    }
+
+    fn fold_item_static(&mut self, item: ItemStatic) -> ItemStatic {
+        item
+    }
+
+    fn fold_local(&mut self, local: Local) -> Local {
+        let literal_only = local
+            .init
+            .as_ref()
+            .is_some_and(|init| is_literal_only(&init.expr));
+        let mut local = fold::fold_local(self, local);
+        let Pat::Type(pat) = &local.pat else {
+            return local;
+        };
+        if !self.flags.checked_narrowing || !is_int_type(&pat.ty) || literal_only {
+            return local;
+        }
+        if let Some(init) = &mut local.init {
+            let expr = &init.expr;
+            let call = quote! { ::safe_math::safe_cast(#expr) };
+            let value = self.fallible(call, expr);
+            init.expr = syn::parse_quote! { #value };
+        }
+        local
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        match stmt {
+            // A marker in statement position, e.g. `wrapping!(x += y);`.
+            Stmt::Macro(StmtMacro {
+                attrs,
+                mac,
+                semi_token,
+            }) if Policy::from_marker(&mac).is_some() || is_unchecked_marker(&mac) => {
+                let expr = self.fold_expr(Expr::Macro(ExprMacro { attrs, mac }));
+                Stmt::Expr(expr, semi_token)
+            }
+            stmt => fold::fold_stmt(self, stmt),
+        }
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        let binary = match expr {
+            Expr::Binary(binary) => binary,
+            Expr::Macro(marker) if is_unchecked_marker(&marker.mac) => {
+                return self.unwrap_unchecked(marker)
+            }
+            Expr::Macro(marker) => match Policy::from_marker(&marker.mac) {
+                Some(policy) => return self.fold_marker(policy, marker),
+                None => return fold::fold_expr(self, Expr::Macro(marker)),
+            },
+            // Const contexts: only the repeated operand is evaluated at runtime.
+            Expr::Repeat(repeat) => {
+                return Expr::Repeat(ExprRepeat {
+                    expr: Box::new(self.fold_expr(*repeat.expr)),
+                    ..repeat
+                })
+            }
+            expr @ Expr::Const(_) => return expr,
+            expr => return fold::fold_expr(self, expr),
+        };
+        let Some((func, is_assign)) = safe_fn_for(&binary.op) else {
+            return fold::fold_expr(self, Expr::Binary(binary));
+        };
+        let func = match func {
+            "safe_rem" if self.flags.float_rem_by_zero => "safe_rem_nonzero",
+            func => func,
+        };
+        if is_assign {
+            // Handle compound assignments by transforming them to regular assignments
+            // to avoid double evaluation of the left-hand side
+            // The place is rewritten too, e.g. the index in `v[i + 1] += x`.
+            // The right-hand side is evaluated before the place is borrowed, as for the
+            // built-in operators on primitives, so it may read the place (`x += x`) or
+            // borrow its parent (`self.total += self.bonus()`).
+            let left = self.fold_expr(*binary.left.clone());
+            let right = self.fold_operand(*binary.right.clone());
+            let temp_var = generate_unique_temp_var();
+            let rhs_var = generate_unique_temp_var();
+            let value =
+                self.checked_call(func, quote! { *#temp_var }, quote! { #rhs_var }, &binary);
+            syn::parse_quote! {
+                {
+                    let #rhs_var = #right;
+                    let #temp_var = &mut #left;
+                    *#temp_var = #value;
+                }
+            }
+        } else {
+            let func = match func {
+                "safe_sub" if self.flags.signed_sub && self.policy_for(func).is_none() => {
+                    "safe_sub_signed"
+                }
+                func => func,
+            };
+            if let Some(error) = literal_overflow(&binary)
+                .filter(|_| self.policy_for(func).is_none() && func != "safe_sub_signed")
+            {
+                self.push_error(error);
+            }
+            // Operations that can't fail don't need a check, e.g. `60u32 * 60` or `x + 0`.
+            if func != "safe_sub_signed" {
+                if let Some(literal) = fold_literals(&binary) {
+                    return literal;
+                }
+            }
+            if let Some(operand) = identity_operand(func, &binary) {
+                // Only a primitive integer can be added to `0` by the helpers, so the
+                // operand has the type of the result. The call keeps it a value rather
+                // than a place, as in `&mut (x + 0)`.
+                let operand = self.fold_operand(operand);
+                let turbofish = self.turbofish();
+                return syn::parse_quote! { ::core::convert::identity #turbofish(#operand) };
+            }
+            let left = self.fold_operand(*binary.left.clone());
+            let right = self.fold_operand(*binary.right.clone());
+            let value = self.checked_call(
+                func,
+                left.into_token_stream(),
+                right.into_token_stream(),
+                &binary,
+            );
+            syn::parse_quote! { #value }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewritten(tokens: TokenStream2) -> String {
+        rewrite_expr(tokens, Mode::Checked).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_binary_operators() {
+        assert_eq!(
+            rewritten(quote! { a + b }),
+            quote! { ::safe_math::safe_add(a, b)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { a - b }),
+            quote! { ::safe_math::safe_sub(a, b)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { a * b }),
+            quote! { ::safe_math::safe_mul(a, b)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { a / b }),
+            quote! { ::safe_math::safe_div(a, b)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { a % b }),
+            quote! { ::safe_math::safe_rem(a, b)? }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_nested_operators() {
+        assert_eq!(
+            rewritten(quote! { a + b * c }),
+            quote! { ::safe_math::safe_add(a, ::safe_math::safe_mul(b, c)?)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { f(a - 1) }),
+            quote! { f(::safe_math::safe_sub(a, 1)?) }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { fetch(a + 1).await * b }),
+            quote! { ::safe_math::safe_mul(fetch(::safe_math::safe_add(a, 1)?).await, b)? }
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_operand_parentheses_dropped() {
+        assert_eq!(
+            rewritten(quote! { (a + b) / (c) }),
+            quote! { ::safe_math::safe_div(::safe_math::safe_add(a, b)?, c)? }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_policy_markers() {
+        assert_eq!(
+            rewritten(quote! { a + saturating!(b * c / d) }),
+            quote! {
+                ::safe_math::safe_add(
+                    a,
+                    ::safe_math::safe_div(::safe_math::saturating_mul(b, c), d)?
+                )?
+            }
+            .to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { a + unchecked!(b * saturating!(c * d)) }),
+            quote! { ::safe_math::safe_add(a, b * saturating!(c * d))? }.to_string()
+        );
+        // Literals that always overflow are fine when the overflow is intended.
+        assert_eq!(
+            rewritten(quote! { wrapping!(255u8 + 1u8) }),
+            quote! { (::safe_math::wrapping_add(255u8, 1u8)) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_operations_that_cannot_fail() {
+        assert_eq!(
+            rewritten(quote! { 60u32 * 60 }),
+            quote! { 3600u32 }.to_string()
+        );
+        assert_eq!(rewritten(quote! { 2i8 - 7i8 }), quote! { -5i8 }.to_string());
+        assert_eq!(
+            rewritten(quote! { 256usize * 256 }),
+            quote! { 65536usize }.to_string()
+        );
+        // Pointer-sized results are only folded if they fit on 32-bit targets.
+        assert_eq!(
+            rewritten(quote! { 65536usize * 65536 }),
+            quote! { ::safe_math::safe_mul(65536usize, 65536)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { 2147483647isize + 1 }),
+            quote! { ::safe_math::safe_add(2147483647isize, 1)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { 7u8 / 2 % 2 }),
+            quote! { ::safe_math::safe_rem(3u8, 2)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { x + 0 }),
+            quote! { ::core::convert::identity(x) }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { 1u8 * (a + b) }),
+            quote! { ::core::convert::identity(::safe_math::safe_add(a, b)?) }.to_string()
+        );
+        // Unsuffixed literals and other identities are still checked.
+        assert_eq!(
+            rewritten(quote! { 2 + 2 }),
+            quote! { ::safe_math::safe_add(2, 2)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { x - 0 }),
+            quote! { ::safe_math::safe_sub(x, 0)? }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { x * 1.0 }),
+            quote! { ::safe_math::safe_mul(x, 1.0)? }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_typed_expr() {
+        assert_eq!(
+            rewrite_typed_expr(quote! { a + b * 2; u32 })
+                .unwrap()
+                .to_string(),
+            quote! {
+                ::safe_math::safe_add::<u32>(a, ::safe_math::safe_mul::<u32>(b, 2)?)?
+            }
+            .to_string()
+        );
+        assert_eq!(
+            rewrite_typed_expr(quote! { x.into() + 0; u64 })
+                .unwrap()
+                .to_string(),
+            quote! { ::core::convert::identity::<u64>(x.into()) }.to_string()
+        );
+        assert!(rewrite_typed_expr(quote! { a + b }).is_err());
+    }
+
+    #[test]
+    fn test_block_macro_policy() {
+        assert_eq!(
+            rewrite_block_macro(quote! { saturating, { a + b / c } })
+                .unwrap()
+                .to_string(),
+            quote! { ({ ::safe_math::saturating_add(a, ::safe_math::safe_div(b, c)?) }) }
+                .to_string()
+        );
+        assert_eq!(
+            rewrite_block_macro(quote! { a * b }).unwrap().to_string(),
+            quote! { ::safe_math::safe_mul(a, b)? }.to_string()
+        );
+        let error = rewrite_block_macro(quote! { clamping, a * b }).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown `safe_math_block!` policy, expected `saturating` or `wrapping`"
+        );
+    }
+
+    #[test]
+    fn test_other_operators_untouched() {
+        assert_eq!(
+            rewritten(quote! { a << b & c == d }),
+            quote! { a << b & c == d }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_nested_wrappers() {
+        // The `?` goes on each operation, never on the wrapping `Ok`.
+        assert_eq!(
+            rewritten(quote! { Ok((Some(a + b), c * d)) }),
+            quote! {
+                Ok((
+                    Some(::safe_math::safe_add(a, b)?),
+                    ::safe_math::safe_mul(c, d)?
+                ))
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment() {
+        let out = rewritten(quote! { x += 1 });
+        assert!(out.contains("__safe_math_temp_ref_"), "{out}");
+        assert!(out.contains(":: safe_math :: safe_add"), "{out}");
+    }
+
+    #[test]
+    fn test_macro_arguments() {
+        let out = rewritten(quote! { println!("{} {x}", a + b, x = c * d) });
+        assert!(out.contains(":: safe_math :: safe_add (a , b) ?"), "{out}");
+        assert!(
+            out.contains("x = :: safe_math :: safe_mul (c , d) ?"),
+            "{out}"
+        );
+        let out = rewritten(quote! { std::vec![a + b; n - 1] });
+        assert!(
+            out.contains("; :: safe_math :: safe_sub (n , 1) ?"),
+            "{out}"
+        );
+        assert_eq!(
+            rewritten(quote! { stringify!(a + b) }),
+            "stringify ! (a + b)"
+        );
+    }
+
+    #[test]
+    fn test_float_rem_by_zero() {
+        let mut rewriter = MathRewriter::new(Mode::Checked);
+        rewriter.flags.float_rem_by_zero = true;
+        let out = rewriter
+            .fold_expr(syn::parse_quote! { { x %= a % b; a / b } })
+            .into_token_stream()
+            .to_string();
+        assert!(
+            out.contains(":: safe_math :: safe_rem_nonzero (a , b) ?"),
+            "{out}"
+        );
+        assert!(
+            out.contains(":: safe_math :: safe_rem_nonzero (* "),
+            "{out}"
+        );
+        assert!(out.contains(":: safe_math :: safe_div (a , b) ?"), "{out}");
+    }
+
+    #[test]
+    fn test_signed_sub() {
+        let mut rewriter = MathRewriter::new(Mode::Checked);
+        rewriter.flags.signed_sub = true;
+        let out = rewriter
+            .fold_expr(syn::parse_quote! { { x -= a - b; wrapping!(a - b) } })
+            .into_token_stream()
+            .to_string();
+        assert!(
+            out.contains(":: safe_math :: safe_sub_signed (a , b) ?"),
+            "{out}"
+        );
+        assert!(out.contains(":: safe_math :: safe_sub (* "), "{out}");
+        assert!(
+            out.contains(":: safe_math :: wrapping_sub (a , b)"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_debug_wrap() {
+        let mut rewriter = MathRewriter::new(Mode::Checked);
+        rewriter.flags.debug_wrap = true;
+        let expr: Expr = syn::parse_quote! { a + wrapping!(b * c) };
+        let out = rewriter.fold_expr(expr).into_token_stream().to_string();
+        assert!(out.contains(":: safe_math :: debug_wrap_add (a ,"), "{out}");
+        // A policy still takes precedence.
+        assert!(
+            out.contains(":: safe_math :: wrapping_mul (b , c)"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_checked_narrowing() {
+        let mut rewriter = MathRewriter::new(Mode::Checked);
+        rewriter.flags.checked_narrowing = true;
+        let block: Block = syn::parse_quote! {{
+            let a: u8 = big;
+            let b: Vec<u8> = v;
+            let c = big;
+            let d: u64 = 1 << 40;
+        }};
+        let out = rewriter.fold_block(block).into_token_stream().to_string();
+        assert!(
+            out.contains("let a : u8 = :: safe_math :: safe_cast (big) ?"),
+            "{out}"
+        );
+        assert!(out.contains("let b : Vec < u8 > = v ;"), "{out}");
+        assert!(out.contains("let c = big ;"), "{out}");
+        assert!(out.contains("let d : u64 = 1 << 40 ;"), "{out}");
+    }
+
+    #[test]
+    fn test_const_contexts() {
+        let out = rewritten(quote! { [a + b; N + 1] });
+        assert!(out.ends_with("; N + 1]"), "{out}");
+        assert!(out.contains(":: safe_math :: safe_add (a , b)"), "{out}");
+        assert_eq!(
+            rewritten(quote! { f::<{ N + 1 }>(const { N * 2 }) }),
+            "f :: < { N + 1 } > (const { N * 2 })"
+        );
+    }
+
+    #[cfg(feature = "analysis")]
+    #[test]
+    fn test_analysis_mode() {
+        let out = rewrite_expr(quote! { a * b % c }, Mode::Analysis)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            out,
+            quote! { ::safe_math::analyze_rem(::safe_math::analyze_mul(a, b), c) }.to_string()
+        );
+    }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn test_strict_mode() {
+        let out = rewrite_expr(quote! { a - b / c }, Mode::Strict)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            out,
+            quote! { ::safe_math::strict_sub(a, ::safe_math::strict_div(b, c)) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_optional_mode() {
+        let out = rewrite_expr(quote! { a + b }, Mode::Optional)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            out,
+            quote! { ::safe_math::safe_add(a, b).ok()? }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_panic_mode() {
+        let out = rewrite_expr(quote! { a + b }, Mode::Panic)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            out,
+            quote! { ::safe_math::safe_expect(::safe_math::safe_add(a, b), "a + b") }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_panic_verbose_mode() {
+        let out = rewrite_expr(quote! { a + b }, Mode::PanicVerbose)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            out,
+            quote! {
+                ::safe_math::safe_expect_verbose(::safe_math::safe_add, "safe_add", a, b, "a + b")
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_struct_literals() {
+        assert_eq!(
+            rewritten(quote! { Point { x: a + b, ..base } }),
+            quote! { Point { x: ::safe_math::safe_add(a, b)?, ..base } }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { Point { x, ..f(a * 2) } }),
+            quote! { Point { x, ..f(::safe_math::safe_mul(a, 2)?) } }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_break_values() {
+        assert_eq!(
+            rewritten(quote! { loop { break 'outer acc * 2; } }),
+            quote! { loop { break 'outer ::safe_math::safe_mul(acc, 2)?; } }.to_string()
+        );
+        assert_eq!(
+            rewritten(quote! { loop { break; } }),
+            quote! { loop { break; } }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(rewrite_expr(quote! { let }, Mode::Checked).is_err());
+    }
+
+    fn literal_error(tokens: TokenStream2) -> Option<String> {
+        rewrite_expr(tokens, Mode::Checked)
+            .err()
+            .map(|err| err.to_string())
+    }
+
+    #[test]
+    fn test_literal_overflow() {
+        assert_eq!(
+            literal_error(quote! { 255u8 + 1u8 }).as_deref(),
+            Some("`255u8 + 1u8` always fails: the result overflows `u8`")
+        );
+        assert!(literal_error(quote! { 0u32 - 1 }).is_some());
+        assert!(literal_error(quote! { 100i8 * 2 }).is_some());
+        assert!(literal_error(quote! { 2i8 - 127 - 5 }).is_none());
+        assert_eq!(
+            literal_error(quote! { 1u8 / 0 }).as_deref(),
+            Some("`1u8 / 0` always fails: division by zero")
+        );
+        assert!(literal_error(quote! { f(7i64 % 0i64) }).is_some());
+    }
+
+    #[test]
+    fn test_literal_in_range() {
+        assert!(literal_error(quote! { 254u8 + 1u8 }).is_none());
+        assert!(literal_error(quote! { 0i8 - 127 }).is_none());
+        // Out-of-range literals and unary minus are left to rustc.
+        assert!(literal_error(quote! { 0 - 200i8 }).is_none());
+        assert!(literal_error(quote! { -128i8 - 1 }).is_none());
+        // Without a suffix the type is only known after inference.
+        assert!(literal_error(quote! { 255 + 1 }).is_none());
+        assert!(literal_error(quote! { x + 1u8 }).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+//! Overflow counting for `#[safe_math(mode = analysis)]`.
+//!
+//! This module is only available when the `analysis` feature is enabled. In the
+//! analysis mode every rewritten operation is still checked, but a failure does not
+//! change the control flow: it increments a thread-local counter and the operation
+//! yields the value the unchecked operator would have produced in a release build
+//! (the wrapped result for integers, infinity or NaN for floats). Integer division
+//! and remainder by zero, which have no such value, yield zero.
+//!
+//! This makes it possible to run a computation over a whole corpus (e.g. from a fuzzer)
+//! and measure how often it would have failed, with [`overflow_count`].
+
+use crate::impls::{safe_add, safe_div, safe_mul, safe_rem, safe_sub};
+use crate::ops::{SafeAdd, SafeDiv, SafeMul, SafeRem, SafeSub};
+use std::cell::Cell;
+
+thread_local! {
+    static OVERFLOW_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns the number of operations that failed on this thread in the analysis mode.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{overflow_count, reset_overflow_count, safe_math};
+///
+/// #[safe_math(mode = analysis)]
+/// fn add(a: u8, b: u8) -> u8 {
+///     a + b
+/// }
+///
+/// reset_overflow_count();
+/// assert_eq!(add(255, 2), 1); // wrapped
+/// assert_eq!(add(1, 2), 3);
+/// assert_eq!(overflow_count(), 1);
+/// ```
+pub fn overflow_count() -> usize {
+    OVERFLOW_COUNT.with(Cell::get)
+}
+
+/// Resets the counter of [`overflow_count`] to zero, returning its previous value.
+pub fn reset_overflow_count() -> usize {
+    OVERFLOW_COUNT.with(|count| count.replace(0))
+}
+
+/// Counts a failed operation.
+#[cold]
+fn record_overflow() {
+    OVERFLOW_COUNT.with(|count| count.set(count.get() + 1));
+}
+
+/// Unchecked arithmetic used as the result of a failed operation in the analysis mode.
+///
+/// Implemented for all primitive integer and floating-point types.
+pub trait WrappingOps: Copy {
+    /// Wrapping addition.
+    fn wrapped_add(self, rhs: Self) -> Self;
+    /// Wrapping subtraction.
+    fn wrapped_sub(self, rhs: Self) -> Self;
+    /// Wrapping multiplication.
+    fn wrapped_mul(self, rhs: Self) -> Self;
+    /// Wrapping division, zero for a zero divisor.
+    fn wrapped_div(self, rhs: Self) -> Self;
+    /// Wrapping remainder, zero for a zero divisor.
+    fn wrapped_rem(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_wrapping_ops_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl WrappingOps for $t {
+                #[inline(always)]
+                fn wrapped_add(self, rhs: Self) -> Self {
+                    self.wrapping_add(rhs)
+                }
+                #[inline(always)]
+                fn wrapped_sub(self, rhs: Self) -> Self {
+                    self.wrapping_sub(rhs)
+                }
+                #[inline(always)]
+                fn wrapped_mul(self, rhs: Self) -> Self {
+                    self.wrapping_mul(rhs)
+                }
+                #[inline(always)]
+                fn wrapped_div(self, rhs: Self) -> Self {
+                    if rhs == 0 { 0 } else { self.wrapping_div(rhs) }
+                }
+                #[inline(always)]
+                fn wrapped_rem(self, rhs: Self) -> Self {
+                    if rhs == 0 { 0 } else { self.wrapping_rem(rhs) }
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapping_ops_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_wrapping_ops_float {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl WrappingOps for $t {
+                #[inline(always)]
+                fn wrapped_add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+                #[inline(always)]
+                fn wrapped_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+                #[inline(always)]
+                fn wrapped_mul(self, rhs: Self) -> Self {
+                    self * rhs
+                }
+                #[inline(always)]
+                fn wrapped_div(self, rhs: Self) -> Self {
+                    self / rhs
+                }
+                #[inline(always)]
+                fn wrapped_rem(self, rhs: Self) -> Self {
+                    self % rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapping_ops_float!(f32, f64);
+
+macro_rules! impl_analyze_ops {
+    ($($fn_name:ident => ($safe:ident, $trait:ident, $wrapped:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Performs ", $desc, ", counting a failure instead of reporting it.")]
+            ///
+            /// Used internally by `#[safe_math(mode = analysis)]`.
+            ///
+            /// # Returns
+            ///
+            #[doc = concat!("The result of [`", stringify!($safe), "`](crate::", stringify!($safe), ") on success, otherwise")]
+            #[doc = concat!("`a.", stringify!($wrapped), "(b)` (see [`WrappingOps`]) after incrementing [`overflow_count`].")]
+            #[inline(always)]
+            pub fn $fn_name<T: $trait + WrappingOps>(a: T, b: T) -> T {
+                match $safe(a, b) {
+                    Ok(value) => value,
+                    Err(_) => {
+                        record_overflow();
+                        a.$wrapped(b)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_analyze_ops!(
+    analyze_add => (safe_add, SafeAdd, wrapped_add, "addition"),
+    analyze_sub => (safe_sub, SafeSub, wrapped_sub, "subtraction"),
+    analyze_mul => (safe_mul, SafeMul, wrapped_mul, "multiplication"),
+    analyze_div => (safe_div, SafeDiv, wrapped_div, "division"),
+    analyze_rem => (safe_rem, SafeRem, wrapped_rem, "remainder"),
+);
@@ -0,0 +1,79 @@
+//! Accumulation of errors across independent safe operations.
+//!
+//! `?` stops at the first failure, which is the right behavior for a single
+//! computation but not for validation pipelines, where every failing field should
+//! be reported at once. [`SafeBatch`] collects the errors of many independent
+//! operations instead.
+
+use crate::error::{SafeMathError, SafeMathResult};
+
+/// Collects the errors of several independent safe operations.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_add, safe_div, safe_mul, SafeBatch, SafeMathError};
+///
+/// let mut batch = SafeBatch::new();
+/// let total = batch.try_add(safe_add(200u8, 100));
+/// let ratio = batch.try_add(safe_div(10u8, 0));
+/// let area = batch.try_add(safe_mul(3u8, 4));
+///
+/// assert_eq!((total, ratio, area), (None, None, Some(12)));
+/// assert_eq!(
+///     batch.into_result(),
+///     Err(vec![SafeMathError::Overflow, SafeMathError::DivisionByZero])
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SafeBatch {
+    errors: Vec<SafeMathError>,
+}
+
+impl SafeBatch {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome of an operation.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(value)` - If `res` is `Ok`
+    /// * `None` - If `res` is `Err`; the error is stored in the batch
+    pub fn try_add<T>(&mut self, res: SafeMathResult<T>) -> Option<T> {
+        match res {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.errors.push(err);
+                None
+            }
+        }
+    }
+
+    /// Returns the errors recorded so far, in the order they were added.
+    pub fn errors(&self) -> &[SafeMathError] {
+        &self.errors
+    }
+
+    /// Returns the number of errors recorded so far.
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no error has been recorded.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Consumes the batch, returning `Ok(())` if every operation succeeded or
+    /// all recorded errors otherwise.
+    pub fn into_result(self) -> Result<(), Vec<SafeMathError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+}
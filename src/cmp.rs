@@ -0,0 +1,82 @@
+//! Ordering of values whose computation may have overflowed.
+//!
+//! Comparing `a * b` with `c * d` normally requires widening both products by
+//! hand. The helpers here compare the checked results directly, treating an
+//! overflowed value as larger than any representable one.
+
+use crate::error::{SafeMathError, SafeMathResult};
+use crate::impls::safe_mul;
+use crate::ops::SafeMul;
+use core::cmp::Ordering;
+use num_traits::Unsigned;
+
+/// Compares two checked results, treating `Overflow` as saturated toward +∞.
+///
+/// This is only meaningful when the computations can overflow upward alone, as
+/// with additions and multiplications of unsigned values.
+///
+/// # Arguments
+///
+/// * `a` - Left-hand result.
+/// * `b` - Right-hand result.
+///
+/// # Returns
+///
+/// * `Ok(ordering)` - If both results are `Ok`, or exactly one of them is `Err(Overflow)`
+///   (it compares greater than the other)
+/// * `Err(SafeMathError::Overflow)` - If both overflowed, as their order is unknown
+/// * `Err(err)` - Any other error of `a`, then of `b`, even if the other one overflowed
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_add, safe_cmp, SafeMathError};
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(safe_cmp(safe_add(1u8, 2), safe_add(2u8, 2)), Ok(Ordering::Less));
+/// assert_eq!(safe_cmp(safe_add(255u8, 1), safe_add(2u8, 2)), Ok(Ordering::Greater));
+/// assert_eq!(
+///     safe_cmp(safe_add(255u8, 1), safe_add(255u8, 2)),
+///     Err(SafeMathError::Overflow)
+/// );
+/// ```
+pub fn safe_cmp<T: Ord>(a: SafeMathResult<T>, b: SafeMathResult<T>) -> SafeMathResult<Ordering> {
+    match (a, b) {
+        (Ok(a), Ok(b)) => Ok(a.cmp(&b)),
+        // Errors other than overflow take precedence: they carry no ordering at all.
+        (Err(err), _) | (_, Err(err)) if err != SafeMathError::Overflow => Err(err),
+        (Err(_), Ok(_)) => Ok(Ordering::Greater),
+        (Ok(_), Err(_)) => Ok(Ordering::Less),
+        (Err(_), Err(_)) => Err(SafeMathError::Overflow),
+    }
+}
+
+/// Compares the products `a * b` and `c * d` of unsigned integers.
+///
+/// A product that overflows compares greater than one that doesn't. See [`safe_cmp`].
+///
+/// # Arguments
+///
+/// * `a`, `b` - Factors of the left-hand product.
+/// * `c`, `d` - Factors of the right-hand product.
+///
+/// # Returns
+///
+/// * `Ok(ordering)` - The ordering of `a * b` with respect to `c * d`
+/// * `Err(SafeMathError::Overflow)` - If both products overflow
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::safe_cmp_products;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(safe_cmp_products(6u8, 7, 5, 8), Ok(Ordering::Greater));
+/// assert_eq!(safe_cmp_products(16u8, 16, 15, 17), Ok(Ordering::Greater));
+/// ```
+pub fn safe_cmp_products<T>(a: T, b: T, c: T, d: T) -> SafeMathResult<Ordering>
+where
+    T: SafeMul + Ord + Unsigned,
+{
+    safe_cmp(safe_mul(a, b), safe_mul(c, d))
+}
@@ -0,0 +1,90 @@
+//! Overflow errors carrying the wrapped result, for `#[safe_math(debug_wrap)]`.
+//!
+//! With `debug_wrap`, every rewritten integer operation still fails on overflow,
+//! but with [`SafeMathError::OverflowWith`] instead of [`SafeMathError::Overflow`].
+//! The error holds the value the wrapping operation would have produced, which
+//! helps to tell e.g. an underflow by one apart from garbage input when logging.
+
+use crate::error::SafeMathError;
+use crate::impls::{safe_add, safe_div, safe_mul, safe_rem, safe_sub};
+use crate::ops::{SafeAdd, SafeDiv, SafeMul, SafeRem, SafeSub};
+
+/// Wrapping arithmetic widened to `i128`, reported by `#[safe_math(debug_wrap)]`.
+///
+/// Implemented for all primitive integer types. A wrapped `u128` above `i128::MAX`
+/// is stored with its bits reinterpreted, so `wrapped as u128` recovers it.
+pub trait DebugWrapOps: Copy {
+    /// Wrapping addition.
+    fn wrapped_add_i128(self, rhs: Self) -> i128;
+    /// Wrapping subtraction.
+    fn wrapped_sub_i128(self, rhs: Self) -> i128;
+    /// Wrapping multiplication.
+    fn wrapped_mul_i128(self, rhs: Self) -> i128;
+    /// Wrapping division (`MIN / -1` is `MIN`), never called with a zero divisor.
+    fn wrapped_div_i128(self, rhs: Self) -> i128;
+    /// Wrapping remainder (`MIN % -1` is `0`), never called with a zero divisor.
+    fn wrapped_rem_i128(self, rhs: Self) -> i128;
+}
+
+macro_rules! impl_debug_wrap_ops {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl DebugWrapOps for $t {
+                #[inline(always)]
+                fn wrapped_add_i128(self, rhs: Self) -> i128 {
+                    self.wrapping_add(rhs) as i128
+                }
+                #[inline(always)]
+                fn wrapped_sub_i128(self, rhs: Self) -> i128 {
+                    self.wrapping_sub(rhs) as i128
+                }
+                #[inline(always)]
+                fn wrapped_mul_i128(self, rhs: Self) -> i128 {
+                    self.wrapping_mul(rhs) as i128
+                }
+                #[inline(always)]
+                fn wrapped_div_i128(self, rhs: Self) -> i128 {
+                    self.wrapping_div(rhs) as i128
+                }
+                #[inline(always)]
+                fn wrapped_rem_i128(self, rhs: Self) -> i128 {
+                    self.wrapping_rem(rhs) as i128
+                }
+            }
+        )*
+    };
+}
+
+impl_debug_wrap_ops!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_debug_wrap_fns {
+    ($($fn_name:ident => ($safe:ident, $trait:ident, $wrapped:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Performs safe ", $desc, ", reporting the wrapped result on overflow.")]
+            ///
+            /// Used internally by `#[safe_math(debug_wrap)]`.
+            ///
+            /// # Returns
+            ///
+            #[doc = concat!("The result of [`", stringify!($safe), "`](crate::", stringify!($safe), "), with")]
+            /// `Err(SafeMathError::Overflow)` replaced by `Err(SafeMathError::OverflowWith { wrapped })`.
+            #[inline(always)]
+            pub fn $fn_name<T: $trait + DebugWrapOps>(a: T, b: T) -> Result<T, SafeMathError> {
+                $safe(a, b).map_err(|err| match err {
+                    SafeMathError::Overflow => SafeMathError::OverflowWith {
+                        wrapped: a.$wrapped(b),
+                    },
+                    err => err,
+                })
+            }
+        )*
+    };
+}
+
+impl_debug_wrap_fns!(
+    debug_wrap_add => (safe_add, SafeAdd, wrapped_add_i128, "addition"),
+    debug_wrap_sub => (safe_sub, SafeSub, wrapped_sub_i128, "subtraction"),
+    debug_wrap_mul => (safe_mul, SafeMul, wrapped_mul_i128, "multiplication"),
+    debug_wrap_div => (safe_div, SafeDiv, wrapped_div_i128, "division"),
+    debug_wrap_rem => (safe_rem, SafeRem, wrapped_rem_i128, "remainder"),
+);
@@ -0,0 +1,93 @@
+//! Safe arithmetic through trait objects.
+//!
+//! [`SafeMathOps`] takes and returns `Self` by value, so it can't be used as a
+//! `dyn` trait. [`DynSafeOps`] offers the same operations on `&self` and a
+//! `&dyn DynSafeOps` right-hand side, returning boxed values, so that values of
+//! different numeric types can be stored and combined behind one trait object.
+
+use crate::error::{SafeMathError, SafeMathResult};
+use crate::ops::SafeMathOps;
+use core::any::Any;
+
+/// Object-safe safe arithmetic, implemented for every [`SafeMathOps`] type.
+///
+/// Both operands must hold the same concrete type: combining e.g. a `u8` with a `u32`
+/// fails with [`SafeMathError::TypeMismatch`] rather than converting either of them.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{DynSafeOps, SafeMathError};
+///
+/// let a: Box<dyn DynSafeOps> = Box::new(200u8);
+/// let b: Box<dyn DynSafeOps> = Box::new(50u8);
+/// let sum = a.safe_add_dyn(&*b)?;
+/// assert_eq!(sum.downcast_ref::<u8>(), Some(&250));
+///
+/// assert_eq!(sum.safe_add_dyn(&*b).err(), Some(SafeMathError::Overflow));
+/// assert_eq!(a.safe_add_dyn(&7u32).err(), Some(SafeMathError::TypeMismatch));
+/// # Ok::<(), SafeMathError>(())
+/// ```
+pub trait DynSafeOps: Any {
+    /// Safe addition with overflow checking.
+    fn safe_add_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>>;
+    /// Safe subtraction with underflow checking.
+    fn safe_sub_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>>;
+    /// Safe multiplication with overflow checking.
+    fn safe_mul_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>>;
+    /// Safe division with division-by-zero checking.
+    fn safe_div_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>>;
+    /// Safe remainder with division-by-zero checking.
+    fn safe_rem_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>>;
+    /// Returns a boxed copy of the value.
+    fn clone_dyn(&self) -> Box<dyn DynSafeOps>;
+    /// Returns the value as `&dyn Any`, e.g. to downcast it.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl dyn DynSafeOps {
+    /// Returns the value if it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+impl Clone for Box<dyn DynSafeOps> {
+    fn clone(&self) -> Self {
+        self.clone_dyn()
+    }
+}
+
+/// Applies `op` to `lhs` and `rhs` if `rhs` is also a `T`.
+fn dyn_op<T: SafeMathOps + 'static>(
+    lhs: T,
+    rhs: &dyn DynSafeOps,
+    op: fn(T, T) -> SafeMathResult<T>,
+) -> SafeMathResult<Box<dyn DynSafeOps>> {
+    let rhs = rhs.downcast_ref::<T>().ok_or(SafeMathError::TypeMismatch)?;
+    Ok(Box::new(op(lhs, *rhs)?))
+}
+
+impl<T: SafeMathOps + 'static> DynSafeOps for T {
+    fn safe_add_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>> {
+        dyn_op(*self, rhs, T::safe_add)
+    }
+    fn safe_sub_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>> {
+        dyn_op(*self, rhs, T::safe_sub)
+    }
+    fn safe_mul_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>> {
+        dyn_op(*self, rhs, T::safe_mul)
+    }
+    fn safe_div_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>> {
+        dyn_op(*self, rhs, T::safe_div)
+    }
+    fn safe_rem_dyn(&self, rhs: &dyn DynSafeOps) -> SafeMathResult<Box<dyn DynSafeOps>> {
+        dyn_op(*self, rhs, T::safe_rem)
+    }
+    fn clone_dyn(&self) -> Box<dyn DynSafeOps> {
+        Box::new(*self)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -1,6 +1,6 @@
 use core::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Error types returned by safe arithmetic operations.
 ///
 /// This enum represents all possible error conditions that can occur during
@@ -18,8 +18,9 @@ use core::fmt;
 ///         Err(SafeMathError::Overflow) => println!("Overflow occurred"),
 ///         Err(SafeMathError::DivisionByZero) => println!("Division by zero"),
 ///         Err(SafeMathError::InfiniteOrNaN) => println!("Infinite or NaN result"),
-///         #[cfg(feature = "derive")]
-///         Err(SafeMathError::NotImplemented) => println!("Operation not implemented"),
+///         Err(SafeMathError::ParseError) => println!("Invalid number"),
+///         // `SafeMathError` is `#[non_exhaustive]`, so a catch-all arm is required
+///         Err(err) => println!("Other error: {}", err),
 ///     }
 /// }
 /// ```
@@ -27,6 +28,10 @@ use core::fmt;
 /// # Features
 ///
 /// The `NotImplemented` variant is only available when the `derive` feature is enabled.
+///
+/// New variants may be added in minor releases, which is why the enum is
+/// `#[non_exhaustive]`.
+#[non_exhaustive]
 pub enum SafeMathError {
     /// Arithmetic overflow or underflow occurred.
     Overflow,
@@ -34,6 +39,12 @@ pub enum SafeMathError {
     DivisionByZero,
     /// Operation resulted in infinite or NaN value (floating-point types).
     InfiniteOrNaN,
+    /// Shift amount is greater than or equal to the bit width of the type.
+    ShiftOverflow,
+    /// Input string is not a valid number (see [`safe_parse`](crate::safe_parse)).
+    ParseError,
+    /// Lower bound greater than the upper bound (see [`safe_clamp`](crate::safe_clamp)).
+    InvalidBounds,
 
     #[cfg(feature = "derive")]
     /// Operation not implemented for the given type.
@@ -42,16 +53,173 @@ pub enum SafeMathError {
     /// It occurs when using `#[derive(SafeMathOps)]` on types that don't
     /// implement the required checked arithmetic operations.
     NotImplemented,
+    /// Operands of different types (see [`DynSafeOps`](crate::DynSafeOps)).
+    TypeMismatch,
+    /// Arithmetic overflow, with the result the wrapping operation would have produced.
+    ///
+    /// Only returned by functions using `#[safe_math(debug_wrap)]`, in place of `Overflow`.
+    OverflowWith {
+        /// The wrapped result, widened to `i128` (see [`DebugWrapOps`](crate::DebugWrapOps)).
+        wrapped: i128,
+    },
 }
 
+impl SafeMathError {
+    /// Returns a stable numeric code for this error, e.g. for returning it over FFI.
+    ///
+    /// Codes are never reused or reassigned; `0` is left free to mean success.
+    ///
+    /// | Variant          | Code |
+    /// |------------------|------|
+    /// | `Overflow`       | 1    |
+    /// | `DivisionByZero` | 2    |
+    /// | `InfiniteOrNaN`  | 3    |
+    /// | `ShiftOverflow`  | 4    |
+    /// | `ParseError`     | 5    |
+    /// | `InvalidBounds`  | 6    |
+    /// | `NotImplemented` | 7    |
+    /// | `TypeMismatch`   | 8    |
+    /// | `OverflowWith`   | 9    |
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use safe_math::SafeMathError;
+    ///
+    /// assert_eq!(SafeMathError::Overflow.code(), 1);
+    /// assert_eq!(SafeMathError::from_code(2), Some(SafeMathError::DivisionByZero));
+    /// ```
+    pub const fn code(&self) -> i32 {
+        match self {
+            SafeMathError::Overflow => 1,
+            SafeMathError::DivisionByZero => 2,
+            SafeMathError::InfiniteOrNaN => 3,
+            SafeMathError::ShiftOverflow => 4,
+            SafeMathError::ParseError => 5,
+            SafeMathError::InvalidBounds => 6,
+            #[cfg(feature = "derive")]
+            SafeMathError::NotImplemented => 7,
+            SafeMathError::TypeMismatch => 8,
+            SafeMathError::OverflowWith { .. } => 9,
+        }
+    }
+
+    /// Returns how actionable this error is, from 1 (least) to 3 (most), e.g. to report
+    /// the worst error of a batch.
+    ///
+    /// * `1` - The values went out of range: `Overflow`, `OverflowWith`, `InfiniteOrNaN`,
+    ///   `ParseError`
+    /// * `2` - A specific invalid operand was not guarded against: `DivisionByZero`,
+    ///   `ShiftOverflow`
+    /// * `3` - The call is wrong whatever the values: `InvalidBounds`, `NotImplemented`,
+    ///   `TypeMismatch`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use safe_math::SafeMathError;
+    ///
+    /// let errors = [SafeMathError::Overflow, SafeMathError::DivisionByZero];
+    /// let worst = errors.iter().max_by_key(|err| err.severity());
+    /// assert_eq!(worst, Some(&SafeMathError::DivisionByZero));
+    /// ```
+    pub const fn severity(&self) -> u8 {
+        match self {
+            SafeMathError::Overflow
+            | SafeMathError::OverflowWith { .. }
+            | SafeMathError::InfiniteOrNaN
+            | SafeMathError::ParseError => 1,
+            SafeMathError::DivisionByZero | SafeMathError::ShiftOverflow => 2,
+            SafeMathError::InvalidBounds | SafeMathError::TypeMismatch => 3,
+            #[cfg(feature = "derive")]
+            SafeMathError::NotImplemented => 3,
+        }
+    }
+
+    /// Returns the coarse [`Category`] of this error, e.g. to handle every out-of-range
+    /// error alike without listing the variants.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use safe_math::{safe_mul, safe_parse, Category};
+    ///
+    /// let err = safe_mul(u8::MAX, 2).unwrap_err();
+    /// assert_eq!(err.kind_category(), Category::Range);
+    /// let err = safe_parse::<u8>("12a", 10).unwrap_err();
+    /// assert_eq!(err.kind_category(), Category::Other);
+    /// ```
+    pub const fn kind_category(&self) -> Category {
+        match self {
+            SafeMathError::Overflow
+            | SafeMathError::OverflowWith { .. }
+            | SafeMathError::ShiftOverflow => Category::Range,
+            SafeMathError::DivisionByZero => Category::DivByZero,
+            SafeMathError::InfiniteOrNaN => Category::NotFinite,
+            SafeMathError::ParseError
+            | SafeMathError::InvalidBounds
+            | SafeMathError::TypeMismatch => Category::Other,
+            #[cfg(feature = "derive")]
+            SafeMathError::NotImplemented => Category::Other,
+        }
+    }
+
+    /// Returns the error whose [`code`](Self::code) is `code`.
+    ///
+    /// # Returns
+    ///
+    /// `None` for codes not assigned to any variant, including `7` (`NotImplemented`)
+    /// when the `derive` feature is disabled, and for `9` (`OverflowWith`), whose wrapped
+    /// value the code doesn't carry.
+    pub const fn from_code(code: i32) -> Option<SafeMathError> {
+        match code {
+            1 => Some(SafeMathError::Overflow),
+            2 => Some(SafeMathError::DivisionByZero),
+            3 => Some(SafeMathError::InfiniteOrNaN),
+            4 => Some(SafeMathError::ShiftOverflow),
+            5 => Some(SafeMathError::ParseError),
+            6 => Some(SafeMathError::InvalidBounds),
+            #[cfg(feature = "derive")]
+            7 => Some(SafeMathError::NotImplemented),
+            8 => Some(SafeMathError::TypeMismatch),
+            _ => None,
+        }
+    }
+}
+
+/// Coarse classification of a [`SafeMathError`], see [`SafeMathError::kind_category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Category {
+    /// A value out of the range of its type: `Overflow`, `OverflowWith`, `ShiftOverflow`.
+    Range,
+    /// A division or remainder by zero: `DivisionByZero`.
+    DivByZero,
+    /// An infinite or NaN float: `InfiniteOrNaN`.
+    NotFinite,
+    /// Invalid input or usage: `ParseError`, `InvalidBounds`, `NotImplemented`,
+    /// `TypeMismatch`.
+    Other,
+}
+
+/// Result type returned by safe arithmetic operations.
+pub type SafeMathResult<T> = Result<T, SafeMathError>;
+
 impl fmt::Display for SafeMathError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             SafeMathError::Overflow => write!(f, "arithmetic overflow"),
             SafeMathError::DivisionByZero => write!(f, "division by zero"),
             SafeMathError::InfiniteOrNaN => write!(f, "infinite or NaN value"),
+            SafeMathError::ShiftOverflow => write!(f, "shift amount out of range"),
+            SafeMathError::ParseError => write!(f, "invalid number"),
+            SafeMathError::InvalidBounds => write!(f, "invalid bounds"),
             #[cfg(feature = "derive")]
             SafeMathError::NotImplemented => write!(f, "operation not implemented"),
+            SafeMathError::TypeMismatch => write!(f, "operands of different types"),
+            SafeMathError::OverflowWith { wrapped } => {
+                write!(f, "arithmetic overflow (wrapped result: {wrapped})")
+            }
         }
     }
 }
@@ -59,6 +227,41 @@ impl fmt::Display for SafeMathError {
 impl std::error::Error for SafeMathError {}
 
 // Allow seamless `?` propagation into functions that still use `Result<_, ()>`.
+// Disable the default `unit-error` feature to require a real error type instead.
+#[cfg(feature = "unit-error")]
 impl From<SafeMathError> for () {
     fn from(_: SafeMathError) -> Self {}
 }
+
+/// Exits with the [`code`](SafeMathError::code) of the error, which is never zero.
+///
+/// This impl is only available when the `exit-code` feature is enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "exit-code")]
+/// # {
+/// use safe_math::{safe_mul, SafeMathError};
+/// use std::process::ExitCode;
+///
+/// fn run() -> Result<u32, SafeMathError> {
+///     safe_mul(u32::MAX, 2)
+/// }
+///
+/// fn main() -> ExitCode {
+///     match run() {
+///         Ok(_) => ExitCode::SUCCESS,
+///         Err(err) => err.into(), // exits with 1 for `Overflow`
+///     }
+/// }
+/// # assert_eq!(main(), ExitCode::from(1));
+/// # }
+/// ```
+#[cfg(feature = "exit-code")]
+impl From<SafeMathError> for std::process::ExitCode {
+    fn from(err: SafeMathError) -> Self {
+        // Codes are small positive numbers, see `SafeMathError::code`.
+        Self::from(err.code() as u8)
+    }
+}
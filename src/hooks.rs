@@ -0,0 +1,51 @@
+//! Global hook invoked whenever a safe arithmetic operation fails.
+//!
+//! This module is only available when the `hooks` feature is enabled. A hook can be
+//! installed once per process with [`set_overflow_hook`]; afterwards every
+//! fallible `safe_*` helper function (and therefore every `Err` produced by code
+//! rewritten by `#[safe_math]` or `safe_math_block!`) calls it right before returning
+//! the `Err`. Calling the `Safe*` trait methods directly does not.
+//!
+//! When no hook is installed, the check is a single load of an uninitialized
+//! [`OnceLock`] on the error path only, so successful operations are unaffected.
+
+use crate::error::SafeMathError;
+use std::sync::OnceLock;
+
+/// Callback invoked with the error produced by a failing safe operation.
+pub type OverflowHook = Box<dyn Fn(&SafeMathError) + Send + Sync>;
+
+static OVERFLOW_HOOK: OnceLock<OverflowHook> = OnceLock::new();
+
+/// Installs the global overflow hook.
+///
+/// The hook can only be set once. If a hook is already installed, the new one is
+/// handed back in the `Err` variant and the existing hook is left in place.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_add, set_overflow_hook};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static FAILURES: AtomicUsize = AtomicUsize::new(0);
+///
+/// set_overflow_hook(Box::new(|_err| {
+///     FAILURES.fetch_add(1, Ordering::Relaxed);
+/// }))
+/// .ok();
+///
+/// assert!(safe_add(255u8, 1).is_err());
+/// assert_eq!(FAILURES.load(Ordering::Relaxed), 1);
+/// ```
+pub fn set_overflow_hook(hook: OverflowHook) -> Result<(), OverflowHook> {
+    OVERFLOW_HOOK.set(hook)
+}
+
+/// Calls the installed hook, if any.
+#[cold]
+pub(crate) fn call_overflow_hook(err: &SafeMathError) {
+    if let Some(hook) = OVERFLOW_HOOK.get() {
+        hook(err);
+    }
+}
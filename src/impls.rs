@@ -8,21 +8,61 @@
 //! - Specialized implementations for floating-point types
 
 use crate::error::SafeMathError;
-use crate::ops::{SafeAdd, SafeDiv, SafeMathOps, SafeMul, SafeRem, SafeSub};
+#[cfg(feature = "strict")]
+use crate::ops::SafeStrict;
+use crate::ops::{
+    Rounding, SafeAdd, SafeDiv, SafeDivRound, SafeIlog, SafeIncDec, SafeMathOps, SafeMathOpsRef,
+    SafeMidpoint, SafeMul, SafeMulAdd, SafeMulDiv, SafeNextPowerOfTwo, SafeRangeLen, SafeRem,
+    SafeShift, SafeSignCast, SafeSignedDiff, SafeSub,
+};
+use core::cmp::Ordering;
+use num_traits::One;
 use sealed::{IsSafeAdd, IsSafeDiv, IsSafeMul, IsSafeRem, IsSafeSub};
 
 macro_rules! doc_for_trait {
     (SafeDiv) => {
-        "`Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` on error."
+        "`Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `b` is zero, \
+         `Err(SafeMathError::Overflow)` on overflow (e.g. `MIN / -1`)."
     };
     (SafeRem) => {
-        "`Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` on error."
+        "`Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `b` is zero, \
+         `Err(SafeMathError::Overflow)` on overflow (e.g. `MIN % -1`)."
     };
     ($trait:ident) => {
         "`Ok(result)` on success, `Err(SafeMathError::Overflow)` on error."
     };
 }
 
+/// Returns `res`, the result of the helper `operation`, reporting it first if it failed.
+///
+/// Every fallible `safe_*` helper function returns through here, so that the overflow
+/// hook and `tracing` see all the failures of rewritten code.
+#[inline(always)]
+pub(crate) fn reported<T>(
+    operation: &'static str,
+    res: Result<T, SafeMathError>,
+) -> Result<T, SafeMathError> {
+    if let Err(err) = &res {
+        report_failure(operation, err);
+    }
+    res
+}
+
+/// Reports the failure of the helper `operation` to the overflow hook and to `tracing`,
+/// when those features are enabled.
+#[cold]
+#[allow(unused_variables)]
+fn report_failure(operation: &'static str, err: &SafeMathError) {
+    #[cfg(feature = "hooks")]
+    crate::hooks::call_overflow_hook(err);
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        operation,
+        error = ?err,
+        "safe arithmetic operation failed"
+    );
+}
+
 macro_rules! impl_safe_math_ops {
     (
         $(
@@ -48,7 +88,7 @@ macro_rules! impl_safe_math_ops {
             #[doc = doc_for_trait!($trait)]
             #[inline(always)]
             pub fn $op<T: $trait>(a: T, b: T) -> Result<T, SafeMathError> {
-                a.$op(b)
+                reported(stringify!($op), a.$op(b))
             }
         )*
     };
@@ -77,10 +117,85 @@ impl_safe_math_ops!(
     }
 );
 
+/// Error reported by the integer `safe_add`, `safe_sub` and `safe_mul` impls.
+#[inline(always)]
+fn overflow_error<T>(_rhs: &T) -> SafeMathError {
+    SafeMathError::Overflow
+}
+
+/// Error reported by the integer `safe_div` and `safe_rem` impls.
+///
+/// `checked_div`/`checked_rem` return `None` both for a zero divisor and for
+/// `MIN / -1`, so the divisor is inspected to tell the two apart.
+#[inline(always)]
+fn division_error<T: num_traits::Zero>(rhs: &T) -> SafeMathError {
+    if rhs.is_zero() {
+        SafeMathError::DivisionByZero
+    } else {
+        SafeMathError::Overflow
+    }
+}
+
+/// Unwraps the result of a safe operation, panicking with a descriptive message on error.
+///
+/// Used internally by `#[safe_math(mode = panic)]`, which expands every operator to
+/// `safe_expect(safe_op(a, b), "a op b")`. Thanks to `#[track_caller]` the panic
+/// points at the operator that failed rather than at this function.
+///
+/// # Arguments
+///
+/// * `result` - Result of a `safe_*` helper.
+/// * `expr` - Source text of the operation, included in the panic message.
+///
+/// # Panics
+///
+/// Panics if `result` is an `Err`.
+#[track_caller]
+#[inline(always)]
+pub fn safe_expect<T>(result: Result<T, SafeMathError>, expr: &str) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => panic!("{err} in `{expr}`"),
+    }
+}
+
+/// Performs an operation on behalf of `#[safe_math(mode = panic_verbose)]`.
+///
+/// Like [`safe_expect`], but the panic message also shows the helper and the values
+/// of its operands, e.g. ``arithmetic overflow in `a + b`: safe_add(200, 100)``.
+///
+/// # Arguments
+///
+/// * `op` - The `safe_*` helper performing the operation.
+/// * `name` - Name of `op`, included in the panic message.
+/// * `a`, `b` - Operands of the operation.
+/// * `expr` - Source text of the operation, included in the panic message.
+///
+/// # Panics
+///
+/// Panics if `op` returns an `Err`.
+#[track_caller]
+#[inline(always)]
+pub fn safe_expect_verbose<T, U>(
+    op: impl FnOnce(T, T) -> Result<U, SafeMathError>,
+    name: &str,
+    a: T,
+    b: T,
+    expr: &str,
+) -> U
+where
+    T: Copy + core::fmt::Debug,
+{
+    match op(a, b) {
+        Ok(value) => value,
+        Err(err) => panic!("{err} in `{expr}`: {name}({a:?}, {b:?})"),
+    }
+}
+
 macro_rules! impl_safe_ops {
     (
         $(
-            ($trait_name:ident, $trait_name_str:ident, $method_name:ident, $checked_method:ident, $bound:ident, $err:expr)
+            ($trait_name:ident, $trait_name_str:ident, $method_name:ident, $checked_method:ident, $bound:ident, $err:ident)
         ),* $(,)?
     ) => {
         $(
@@ -91,7 +206,7 @@ macro_rules! impl_safe_ops {
             {
                 #[inline(always)]
                 fn $method_name(self, rhs: T) -> Result<T, SafeMathError> {
-                    self.$checked_method(&rhs).ok_or($err)
+                    self.$checked_method(&rhs).ok_or_else(|| $err(&rhs))
                 }
             }
         )*
@@ -105,7 +220,7 @@ impl_safe_ops!(
         safe_add,
         checked_add,
         IsSafeAdd,
-        SafeMathError::Overflow
+        overflow_error
     ),
     (
         SafeSub,
@@ -113,7 +228,7 @@ impl_safe_ops!(
         safe_sub,
         checked_sub,
         IsSafeSub,
-        SafeMathError::Overflow
+        overflow_error
     ),
     (
         SafeMul,
@@ -121,7 +236,7 @@ impl_safe_ops!(
         safe_mul,
         checked_mul,
         IsSafeMul,
-        SafeMathError::Overflow
+        overflow_error
     ),
     (
         SafeDiv,
@@ -129,7 +244,7 @@ impl_safe_ops!(
         safe_div,
         checked_div,
         IsSafeDiv,
-        SafeMathError::DivisionByZero
+        division_error
     ),
     (
         SafeRem,
@@ -137,7 +252,7 @@ impl_safe_ops!(
         safe_rem,
         checked_rem,
         IsSafeRem,
-        SafeMathError::DivisionByZero
+        division_error
     ),
 );
 
@@ -199,6 +314,25 @@ impl_safe_float_ops!(
     SafeRem, safe_rem, %
 );
 
+/// Computes `a % b`, reporting a zero `b` as a division by zero for floats too.
+///
+/// `x % 0.0` is NaN, which [`SafeRem`] reports as `InfiniteOrNaN` like any other
+/// non-finite float result. This helper checks the divisor first, so it fails with
+/// `DivisionByZero` as integer remainder does. `#[safe_math(float_rem_by_zero)]`
+/// rewrites `%` to it. Failures are reported like those of [`safe_rem`].
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `b` is zero,
+/// otherwise the error of [`SafeRem::safe_rem`].
+#[inline(always)]
+pub fn safe_rem_nonzero<T: SafeRem + num_traits::Zero>(a: T, b: T) -> Result<T, SafeMathError> {
+    if b.is_zero() {
+        return reported("safe_rem", Err(SafeMathError::DivisionByZero));
+    }
+    safe_rem(a, b)
+}
+
 #[diagnostic::do_not_recommend]
 impl<T> SafeMathOps for T
 where
@@ -226,8 +360,794 @@ where
     }
 }
 
+#[diagnostic::do_not_recommend]
+impl<T: SafeMathOps> SafeMathOpsRef for T {
+    #[inline(always)]
+    fn safe_add_ref(&self, rhs: &Self) -> Result<Self, SafeMathError> {
+        SafeMathOps::safe_add(*self, *rhs)
+    }
+    #[inline(always)]
+    fn safe_sub_ref(&self, rhs: &Self) -> Result<Self, SafeMathError> {
+        SafeMathOps::safe_sub(*self, *rhs)
+    }
+    #[inline(always)]
+    fn safe_mul_ref(&self, rhs: &Self) -> Result<Self, SafeMathError> {
+        SafeMathOps::safe_mul(*self, *rhs)
+    }
+    #[inline(always)]
+    fn safe_div_ref(&self, rhs: &Self) -> Result<Self, SafeMathError> {
+        SafeMathOps::safe_div(*self, *rhs)
+    }
+    #[inline(always)]
+    fn safe_rem_ref(&self, rhs: &Self) -> Result<Self, SafeMathError> {
+        SafeMathOps::safe_rem(*self, *rhs)
+    }
+}
+
+#[diagnostic::do_not_recommend]
+impl<T> SafeIncDec for T
+where
+    T: SafeAdd + SafeSub + One,
+{
+    #[inline(always)]
+    fn safe_inc(self) -> Result<Self, SafeMathError> {
+        safe_add(self, T::one())
+    }
+
+    #[inline(always)]
+    fn safe_dec(self) -> Result<Self, SafeMathError> {
+        safe_sub(self, T::one())
+    }
+}
+
+/// Returns the absolute difference of two unsigned integers.
+///
+/// This function delegates to [`SafeSignedDiff::safe_signed_diff`].
+///
+/// # Arguments
+///
+/// * `a` - First operand.
+/// * `b` - Second operand.
+///
+/// # Returns
+///
+/// `Ok(result)` in all cases, since the absolute difference cannot overflow.
+#[inline(always)]
+pub fn safe_signed_diff<T: SafeSignedDiff>(a: T, b: T) -> Result<T, SafeMathError> {
+    reported("safe_signed_diff", a.safe_signed_diff(b))
+}
+
+/// Computes `a - b` for two unsigned integers as a signed value.
+///
+/// This function delegates to [`SafeSignedDiff::safe_sub_signed`].
+///
+/// # Arguments
+///
+/// * `a` - First operand.
+/// * `b` - Second operand.
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::Overflow)` if the difference
+/// doesn't fit in the signed type.
+#[inline(always)]
+pub fn safe_sub_signed<T: SafeSignedDiff>(a: T, b: T) -> Result<T::Signed, SafeMathError> {
+    reported("safe_sub_signed", a.safe_sub_signed(b))
+}
+
+macro_rules! impl_safe_signed_diff {
+    ($($t:ty => $signed:ty),* $(,)?) => {
+        $(
+            impl SafeSignedDiff for $t {
+                type Signed = $signed;
+
+                #[inline(always)]
+                fn safe_signed_diff(self, rhs: Self) -> Result<Self, SafeMathError> {
+                    Ok(self.abs_diff(rhs))
+                }
+
+                #[inline(always)]
+                fn safe_sub_signed(self, rhs: Self) -> Result<$signed, SafeMathError> {
+                    // `$signed` is strictly wider than `$t`, so neither the casts
+                    // nor the subtraction can overflow.
+                    Ok(self as $signed - rhs as $signed)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_signed_diff!(u8 => i16, u16 => i32, u32 => i64, u64 => i128, usize => i128);
+
+impl SafeSignedDiff for u128 {
+    type Signed = i128;
+
+    #[inline(always)]
+    fn safe_signed_diff(self, rhs: Self) -> Result<Self, SafeMathError> {
+        Ok(self.abs_diff(rhs))
+    }
+
+    #[inline(always)]
+    fn safe_sub_signed(self, rhs: Self) -> Result<i128, SafeMathError> {
+        let diff = self.abs_diff(rhs);
+        if self >= rhs {
+            i128::try_from(diff).map_err(|_| SafeMathError::Overflow)
+        } else {
+            0i128
+                .checked_sub_unsigned(diff)
+                .ok_or(SafeMathError::Overflow)
+        }
+    }
+}
+
+/// Converts an integer to the type of the same width and the other signedness.
+///
+/// This function delegates to [`SafeSignCast::safe_sign_cast`].
+///
+/// # Arguments
+///
+/// * `value` - Value to convert.
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::Overflow)` if `value` is negative and
+/// converted to unsigned, or above the maximum of the signed type.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_sign_cast, SafeMathError};
+///
+/// assert_eq!(safe_sign_cast(127u8), Ok(127i8));
+/// assert_eq!(safe_sign_cast(-1i32), Err(SafeMathError::Overflow));
+/// ```
+#[inline(always)]
+pub fn safe_sign_cast<T: SafeSignCast>(value: T) -> Result<T::Counterpart, SafeMathError> {
+    reported("safe_sign_cast", value.safe_sign_cast())
+}
+
+macro_rules! impl_safe_sign_cast {
+    ($($signed:ty => $unsigned:ty),* $(,)?) => {
+        $(
+            impl SafeSignCast for $signed {
+                type Counterpart = $unsigned;
+
+                #[inline(always)]
+                fn safe_sign_cast(self) -> Result<$unsigned, SafeMathError> {
+                    <$unsigned>::try_from(self).map_err(|_| SafeMathError::Overflow)
+                }
+            }
+
+            impl SafeSignCast for $unsigned {
+                type Counterpart = $signed;
+
+                #[inline(always)]
+                fn safe_sign_cast(self) -> Result<$signed, SafeMathError> {
+                    <$signed>::try_from(self).map_err(|_| SafeMathError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_sign_cast!(
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+);
+
+/// Shifts `a` left by `b` bits.
+///
+/// This function delegates to [`SafeShift::safe_shl`].
+///
+/// # Arguments
+///
+/// * `a` - Value to shift.
+/// * `b` - Shift amount in bits.
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::ShiftOverflow)` if `b` is not less than
+/// the bit width of `T`.
+#[inline(always)]
+pub fn safe_shl<T: SafeShift>(a: T, b: u32) -> Result<T, SafeMathError> {
+    reported("safe_shl", a.safe_shl(b))
+}
+
+/// Shifts `a` right by `b` bits.
+///
+/// This function delegates to [`SafeShift::safe_shr`].
+///
+/// # Arguments
+///
+/// * `a` - Value to shift.
+/// * `b` - Shift amount in bits.
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::ShiftOverflow)` if `b` is not less than
+/// the bit width of `T`.
+#[inline(always)]
+pub fn safe_shr<T: SafeShift>(a: T, b: u32) -> Result<T, SafeMathError> {
+    reported("safe_shr", a.safe_shr(b))
+}
+
+macro_rules! impl_safe_shift {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeShift for $t {
+                #[inline(always)]
+                fn safe_shl(self, rhs: u32) -> Result<Self, SafeMathError> {
+                    self.checked_shl(rhs).ok_or(SafeMathError::ShiftOverflow)
+                }
+
+                #[inline(always)]
+                fn safe_shr(self, rhs: u32) -> Result<Self, SafeMathError> {
+                    self.checked_shr(rhs).ok_or(SafeMathError::ShiftOverflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_shift!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Restricts `value` to the interval `[lo, hi]`.
+///
+/// Unlike [`Ord::clamp`], invalid bounds are reported as an error instead of a panic.
+///
+/// # Arguments
+///
+/// * `value` - Value to clamp.
+/// * `lo` - Lower bound.
+/// * `hi` - Upper bound.
+///
+/// # Returns
+///
+/// `Ok(lo)` if `value < lo`, `Ok(hi)` if `value > hi`, `Ok(value)` otherwise, and
+/// `Err(SafeMathError::InvalidBounds)` if `lo > hi` or the bounds are not comparable
+/// (e.g. NaN).
+#[inline(always)]
+pub fn safe_clamp<T: PartialOrd>(value: T, lo: T, hi: T) -> Result<T, SafeMathError> {
+    if !matches!(lo.partial_cmp(&hi), Some(Ordering::Less | Ordering::Equal)) {
+        return reported("safe_clamp", Err(SafeMathError::InvalidBounds));
+    }
+    Ok(if value < lo {
+        lo
+    } else if value > hi {
+        hi
+    } else {
+        value
+    })
+}
+
+/// Converts `value` to `U`, failing if it doesn't fit instead of truncating like `as`.
+///
+/// Used by `#[safe_math(checked_narrowing)]` for the initializers of `let` statements
+/// annotated with an integer type.
+///
+/// # Arguments
+///
+/// * `value` - Value to convert.
+///
+/// # Returns
+///
+/// `Ok(value)` converted to `U` on success, `Err(SafeMathError::Overflow)` if it is out of
+/// the range of `U`.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_cast, SafeMathError};
+///
+/// assert_eq!(safe_cast::<u32, u8>(255), Ok(255));
+/// assert_eq!(safe_cast::<u32, u8>(256), Err(SafeMathError::Overflow));
+/// assert_eq!(safe_cast::<i8, u16>(-1), Err(SafeMathError::Overflow));
+/// ```
+#[inline(always)]
+pub fn safe_cast<T, U: TryFrom<T>>(value: T) -> Result<U, SafeMathError> {
+    reported(
+        "safe_cast",
+        U::try_from(value).map_err(|_| SafeMathError::Overflow),
+    )
+}
+
+/// Converts `value` to `U`, clamping it to the range of `U` instead of failing.
+///
+/// The infallible counterpart of [`safe_cast`], e.g. for values that only need to be
+/// brought within the range of a narrower type.
+///
+/// # Arguments
+///
+/// * `value` - Value to convert.
+///
+/// # Returns
+///
+/// `value` converted to `U`, `U::min_value()` if it is below the range of `U`, or
+/// `U::max_value()` if it is above.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::clamping_cast;
+///
+/// assert_eq!(clamping_cast::<u32, u8>(300), 255);
+/// assert_eq!(clamping_cast::<i32, u8>(-5), 0);
+/// assert_eq!(clamping_cast::<i64, i8>(-1000), -128);
+/// assert_eq!(clamping_cast::<u8, i8>(100), 100);
+/// ```
+#[inline(always)]
+pub fn clamping_cast<T, U>(value: T) -> U
+where
+    T: Copy + PartialOrd + num_traits::Zero,
+    U: TryFrom<T> + num_traits::Bounded,
+{
+    match U::try_from(value) {
+        Ok(value) => value,
+        // Out of range: below the minimum of `U` only if negative.
+        Err(_) if value < T::zero() => U::min_value(),
+        Err(_) => U::max_value(),
+    }
+}
+
+/// Returns the length of the range `start..end`, e.g. to size a buffer from indices.
+///
+/// This function delegates to [`SafeRangeLen::safe_range_len`].
+///
+/// # Arguments
+///
+/// * `start` - Inclusive start of the range.
+/// * `end` - Exclusive end of the range.
+///
+/// # Returns
+///
+/// `Ok(end - start)` on success, `Err(SafeMathError::InvalidBounds)` if `end < start`,
+/// `Err(SafeMathError::Overflow)` if the length doesn't fit in `usize`.
+#[inline(always)]
+pub fn safe_range_len<T: SafeRangeLen>(start: T, end: T) -> Result<usize, SafeMathError> {
+    reported("safe_range_len", start.safe_range_len(end))
+}
+
+macro_rules! impl_safe_range_len {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeRangeLen for $t {
+                #[inline(always)]
+                fn safe_range_len(self, end: Self) -> Result<usize, SafeMathError> {
+                    if end < self {
+                        return Err(SafeMathError::InvalidBounds);
+                    }
+                    // `abs_diff` returns the unsigned type of the same width, which
+                    // holds any span of `$t`.
+                    usize::try_from(end.abs_diff(self)).map_err(|_| SafeMathError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_range_len!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Divides `a` by `b`, rounding the quotient with `mode`.
+///
+/// This function delegates to [`SafeDivRound::safe_div_round`].
+///
+/// # Arguments
+///
+/// * `a` - Dividend.
+/// * `b` - Divisor.
+/// * `mode` - How to round a quotient that isn't exact.
+///
+/// # Returns
+///
+/// `Ok(quotient)` on success, `Err(SafeMathError::DivisionByZero)` if `b` is zero,
+/// `Err(SafeMathError::Overflow)` on overflow (`MIN / -1`).
+#[inline(always)]
+pub fn safe_div_round<T: SafeDivRound>(a: T, b: T, mode: Rounding) -> Result<T, SafeMathError> {
+    reported("safe_div_round", a.safe_div_round(b, mode))
+}
+
+macro_rules! impl_safe_div_round {
+    (@negative unsigned, $r:expr, $rhs:expr) => { false };
+    (@negative signed, $r:expr, $rhs:expr) => { ($r < 0) != ($rhs < 0) };
+    ($kind:ident: $($t:ty),* $(,)?) => {
+        $(
+            impl SafeDivRound for $t {
+                #[inline(always)]
+                fn safe_div_round(self, rhs: Self, mode: Rounding) -> Result<Self, SafeMathError> {
+                    let quotient = <Self as SafeDiv>::safe_div(self, rhs)?;
+                    // Cannot overflow: the division above succeeded.
+                    let rem = self % rhs;
+                    if rem == 0 {
+                        return Ok(quotient);
+                    }
+                    // Whether the exact quotient is negative, i.e. `quotient` was
+                    // truncated upward.
+                    let negative = impl_safe_div_round!(@negative $kind, rem, rhs);
+                    let away_from_zero = match mode {
+                        Rounding::Trunc => false,
+                        Rounding::Floor => negative,
+                        Rounding::Ceil => !negative,
+                        // `|rem| >= |rhs| / 2`, without doubling `rem`.
+                        Rounding::Round => rem.abs_diff(0) >= rhs.abs_diff(0) - rem.abs_diff(0),
+                    };
+                    match (away_from_zero, negative) {
+                        (false, _) => Ok(quotient),
+                        (true, false) => quotient.checked_add(1).ok_or(SafeMathError::Overflow),
+                        (true, true) => quotient.checked_sub(1).ok_or(SafeMathError::Overflow),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_div_round!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_safe_div_round!(signed: i8, i16, i32, i64, i128, isize);
+
+/// Returns the average of `a` and `b`, without overflowing on the way.
+///
+/// This function delegates to [`SafeMidpoint::safe_midpoint`].
+///
+/// # Returns
+///
+/// `Ok(midpoint)` for any integers, `Err(SafeMathError::InfiniteOrNaN)` if a float
+/// operand is not finite.
+#[inline(always)]
+pub fn safe_midpoint<T: SafeMidpoint>(a: T, b: T) -> Result<T, SafeMathError> {
+    reported("safe_midpoint", a.safe_midpoint(b))
+}
+
+macro_rules! impl_safe_midpoint_int {
+    (@toward_zero unsigned, $floor:expr, $diff:expr) => { $floor };
+    // The floor is one below the truncated average when it is negative and inexact.
+    (@toward_zero signed, $floor:expr, $diff:expr) => {
+        if $floor < 0 && $diff & 1 != 0 { $floor + 1 } else { $floor }
+    };
+    ($kind:ident: $($t:ty),* $(,)?) => {
+        $(
+            impl SafeMidpoint for $t {
+                #[inline(always)]
+                fn safe_midpoint(self, rhs: Self) -> Result<Self, SafeMathError> {
+                    // The common bits plus half of the differing ones: the floor of
+                    // `(self + rhs) / 2`, computed without the sum.
+                    let diff = self ^ rhs;
+                    let floor = (self & rhs) + (diff >> 1);
+                    Ok(impl_safe_midpoint_int!(@toward_zero $kind, floor, diff))
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_midpoint_int!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_safe_midpoint_int!(signed: i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_safe_midpoint_float {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeMidpoint for $t {
+                #[inline(always)]
+                fn safe_midpoint(self, rhs: Self) -> Result<Self, SafeMathError> {
+                    if !self.is_finite() || !rhs.is_finite() {
+                        return Err(SafeMathError::InfiniteOrNaN);
+                    }
+                    // Halving first avoids the infinite sum of two large values, but
+                    // would lose the last bit of two subnormal ones.
+                    const HALF_MAX: $t = <$t>::MAX / 2.0;
+                    Ok(if self.abs() <= HALF_MAX && rhs.abs() <= HALF_MAX {
+                        (self + rhs) / 2.0
+                    } else {
+                        self / 2.0 + rhs / 2.0
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_midpoint_float!(f32, f64);
+
+/// Rounds `a` up to the next power of two.
+///
+/// This function delegates to [`SafeNextPowerOfTwo::safe_next_power_of_two`].
+///
+/// # Arguments
+///
+/// * `a` - Value to round up.
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::Overflow)` if the next power of two
+/// doesn't fit in `T`.
+#[inline(always)]
+pub fn safe_next_power_of_two<T: SafeNextPowerOfTwo>(a: T) -> Result<T, SafeMathError> {
+    reported("safe_next_power_of_two", a.safe_next_power_of_two())
+}
+
+macro_rules! impl_safe_next_power_of_two {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeNextPowerOfTwo for $t {
+                #[inline(always)]
+                fn safe_next_power_of_two(self) -> Result<Self, SafeMathError> {
+                    self.checked_next_power_of_two().ok_or(SafeMathError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_next_power_of_two!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_policy_fns {
+    ($($fn_name:ident => ($trait:ident, $marker:literal, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!($desc, ".")]
+            ///
+            #[doc = concat!("Used internally by `#[safe_math]` for the operations inside a `", $marker, "!(..)` marker.")]
+            #[doc = concat!("This function delegates to [`num_traits::", stringify!($trait), "`].")]
+            #[inline(always)]
+            pub fn $fn_name<T: num_traits::$trait>(a: T, b: T) -> T {
+                a.$fn_name(&b)
+            }
+        )*
+    };
+}
+
+impl_policy_fns!(
+    saturating_add => (SaturatingAdd, "saturating", "Saturating addition, clamping at the numeric bounds"),
+    saturating_sub => (SaturatingSub, "saturating", "Saturating subtraction, clamping at the numeric bounds"),
+    saturating_mul => (SaturatingMul, "saturating", "Saturating multiplication, clamping at the numeric bounds"),
+    wrapping_add => (WrappingAdd, "wrapping", "Wrapping (modular) addition"),
+    wrapping_sub => (WrappingSub, "wrapping", "Wrapping (modular) subtraction"),
+    wrapping_mul => (WrappingMul, "wrapping", "Wrapping (modular) multiplication"),
+);
+
+#[cfg(feature = "strict")]
+macro_rules! impl_safe_strict {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeStrict for $t {
+                impl_safe_strict!(@method add_strict, strict_add, checked_add, "attempt to add with overflow");
+                impl_safe_strict!(@method sub_strict, strict_sub, checked_sub, "attempt to subtract with overflow");
+                impl_safe_strict!(@method mul_strict, strict_mul, checked_mul, "attempt to multiply with overflow");
+                impl_safe_strict!(
+                    @method div_strict, strict_div, checked_div, "attempt to divide with overflow",
+                    "attempt to divide by zero"
+                );
+                impl_safe_strict!(
+                    @method rem_strict, strict_rem, checked_rem,
+                    "attempt to calculate the remainder with overflow",
+                    "attempt to calculate the remainder with a divisor of zero"
+                );
+            }
+        )*
+    };
+    // The fallback messages are those of the `strict_*` methods.
+    (@method $method:ident, $strict:ident, $checked:ident, $overflow:literal $(, $by_zero:literal)?) => {
+        #[track_caller]
+        #[inline(always)]
+        fn $method(self, rhs: Self) -> Self {
+            // Only compiled on toolchains where `strict_*` is stable, see `build.rs`.
+            #[cfg(has_strict_ops)]
+            #[allow(clippy::incompatible_msrv)]
+            {
+                self.$strict(rhs)
+            }
+            #[cfg(not(has_strict_ops))]
+            match self.$checked(rhs) {
+                Some(value) => value,
+                $(None if rhs == 0 => panic!($by_zero),)?
+                None => panic!($overflow),
+            }
+        }
+    };
+}
+
+#[cfg(feature = "strict")]
+impl_safe_strict!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "strict")]
+macro_rules! impl_strict_fns {
+    ($($fn_name:ident => ($method:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Strict ", $desc, ", panicking on failure.")]
+            ///
+            #[doc = concat!("Used internally by `#[safe_math(mode = strict)]`. This function delegates to [`SafeStrict::", stringify!($method), "`].")]
+            ///
+            /// # Panics
+            ///
+            /// Panics at the location of the caller if the operation fails.
+            #[track_caller]
+            #[inline(always)]
+            pub fn $fn_name<T: SafeStrict>(a: T, b: T) -> T {
+                a.$method(b)
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "strict")]
+impl_strict_fns!(
+    strict_add => (add_strict, "addition"),
+    strict_sub => (sub_strict, "subtraction"),
+    strict_mul => (mul_strict, "multiplication"),
+    strict_div => (div_strict, "division"),
+    strict_rem => (rem_strict, "remainder"),
+);
+
+/// Returns the base 2 logarithm of `a`, rounded down.
+///
+/// This function delegates to [`SafeIlog::safe_ilog2`].
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `a` is zero.
+#[inline(always)]
+pub fn safe_ilog2<T: SafeIlog>(a: T) -> Result<u32, SafeMathError> {
+    reported("safe_ilog2", a.safe_ilog2())
+}
+
+/// Returns the base 10 logarithm of `a`, rounded down.
+///
+/// This function delegates to [`SafeIlog::safe_ilog10`].
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `a` is zero.
+#[inline(always)]
+pub fn safe_ilog10<T: SafeIlog>(a: T) -> Result<u32, SafeMathError> {
+    reported("safe_ilog10", a.safe_ilog10())
+}
+
+/// Returns the logarithm of `a` in the given `base`, rounded down.
+///
+/// This function delegates to [`SafeIlog::safe_ilog`].
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `a` is zero or
+/// `base` is less than 2.
+#[inline(always)]
+pub fn safe_ilog<T: SafeIlog>(a: T, base: T) -> Result<u32, SafeMathError> {
+    reported("safe_ilog", a.safe_ilog(base))
+}
+
+macro_rules! impl_safe_ilog {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeIlog for $t {
+                #[inline(always)]
+                fn safe_ilog2(self) -> Result<u32, SafeMathError> {
+                    self.checked_ilog2().ok_or(SafeMathError::DivisionByZero)
+                }
+
+                #[inline(always)]
+                fn safe_ilog10(self) -> Result<u32, SafeMathError> {
+                    self.checked_ilog10().ok_or(SafeMathError::DivisionByZero)
+                }
+
+                #[inline(always)]
+                fn safe_ilog(self, base: Self) -> Result<u32, SafeMathError> {
+                    self.checked_ilog(base).ok_or(SafeMathError::DivisionByZero)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_ilog!(u8, u16, u32, u64, u128, usize);
+
+/// Computes `a * b + c` with a fused multiply-add.
+///
+/// This function delegates to [`SafeMulAdd::safe_mul_add`].
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::Overflow)` if an integer step
+/// overflows, `Err(SafeMathError::InfiniteOrNaN)` if a float result is not finite.
+#[inline(always)]
+pub fn safe_mul_add<T: SafeMulAdd>(a: T, b: T, c: T) -> Result<T, SafeMathError> {
+    reported("safe_mul_add", a.safe_mul_add(b, c))
+}
+
+macro_rules! impl_safe_mul_add_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeMulAdd for $t {
+                #[inline(always)]
+                fn safe_mul_add(self, a: Self, b: Self) -> Result<Self, SafeMathError> {
+                    self.checked_mul(a)
+                        .and_then(|product| product.checked_add(b))
+                        .ok_or(SafeMathError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_mul_add_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_safe_mul_add_float {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeMulAdd for $t {
+                #[inline(always)]
+                fn safe_mul_add(self, a: Self, b: Self) -> Result<Self, SafeMathError> {
+                    let res = self.mul_add(a, b);
+                    res.is_finite().then_some(res).ok_or(SafeMathError::InfiniteOrNaN)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_mul_add_float!(f32, f64);
+
+/// Computes `value * num / den`, with the product in the next wider integer type.
+///
+/// `u128` and `i128` have no wider type, so 128-bit inputs can still overflow on the
+/// intermediate product. This function delegates to [`SafeMulDiv::safe_mul_div`].
+///
+/// # Returns
+///
+/// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `den` is zero,
+/// `Err(SafeMathError::Overflow)` if the result (or, for 128-bit inputs, the product)
+/// doesn't fit.
+#[inline(always)]
+pub fn safe_mul_div<T: SafeMulDiv>(value: T, num: T, den: T) -> Result<T, SafeMathError> {
+    reported("safe_mul_div", value.safe_mul_div(num, den))
+}
+
+macro_rules! impl_safe_mul_div {
+    ($($t:ty => $wide:ty),* $(,)?) => {
+        $(
+            impl SafeMulDiv for $t {
+                #[inline(always)]
+                fn safe_mul_div(self, num: Self, den: Self) -> Result<Self, SafeMathError> {
+                    if den == 0 {
+                        return Err(SafeMathError::DivisionByZero);
+                    }
+                    // `$wide` has at least twice the bits of `$t`, so the product fits
+                    // and can't be `MIN`, leaving no overflow to the division either.
+                    let quotient = self as $wide * num as $wide / den as $wide;
+                    <$t>::try_from(quotient).map_err(|_| SafeMathError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_mul_div!(u8 => u16, u16 => u32, u32 => u64, u64 => u128, usize => u128);
+impl_safe_mul_div!(i8 => i16, i16 => i32, i32 => i64, i64 => i128, isize => i128);
+
+macro_rules! impl_safe_mul_div_128 {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeMulDiv for $t {
+                #[inline(always)]
+                fn safe_mul_div(self, num: Self, den: Self) -> Result<Self, SafeMathError> {
+                    if den == 0 {
+                        return Err(SafeMathError::DivisionByZero);
+                    }
+                    self.checked_mul(num)
+                        .and_then(|product| product.checked_div(den))
+                        .ok_or(SafeMathError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_mul_div_128!(u128, i128);
+
 mod sealed {
     use num_traits::ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
+    use num_traits::Zero;
     pub trait Sealed {}
 
     macro_rules! impl_sealed {
@@ -243,12 +1163,12 @@ mod sealed {
     pub trait IsSafeAdd: Sealed + CheckedAdd {}
     pub trait IsSafeSub: Sealed + CheckedSub {}
     pub trait IsSafeMul: Sealed + CheckedMul {}
-    pub trait IsSafeDiv: Sealed + CheckedDiv {}
-    pub trait IsSafeRem: Sealed + CheckedRem {}
+    pub trait IsSafeDiv: Sealed + CheckedDiv + Zero {}
+    pub trait IsSafeRem: Sealed + CheckedRem + Zero {}
 
     impl<T> IsSafeAdd for T where T: Sealed + CheckedAdd {}
     impl<T> IsSafeSub for T where T: Sealed + CheckedSub {}
     impl<T> IsSafeMul for T where T: Sealed + CheckedMul {}
-    impl<T> IsSafeDiv for T where T: Sealed + CheckedDiv {}
-    impl<T> IsSafeRem for T where T: Sealed + CheckedRem {}
+    impl<T> IsSafeDiv for T where T: Sealed + CheckedDiv + Zero {}
+    impl<T> IsSafeRem for T where T: Sealed + CheckedRem + Zero {}
 }
@@ -0,0 +1,45 @@
+//! Safe accumulation over iterators.
+//!
+//! `Iterator::sum` panics or wraps on overflow depending on the build profile.
+//! [`SafeIteratorExt`] folds with the checked operations instead, stopping at the
+//! first failure.
+
+use crate::error::SafeMathError;
+use crate::impls::safe_add;
+use crate::ops::SafeAdd;
+use num_traits::Zero;
+
+/// Extension trait for safe accumulation over an iterator of results.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeIteratorExt, SafeMathError};
+///
+/// let readings: Vec<Result<u8, SafeMathError>> = vec![Ok(100), Ok(100)];
+/// assert_eq!(readings.into_iter().try_safe_sum(), Ok(200));
+///
+/// let readings: Vec<Result<u8, SafeMathError>> = vec![Ok(200), Ok(100)];
+/// assert_eq!(readings.into_iter().try_safe_sum(), Err(SafeMathError::Overflow));
+/// ```
+pub trait SafeIteratorExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Sums the `Ok` items with [`safe_add`](crate::safe_add), starting from zero.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(sum)` - If every item is `Ok` and the sum fits in `T`
+    /// * `Err(err)` - The first `Err` item, or the overflow converted with `E::from`,
+    ///   whichever comes first. The remaining items are not consumed.
+    fn try_safe_sum(self) -> Result<T, E>;
+}
+
+impl<I, T, E> SafeIteratorExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+    T: SafeAdd + Zero,
+    E: From<SafeMathError>,
+{
+    fn try_safe_sum(mut self) -> Result<T, E> {
+        self.try_fold(T::zero(), |acc, item| Ok(safe_add(acc, item?)?))
+    }
+}
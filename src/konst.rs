@@ -0,0 +1,109 @@
+//! `const fn` versions of the safe operations for primitive integers.
+//!
+//! The trait-based functions such as [`safe_add`](crate::safe_add) are generic and
+//! can't be called in const contexts. This module provides one submodule per
+//! primitive integer type instead, with `const fn` variants built on the
+//! `checked_*` methods of the standard library:
+//!
+//! ```rust
+//! use safe_math::{konst, SafeMathError};
+//!
+//! const TABLE_SIZE: usize = match konst::usize::safe_mul(64, 1024) {
+//!     Ok(size) => size,
+//!     Err(_) => panic!("table size overflows usize"),
+//! };
+//!
+//! assert_eq!(TABLE_SIZE, 65536);
+//! assert_eq!(konst::u8::safe_add(200, 100), Err(SafeMathError::Overflow));
+//! ```
+
+use crate::error::SafeMathError;
+
+macro_rules! impl_const_ops {
+    ($($t:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("`const fn` safe operations for `", stringify!($t), "`.")]
+            pub mod $t {
+                use crate::error::SafeMathError;
+
+                /// Performs safe addition checking.
+                ///
+                /// # Returns
+                ///
+                /// `Ok(result)` on success, `Err(SafeMathError::Overflow)` on overflow.
+                #[inline(always)]
+                pub const fn safe_add(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    match a.checked_add(b) {
+                        Some(res) => Ok(res),
+                        None => Err(SafeMathError::Overflow),
+                    }
+                }
+
+                /// Performs safe subtraction checking.
+                ///
+                /// # Returns
+                ///
+                /// `Ok(result)` on success, `Err(SafeMathError::Overflow)` on overflow.
+                #[inline(always)]
+                pub const fn safe_sub(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    match a.checked_sub(b) {
+                        Some(res) => Ok(res),
+                        None => Err(SafeMathError::Overflow),
+                    }
+                }
+
+                /// Performs safe multiplication checking.
+                ///
+                /// # Returns
+                ///
+                /// `Ok(result)` on success, `Err(SafeMathError::Overflow)` on overflow.
+                #[inline(always)]
+                pub const fn safe_mul(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    match a.checked_mul(b) {
+                        Some(res) => Ok(res),
+                        None => Err(SafeMathError::Overflow),
+                    }
+                }
+
+                /// Performs safe division checking.
+                ///
+                /// # Returns
+                ///
+                /// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `b` is zero,
+                /// `Err(SafeMathError::Overflow)` on overflow (e.g. `MIN / -1`).
+                #[inline(always)]
+                pub const fn safe_div(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    match a.checked_div(b) {
+                        Some(res) => Ok(res),
+                        None => Err(super::div_error(b == 0)),
+                    }
+                }
+
+                /// Performs safe remainder checking.
+                ///
+                /// # Returns
+                ///
+                /// `Ok(result)` on success, `Err(SafeMathError::DivisionByZero)` if `b` is zero,
+                /// `Err(SafeMathError::Overflow)` on overflow (e.g. `MIN % -1`).
+                #[inline(always)]
+                pub const fn safe_rem(a: $t, b: $t) -> Result<$t, SafeMathError> {
+                    match a.checked_rem(b) {
+                        Some(res) => Ok(res),
+                        None => Err(super::div_error(b == 0)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Error of a failed division or remainder.
+const fn div_error(by_zero: bool) -> SafeMathError {
+    if by_zero {
+        SafeMathError::DivisionByZero
+    } else {
+        SafeMathError::Overflow
+    }
+}
+
+impl_const_ops!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
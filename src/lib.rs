@@ -38,6 +38,23 @@
 //!- Division (`/`, `/=`)
 //!- Remainder (`%`, `%=`)
 //!
+//!In a compound assignment such as `x += a * b`, the right-hand side is checked first, then the
+//!addition; `x` is only written once both succeeded.
+//!
+//!Arithmetic in an index is checked as well: in `v[i + 1]`, an overflowing `i + 1` returns
+//!`Err(SafeMathError::Overflow)` before `v` is indexed. The bounds check of the indexing itself is
+//!unchanged and still panics on an out-of-range index.
+//!
+//!Arguments are checked the same way, before the call: `f(a + b)` returns the error without calling
+//!`f`, whether `f` is a function, a method or a closure. This extends to the arguments of the standard
+//!`format!`, `print!`/`println!`, `write!`/`writeln!`, `panic!`, `assert!` families and `vec!`;
+//!the tokens of any other macro are left untouched.
+//!
+//!Both operands of an operator are passed to the same `safe_*` function, so they must have the same
+//!type: dereference borrowed operands, e.g. `*x + 1` when iterating with `iter()`. The same goes for
+//!smart pointers such as `Box<u32>` or `Rc<u32>`, which can't implement the `Copy`-based `Safe*`
+//!traits: write `*a + *b`.
+//!
 //!## Error Handling
 //!
 //!Operations return `SafeMathError` for exceptional cases:
@@ -46,10 +63,35 @@
 //!    Overflow,           // Result exceeds type bounds
 //!    DivisionByZero,    // Division or remainder by zero
 //!    InfiniteOrNaN,    // Result is infinite or NaN (floating-point types)
+//!    ShiftOverflow,    // Shift amount >= bit width (`safe_shl`/`safe_shr`)
+//!    ParseError,       // Invalid digits in `safe_parse` input
+//!    InvalidBounds,    // `lo > hi` in `safe_clamp`
 //!    NotImplemented,    // Missing trait implementation (derive feature)
+//!    TypeMismatch,     // Operands of different types behind `dyn DynSafeOps`
+//!    OverflowWith { wrapped: i128 }, // Overflow with its wrapped result (`debug_wrap`)
 //!}
 //!```
 //!
+//!The enum is `#[non_exhaustive]`: new variants may be added in minor releases, so a `match` on it
+//!outside of this crate needs a wildcard arm.
+//!
+//!The `?` operator also converts `SafeMathError` into `()`, so functions returning `Result<_, ()>`
+//!work out of the box. This conversion is provided by the default `unit-error` feature; disable
+//!default features to make `?` require a proper error type instead:
+//!
+//!```toml
+//!safe_math = { version = "2.0.0-beta", default-features = false }
+//!```
+//!
+//!With the `exit-code` feature, `SafeMathError` also converts into `std::process::ExitCode`, using
+//!the nonzero `SafeMathError::code` of each variant, so a CLI can end with `err.into()` from a
+//!`main` returning `ExitCode` (see `examples/exit_code.rs`).
+//!
+//!With the `tracing` feature, every fallible `safe_*` helper function (and so every `Err`
+//!produced by code rewritten by `#[safe_math]`) emits a `tracing` event at the `WARN` level when
+//!it fails, with the helper's name in the `operation` field (e.g. `"safe_mul"`) and the error in
+//!the `error` field. Calling the `Safe*` trait methods directly bypasses it.
+//!
 //!## Type Support
 //!
 //!Built-in support for:
@@ -57,6 +99,10 @@
 //!- Signed integers: `i8` through `i128`, `isize`
 //!- Floating point: `f32`, `f64` (with infinity/NaN handling)
 //!
+//!Float remainder by zero (`x % 0.0`) yields NaN and is reported as `InfiniteOrNaN`. To report it
+//!as `DivisionByZero`, like integer remainder, call `safe_rem_nonzero` or add `float_rem_by_zero`
+//!to a function's `#[safe_math(...)]`, which rewrites its `%` and `%=` to that helper.
+//!
 //!# Advanced Usage
 //!
 //!## Custom Types
@@ -78,11 +124,202 @@
 //!
 //!**Note:** For the derive to work, your type must implement both the standard arithmetic traits
 //!(like `Add`, `Sub`, `Mul`, `Div`, `Rem`) and their checked counterparts (like `CheckedAdd`,
-//!`CheckedSub`, `CheckedMul`, `CheckedDiv`, `CheckedRem`) from the `num-traits` crate.
+//!`CheckedSub`, `CheckedMul`, `CheckedDiv`, `CheckedRem`) from the `num-traits` crate. `div`
+//!also needs `Default + PartialEq`, to tell a zero divisor (`Default::default()`) from an
+//!overflow such as `MIN / -1`, while `rem` reports any failure as a division by zero.
 //!
 //!This requirement exists because without knowing what a type represents, it's impossible to
 //!determine what operations are safe to perform or what constitutes a "checked" operation.
 //!
+//!Adding `checked_sum` next to `add` also generates an associated
+//!`checked_sum(iter) -> Result<Self, SafeMathError>` function, an overflow-aware alternative to
+//!`Iterator::sum`. It folds with `safe_add` starting from `num_traits::Zero::zero()`, so the type
+//!must implement `Zero` as well:
+//!
+//!```rust,ignore
+//!#[derive(SafeMathOps)]
+//!#[SafeMathOps(add, checked_sum)]
+//!struct Points(u32);
+//!
+//!let total = Points::checked_sum(vec![Points(1), Points(2)])?;
+//!```
+//!
+//!Listing `scale` generates `SafeScale<F>`, where `F` is the type of the struct's first field,
+//!to multiply a value by a bare scalar. Other fields (e.g. `PhantomData` unit markers) are
+//!copied unchanged:
+//!
+//!```rust,ignore
+//!#[derive(SafeMathOps)]
+//!#[SafeMathOps(mul, scale)]
+//!struct Meters(u32);
+//!
+//!let doubled = Meters(5).safe_mul_scalar(2)?;
+//!```
+//!
+//!Listing `cmp` generates an inherent `safe_cmp(&self, &other) -> Ordering`, comparing the fields
+//!with `Ord::cmp` in declaration order, e.g. for `prices.sort_by(Cents::safe_cmp)`. It is also
+//!available with `by_ref`.
+//!
+//!Listing `saturating` or `wrapping` next to `add`, `sub` or `mul` generates the matching
+//!`num_traits` impls (`SaturatingAdd`, `WrappingMul`, ...), which perform the operation on the
+//!struct's first field and copy the other fields, like `scale`. The type then also works inside
+//!`saturating!(..)` and `wrapping!(..)`:
+//!
+//!```rust,ignore
+//!#[derive(SafeMathOps)]
+//!#[SafeMathOps(add, mul, wrapping)]
+//!struct Hash(u64);
+//!
+//!let mixed = wrapping!(seed * Hash(31) + byte);
+//!```
+//!
+//!For types whose checked methods don't follow the `num-traits` naming, `op = method` (e.g.
+//!`#[SafeMathOps(add = checked_addition)]`) calls `self.method(rhs)` instead of the `CheckedAdd`
+//!impl, and a path such as `mul = MyTrait::checked_times` calls `MyTrait::checked_times(self, rhs)`.
+//!Either must return `Option<Self>`.
+//!
+//!With `error = path`, e.g. `#[SafeMathOps(add, sub, error = crate::MoneyError)]`, the derive
+//!also generates inherent `safe_add`/`safe_sub`/... methods returning that error type, which
+//!must implement `From<SafeMathError>`. They take precedence over the trait methods in
+//!method-call syntax, so `a.safe_add(b)` fits into a domain error hierarchy, while the
+//!`SafeMathOps` impls (and thus `#[safe_math]`) keep using `SafeMathError`.
+//!
+//!Floats have no `checked_*` methods. For a struct whose first field is a float, add `float`, e.g.
+//!`#[SafeMathOps(add, mul, float)]` on `struct Price(f64)`: the operations then use the standard
+//!operators (`Add`, `Mul`, ...), and fail with `SafeMathError::InfiniteOrNaN` if the first field of
+//!the result is infinite or NaN, like the built-in float impls. It can't be combined with `by_ref`,
+//!`op = method`, `saturating` or `wrapping`.
+//!
+//!`SafeMathOps` requires `Copy`. For heap-backed types such as big integers, add `by_ref` to
+//!derive `SafeMathOpsRef` instead, whose `safe_add_ref(&self, &rhs)`/... methods borrow both
+//!operands. It needs only the `Checked*` traits (and `Default + PartialEq` for `div` and `rem`, to
+//!tell a zero divisor apart), and can't be combined with `checked_sum`, `scale`, `saturating`,
+//!`wrapping` or `error`. Since
+//!`#[safe_math]` rewrites operators to by-value calls, such types are used through the methods:
+//!
+//!```rust,ignore
+//!#[derive(Clone, SafeMathOps)]
+//!#[SafeMathOps(add, mul, by_ref)]
+//!struct Big(BigUint);
+//!
+//!let total = a.safe_add_ref(&b)?.safe_mul_ref(&c)?;
+//!```
+//!
+//!For dynamic dispatch, every `SafeMathOps` type also implements the object-safe `DynSafeOps`,
+//!whose `safe_add_dyn(&self, &dyn DynSafeOps)`/... methods return a `Box<dyn DynSafeOps>`. Both
+//!operands must have the same concrete type, otherwise they fail with `SafeMathError::TypeMismatch`;
+//!`downcast_ref` recovers the value.
+//!
+//!## Option-Returning Functions
+//!
+//!In functions returning an `Option`, a failing operation returns `None` instead, so the
+//!arithmetic mixes with `?` on other `Option`s:
+//!
+//!```rust
+//!use safe_math::safe_math;
+//!
+//!#[safe_math]
+//!fn first_plus(values: &[u32], delta: u32) -> Option<u32> {
+//!    let first = values.first()?;
+//!    Some(*first + delta)
+//!}
+//!
+//!assert_eq!(first_plus(&[1], 2), Some(3));
+//!assert_eq!(first_plus(&[u32::MAX], 1), None);
+//!assert_eq!(first_plus(&[], 1), None);
+//!```
+//!
+//!## Signed Subtraction
+//!
+//!With `#[safe_math(signed_sub)]`, `a - b` computes the difference of two unsigned operands in the
+//!next wider signed type (see `SafeSignedDiff::safe_sub_signed`), so `3u32 - 10u32` is `-7i64`
+//!instead of an error. Every subtraction of the function is affected, so their operands must be
+//!unsigned. A compound `-=` is left as a regular checked subtraction, since it can't change the
+//!type of its place:
+//!
+//!```rust
+//!use safe_math::safe_math;
+//!
+//!#[safe_math(signed_sub)]
+//!fn delta(before: u32, after: u32) -> Result<i64, safe_math::SafeMathError> {
+//!    Ok(after - before)
+//!}
+//!
+//!assert_eq!(delta(10, 3), Ok(-7));
+//!```
+//!
+//!## Checked Narrowing
+//!
+//!`let small: u8 = big;` doesn't compile when `big` is a `u32`, and `big as u8` silently truncates.
+//!With `#[safe_math(checked_narrowing)]`, the initializer of every `let` annotated with a primitive
+//!integer type is converted with `safe_cast` (a `TryFrom` conversion), so a value out of range
+//!fails like an overflowing operation. The initializer's type must be known without the
+//!annotation, which rules out e.g. `.into()` or an unannotated `.parse()`. Initializers made of
+//!literals only, such as `let mask: u64 = 1 << 40;`, are left as written:
+//!
+//!```rust
+//!use safe_math::safe_math;
+//!
+//!#[safe_math(checked_narrowing)]
+//!fn to_byte(big: u32) -> Result<u8, safe_math::SafeMathError> {
+//!    let small: u8 = big;
+//!    Ok(small)
+//!}
+//!
+//!assert_eq!(to_byte(255), Ok(255));
+//!assert_eq!(to_byte(256), Err(safe_math::SafeMathError::Overflow));
+//!```
+//!
+//!Where an out-of-range value should be brought within range instead, convert it explicitly with
+//!`clamping_cast`, e.g. `clamping_cast::<u32, u8>(300) == 255`.
+//!
+//!## Wrapped Results on Overflow
+//!
+//!To find out what an overflowing operation would have produced, e.g. to tell an underflow by one
+//!apart from bad input in the logs, use `#[safe_math(debug_wrap)]`. Integer overflows then fail with
+//!`SafeMathError::OverflowWith { wrapped }` instead of `Overflow`, where `wrapped` is the result of
+//!the wrapping operation widened to `i128`. Float operations are not supported in such functions:
+//!
+//!```rust
+//!use safe_math::{safe_math, SafeMathError};
+//!
+//!#[safe_math(debug_wrap)]
+//!fn elapsed(start: u32, end: u32) -> Result<u32, SafeMathError> {
+//!    Ok(end - start)
+//!}
+//!
+//!assert_eq!(elapsed(11, 10), Err(SafeMathError::OverflowWith { wrapped: u32::MAX as i128 }));
+//!```
+//!
+//!## Modules
+//!
+//!`#[safe_math]` on an inline module rewrites its functions as if each was annotated on its own:
+//!free functions, methods in `impl` blocks, provided trait methods, and those of nested inline
+//!modules. In the checked mode, functions that don't return a `Result` or an `Option`, such as
+//!`#[test]`s or helpers returning a plain value, are left unchanged. So are `const fn`s, and a
+//!function with its own `#[safe_math(...)]` attribute keeps its arguments. Modules declared in
+//!another file (`mod foo;`) aren't part of the attribute's input and aren't covered:
+//!
+//!```rust
+//!#[safe_math::safe_math]
+//!mod billing {
+//!    use safe_math::SafeMathError;
+//!
+//!    pub fn total(price: u8, quantity: u8) -> Result<u8, SafeMathError> {
+//!        Ok(price * quantity)
+//!    }
+//!
+//!    pub mod discounts {
+//!        pub fn discounted(price: u8, discount: u8) -> Option<u8> {
+//!            Some(price - discount)
+//!        }
+//!    }
+//!}
+//!
+//!assert_eq!(billing::total(20, 13), Err(safe_math::SafeMathError::Overflow));
+//!assert_eq!(billing::discounts::discounted(5, 20), None);
+//!```
+//!
 //!## Block-Level Safety
 //!
 //!Use `safe_math_block!` to apply checked operations to a specific block of code:
@@ -105,13 +342,187 @@
 //!- Apply safe arithmetic to specific expression
 //!- Mix checked and unchecked operations in the same function
 //!
+//!Errors leave the block with `?`, i.e. they return from the enclosing function, so its error
+//!type must implement `From<SafeMathError>`. Other `?` expressions inside the block are left
+//!untouched and keep propagating their own errors; the error type then needs a `From` impl for
+//!each of them as well.
+//!
+//!A leading `saturating` or `wrapping` applies that policy to the whole block, like wrapping it in
+//!`saturating!(..)` or `wrapping!(..)` (see below). Such a block needs no `Result` as long as it
+//!doesn't divide:
+//!
+//!```rust
+//!use safe_math::safe_math_block;
+//!
+//!fn clamp_sum(a: u8, b: u8) -> u8 {
+//!    safe_math_block!(saturating, { a + b })
+//!}
+//!
+//!assert_eq!(clamp_sum(255, 10), 255);
+//!```
+//!
+//!When an operation's type can't be inferred, such as for plain literals or a generic `into()`
+//!followed by a method call, `safe!(expr; Type)` checks `expr` like `safe_math_block!` while
+//!performing every operation in `Type`:
+//!
+//!```rust
+//!use safe_math::{safe, SafeMathError};
+//!
+//!fn capped_square<T: Copy + Into<u32> + Into<u64>>(x: T, cap: u64) -> Result<u64, SafeMathError> {
+//!    Ok(safe!(x.into() * x.into(); u64).min(cap))
+//!}
+//!
+//!fn literal_bits() -> Result<u32, SafeMathError> {
+//!    Ok(safe!(200 + 100; u8).count_ones())
+//!}
+//!
+//!assert_eq!(capped_square(3u8, 100), Ok(9));
+//!assert_eq!(literal_bits(), Err(SafeMathError::Overflow));
+//!```
+//!
+//!## Per-Expression Policies
+//!
+//!Inside `#[safe_math]` or `safe_math_block!`, wrap an expression in `saturating!(..)` or
+//!`wrapping!(..)` to make its `+`, `-` and `*` saturate or wrap instead of failing. Division and
+//!remainder stay checked, and so does everything outside of the marker:
+//!
+//!```rust
+//!use safe_math::safe_math;
+//!
+//!#[safe_math]
+//!fn score(base: u8, bonus: u8, factor: u8) -> Result<u8, safe_math::SafeMathError> {
+//!    Ok(base + saturating!(bonus * factor))
+//!}
+//!
+//!assert_eq!(score(0, 100, 100), Ok(255));
+//!assert!(score(1, 100, 100).is_err());
+//!```
+//!
+//!Markers apply to their own subtree only, and the innermost one wins: in
+//!`saturating!(a + wrapping!(b * 2))` the product wraps and the sum saturates. Operators outside
+//!of any marker follow the mode of the function, and operate on the already clamped or wrapped
+//!value, so `saturating!(a * b) + c` first clamps the product and then fails if adding `c` to the
+//!clamped value overflows (or panics, in the panic mode).
+//!
+//!`unchecked!(..)` goes one step further and leaves its contents as written, e.g. for a hot line
+//!that is known not to overflow or that relies on `wrapping_*` methods.
+//!
+//!The markers are only recognized by the macros and are not defined anywhere else.
+//!
+//!## Panic Mode
+//!
+//!`#[safe_math(mode = panic)]` keeps the fail-fast behavior of debug builds in every
+//!profile: a failing operation panics with a message naming the operation, at the
+//!location of the operator. The function does not need to return a `Result`, which makes
+//!this mode a convenient first step before adopting full error handling.
+//!
+//!```rust,should_panic
+//!use safe_math::safe_math;
+//!
+//!#[safe_math(mode = panic)]
+//!fn add(a: u8, b: u8) -> u8 {
+//!    a + b
+//!}
+//!
+//!add(255, 1); // panics with "arithmetic overflow in `a + b`"
+//!```
+//!
+//!For a single invariant, e.g. in a test, `safe_assert_no_overflow!(expr)` evaluates `expr` the
+//!same way without an attribute on the enclosing function:
+//!
+//!```rust,should_panic
+//!use safe_math::safe_assert_no_overflow;
+//!
+//!let (a, b) = (200u8, 100u8);
+//!assert_eq!(safe_assert_no_overflow!(a / 2 + b), 200);
+//!safe_assert_no_overflow!(a + b); // panics with "arithmetic overflow in `a + b`"
+//!```
+//!
+//!`#[safe_math(mode = panic_verbose)]` panics the same way, but also shows the values of the
+//!operands, which is handy to debug a failing test. It requires the operands to implement `Debug`:
+//!
+//!```rust,should_panic
+//!use safe_math::safe_math;
+//!
+//!#[safe_math(mode = panic_verbose)]
+//!fn scale(a: u8, b: u8, c: u8) -> u8 {
+//!    a * b / c
+//!}
+//!
+//!scale(200, 2, 4); // panics with "arithmetic overflow in `a * b`: safe_mul(200, 2)"
+//!```
+//!
+//!This is also the mode to use for `extern "C"` functions, which can't return a `Result` across
+//!the FFI boundary. Note that a panic can't unwind out of such a function and aborts the process
+//!instead, unless it uses an unwinding ABI such as `extern "C-unwind"`. To clamp rather than
+//!panic, leave `#[safe_math]` off and wrap the arithmetic in
+//!`safe_math_block!(saturating!(..))`, which needs no `Result` as long as it doesn't divide.
+//!
+//!## Strict Mode
+//!
+//!With the `strict` feature, `#[safe_math(mode = strict)]` behaves like the panic mode for
+//!primitive integers, but relies on the standard `strict_*` methods (`strict_add`, ...) where
+//!the toolchain provides them (Rust 1.91 and later), with the same panic messages otherwise:
+//!
+//!```rust,ignore
+//!use safe_math::safe_math;
+//!
+//!#[safe_math(mode = strict)]
+//!fn add(a: u8, b: u8) -> u8 {
+//!    a + b
+//!}
+//!
+//!add(255, 1); // panics with "attempt to add with overflow"
+//!```
+//!
+//!## Analysis Mode
+//!
+//!With the `analysis` feature, `#[safe_math(mode = analysis)]` measures how overflow-prone a
+//!computation is without aborting it: a failing operation increments a thread-local
+//!counter, read with `overflow_count()`, and yields the unchecked result (wrapped for integers,
+//!zero for an integer division by zero). Like the panic mode, it does not require a `Result`.
+//!
+//!```rust,ignore
+//!use safe_math::{overflow_count, reset_overflow_count, safe_math};
+//!
+//!#[safe_math(mode = analysis)]
+//!fn step(x: u8) -> u8 {
+//!    x * 3
+//!}
+//!
+//!reset_overflow_count();
+//!for x in 0..=u8::MAX {
+//!    step(x);
+//!}
+//!assert_eq!(overflow_count(), 170);
+//!```
+//!
+//!## Testing Against Checked Code
+//!
+//!`assert_safe_equivalent!` checks that a `#[safe_math]` function returns the same results as a
+//!reference implementation, e.g. one written with the checked helpers, for a list of inputs:
+//!
+//!```rust
+//!use safe_math::{assert_safe_equivalent, safe_math, safe_mul, safe_sub, SafeMathError};
+//!
+//!#[safe_math]
+//!fn area(width: u16, height: u16, cutout: u16) -> Result<u16, SafeMathError> {
+//!    Ok(width * height - cutout)
+//!}
+//!
+//!assert_safe_equivalent!(
+//!    area,
+//!    |w: u16, h: u16, c: u16| safe_sub(safe_mul(w, h)?, c),
+//!    (10, 20, 5),
+//!    (1000, 1000, 0),
+//!    (1, 1, 2),
+//!);
+//!```
+//!
 //!# Roadmap
 //!
 //!Planned upcoming features:
 //!
-//!- **Option-returning functions**  
-//!  Support for functions that return `Option<T>` instead of `Result<T, SafeMathError>`.
-//!
 //!- **Crate-level macro support**  
 //!  Ability to apply `#[safe_math]` to the entire crate with a single attribute:
 //!
@@ -143,16 +554,76 @@
 // Re-export the procedural macro so users can simply `use safe_math::safe_math`.
 #[cfg(feature = "derive")]
 pub use safe_math_macros::SafeMathOps;
-pub use safe_math_macros::{safe_math, safe_math_block};
+// Referenced by the code generated by `#[derive(SafeMathOps)]`, so that it does not
+// depend on how (or whether) the user's crate names `num-traits`.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use num_traits;
+pub use safe_math_macros::{safe, safe_assert_no_overflow, safe_math, safe_math_block};
+
+// Per-type `const fn` operations, kept in their own namespace.
+pub mod konst;
 
 // Re-export the most relevant items at the crate root for a clean API.
-pub use error::SafeMathError;
-pub use ops::{SafeAdd, SafeDiv, SafeMathOps, SafeMul, SafeRem, SafeSub};
+#[cfg(feature = "analysis")]
+pub use analysis::{
+    analyze_add, analyze_div, analyze_mul, analyze_rem, analyze_sub, overflow_count,
+    reset_overflow_count, WrappingOps,
+};
+pub use batch::SafeBatch;
+pub use cmp::{safe_cmp, safe_cmp_products};
+pub use debug_wrap::{
+    debug_wrap_add, debug_wrap_div, debug_wrap_mul, debug_wrap_rem, debug_wrap_sub, DebugWrapOps,
+};
+pub use dyn_ops::DynSafeOps;
+pub use error::{Category, SafeMathError, SafeMathResult};
+#[cfg(feature = "hooks")]
+pub use hooks::{set_overflow_hook, OverflowHook};
+pub use iter::SafeIteratorExt;
+#[cfg(feature = "strict")]
+pub use ops::SafeStrict;
+pub use ops::{
+    Rounding, SafeAdd, SafeDiv, SafeDivRound, SafeIlog, SafeIncDec, SafeMathOps, SafeMathOpsRef,
+    SafeMidpoint, SafeMul, SafeMulAdd, SafeMulDiv, SafeNextPowerOfTwo, SafeRangeLen, SafeRem,
+    SafeScale, SafeShift, SafeSignCast, SafeSignedDiff, SafeSub,
+};
+pub use parse::safe_parse;
+pub use result_ext::SafeResultExt;
+pub use slice::{
+    safe_add_arrays, safe_add_slices, safe_div_arrays, safe_div_slices, safe_mul_arrays,
+    safe_mul_slices, safe_rem_arrays, safe_rem_slices, safe_sub_arrays, safe_sub_slices,
+    SliceError,
+};
+pub use sticky::Checked;
+pub use wrapper::Safe;
 
 // These helper functions are intentionally re-exported because the macro expands to them
-pub use impls::{safe_add, safe_div, safe_mul, safe_rem, safe_sub};
+pub use impls::{
+    clamping_cast, safe_add, safe_cast, safe_clamp, safe_div, safe_div_round, safe_expect,
+    safe_expect_verbose, safe_ilog, safe_ilog10, safe_ilog2, safe_midpoint, safe_mul, safe_mul_add,
+    safe_mul_div, safe_next_power_of_two, safe_range_len, safe_rem, safe_rem_nonzero, safe_shl,
+    safe_shr, safe_sign_cast, safe_signed_diff, safe_sub, safe_sub_signed, saturating_add,
+    saturating_mul, saturating_sub, wrapping_add, wrapping_mul, wrapping_sub,
+};
+#[cfg(feature = "strict")]
+pub use impls::{strict_add, strict_div, strict_mul, strict_rem, strict_sub};
 
 // Internal modules
+#[cfg(feature = "analysis")]
+mod analysis;
+mod batch;
+mod cmp;
+mod debug_wrap;
+mod dyn_ops;
 mod error;
+#[cfg(feature = "hooks")]
+mod hooks;
 mod impls;
+mod iter;
 mod ops;
+mod parse;
+mod result_ext;
+mod slice;
+mod sticky;
+mod testing;
+mod wrapper;
@@ -228,6 +228,7 @@ pub trait SafeDiv: Copy + Div<Output = Self> {
 ///
 /// * `Ok(result)` - The remainder of `self` divided by `rhs` if operation is valid
 /// * `Err(SafeMathError::DivisionByZero)` - If `rhs` is zero
+/// * `Err(SafeMathError::Overflow)` - If the remainder would overflow (e.g., MIN % -1 for signed integers)
 ///
 /// # Examples
 ///
@@ -269,6 +270,7 @@ pub trait SafeRem: Copy + Rem<Output = Self> {
     ///
     /// * `Ok(result)` - The remainder of `self` divided by `rhs` if operation is valid
     /// * `Err(SafeMathError::DivisionByZero)` - If `rhs` is zero
+    /// * `Err(SafeMathError::Overflow)` - If the remainder would overflow
     fn safe_rem(self, rhs: Self) -> Result<Self, SafeMathError>;
 }
 
@@ -277,6 +279,10 @@ pub trait SafeRem: Copy + Rem<Output = Self> {
 /// This trait combines all individual safe operation traits for convenience.
 /// Types implementing this trait can perform all basic arithmetic operations
 /// with overflow/underflow and division-by-zero protection.
+///
+/// The operators rewritten by `#[safe_math]` call the free functions bounded on the
+/// individual traits, so generic functions should use those as bounds instead, e.g.
+/// `T: SafeAdd + SafeMul` for `a * b + c`.
 pub trait SafeMathOps: Copy {
     /// Safe addition with overflow checking.
     fn safe_add(self, rhs: Self) -> Result<Self, SafeMathError>;
@@ -289,3 +295,512 @@ pub trait SafeMathOps: Copy {
     /// Safe remainder with division-by-zero checking.
     fn safe_rem(self, rhs: Self) -> Result<Self, SafeMathError>;
 }
+
+/// Safe arithmetic taking its operands by reference.
+///
+/// [`SafeMathOps`] and the individual `Safe*` traits take their operands by value and
+/// require `Copy`, which rules out types such as big numbers or fixed-point values
+/// backed by a heap allocation. This trait offers the same operations on `&self` and
+/// `&rhs` instead. It is implemented for every [`SafeMathOps`] type and can be derived
+/// with `#[SafeMathOps(add, ..., by_ref)]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeMathOpsRef};
+///
+/// assert_eq!(250u8.safe_add_ref(&5), Ok(255));
+/// assert_eq!(1u8.safe_div_ref(&0), Err(SafeMathError::DivisionByZero));
+/// ```
+pub trait SafeMathOpsRef: Sized {
+    /// Safe addition with overflow checking.
+    fn safe_add_ref(&self, rhs: &Self) -> Result<Self, SafeMathError>;
+    /// Safe subtraction with underflow checking.
+    fn safe_sub_ref(&self, rhs: &Self) -> Result<Self, SafeMathError>;
+    /// Safe multiplication with overflow checking.
+    fn safe_mul_ref(&self, rhs: &Self) -> Result<Self, SafeMathError>;
+    /// Safe division with division-by-zero checking.
+    fn safe_div_ref(&self, rhs: &Self) -> Result<Self, SafeMathError>;
+    /// Safe remainder with division-by-zero checking.
+    fn safe_rem_ref(&self, rhs: &Self) -> Result<Self, SafeMathError>;
+}
+
+/// Signed difference of two unsigned integers.
+///
+/// Subtracting two unsigned values (e.g. timestamps or indices) underflows as soon as
+/// the right-hand side is the larger one. This trait offers two ways around it:
+/// the absolute difference, which can never overflow, and the signed difference,
+/// computed in a signed type wide enough to hold the result.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::SafeSignedDiff;
+///
+/// assert_eq!(3u32.safe_signed_diff(10), Ok(7u32));
+/// assert_eq!(3u32.safe_sub_signed(10), Ok(-7i64));
+/// assert_eq!(10u32.safe_sub_signed(3), Ok(7i64));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeSub`] - Plain checked subtraction
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeSignedDiff: Copy {
+    /// Signed type the difference is computed in.
+    type Signed;
+
+    /// Returns the absolute difference between `self` and `rhs`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - Always, since the absolute difference of two unsigned
+    ///   values fits in the same type
+    fn safe_signed_diff(self, rhs: Self) -> Result<Self, SafeMathError>;
+
+    /// Returns `self - rhs` as a signed value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The signed difference of `self` and `rhs`
+    /// * `Err(SafeMathError::Overflow)` - If the difference doesn't fit in [`Self::Signed`]
+    ///   (only possible for `u128`)
+    fn safe_sub_signed(self, rhs: Self) -> Result<Self::Signed, SafeMathError>;
+}
+
+/// Checked conversion between the signed and unsigned integer types of the same width.
+///
+/// `x as u32` on an `i32` (and back) reinterprets the bits, turning `-1` into
+/// `u32::MAX`. This conversion fails instead when the value isn't representable in the
+/// other type: a negative value converted to unsigned, or an unsigned value above the
+/// signed maximum.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeSignCast};
+///
+/// assert_eq!(7i32.safe_sign_cast(), Ok(7u32));
+/// assert_eq!((-1i32).safe_sign_cast(), Err(SafeMathError::Overflow));
+/// assert_eq!(200u8.safe_sign_cast(), Err(SafeMathError::Overflow));
+/// ```
+///
+/// # See also
+///
+/// * [`safe_cast`](crate::safe_cast) - Checked conversion between any integer types
+pub trait SafeSignCast: Copy {
+    /// Integer type of the same width and the other signedness.
+    type Counterpart;
+
+    /// Converts `self` to [`Self::Counterpart`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The same value in the other type
+    /// * `Err(SafeMathError::Overflow)` - If the value isn't representable in it
+    fn safe_sign_cast(self) -> Result<Self::Counterpart, SafeMathError>;
+}
+
+/// Checked increment and decrement by one.
+///
+/// A clearer alternative to `x += 1` / `x -= 1` for counters, indices and ring
+/// buffer cursors. Implemented for every type that supports [`SafeAdd`] and
+/// [`SafeSub`] and has a [`num_traits::One`] value.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeIncDec, SafeMathError};
+///
+/// assert_eq!(41u8.safe_inc(), Ok(42));
+/// assert_eq!(u8::MAX.safe_inc(), Err(SafeMathError::Overflow));
+/// assert_eq!(0u8.safe_dec(), Err(SafeMathError::Overflow));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeAdd`] - Checked addition of arbitrary values
+/// * [`SafeSub`] - Checked subtraction of arbitrary values
+pub trait SafeIncDec: Copy {
+    /// Returns `self + 1`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The incremented value
+    /// * `Err(SafeMathError::Overflow)` - If `self` is already the maximum value
+    fn safe_inc(self) -> Result<Self, SafeMathError>;
+
+    /// Returns `self - 1`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The decremented value
+    /// * `Err(SafeMathError::Overflow)` - If `self` is already the minimum value
+    fn safe_dec(self) -> Result<Self, SafeMathError>;
+}
+
+/// Checked bit shifts.
+///
+/// Shifting by at least the bit width of the type is an error in Rust (it panics in
+/// debug builds and masks the amount in release builds). These methods report it as
+/// [`SafeMathError::ShiftOverflow`] instead, keeping it distinct from a value overflow.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeShift};
+///
+/// assert_eq!(1u8.safe_shl(7), Ok(128));
+/// assert_eq!(1u8.safe_shl(9), Err(SafeMathError::ShiftOverflow));
+/// assert_eq!((-8i32).safe_shr(1), Ok(-4));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeShift: Copy {
+    /// Shifts `self` left by `rhs` bits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - `self << rhs`
+    /// * `Err(SafeMathError::ShiftOverflow)` - If `rhs` is not less than the bit width
+    fn safe_shl(self, rhs: u32) -> Result<Self, SafeMathError>;
+
+    /// Shifts `self` right by `rhs` bits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - `self >> rhs`
+    /// * `Err(SafeMathError::ShiftOverflow)` - If `rhs` is not less than the bit width
+    fn safe_shr(self, rhs: u32) -> Result<Self, SafeMathError>;
+}
+
+/// Checked rounding up to the next power of two.
+///
+/// [`u32::next_power_of_two`] and friends panic in debug builds (and return 0 in
+/// release builds) when the next power of two doesn't fit in the type. This is a
+/// common pitfall when growing the capacity of allocators or hash tables.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeNextPowerOfTwo};
+///
+/// assert_eq!(100u32.safe_next_power_of_two(), Ok(128));
+/// assert_eq!(128u32.safe_next_power_of_two(), Ok(128));
+/// assert_eq!(usize::MAX.safe_next_power_of_two(), Err(SafeMathError::Overflow));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeNextPowerOfTwo: Copy {
+    /// Returns the smallest power of two greater than or equal to `self`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The next power of two (`1` for `0`)
+    /// * `Err(SafeMathError::Overflow)` - If the next power of two doesn't fit in the type
+    fn safe_next_power_of_two(self) -> Result<Self, SafeMathError>;
+}
+
+/// Checked integer logarithms.
+///
+/// [`u32::ilog2`] and friends panic when `self` is zero, which is easy to miss when
+/// sizing buffers from a length that may be empty. These methods report it as an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeIlog, SafeMathError};
+///
+/// assert_eq!(8u32.safe_ilog2(), Ok(3));
+/// assert_eq!(999u32.safe_ilog10(), Ok(2));
+/// assert_eq!(81u32.safe_ilog(3), Ok(4));
+/// assert_eq!(0u32.safe_ilog2(), Err(SafeMathError::DivisionByZero));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeIlog: Copy {
+    /// Returns the base 2 logarithm of `self`, rounded down.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - `self.ilog2()`
+    /// * `Err(SafeMathError::DivisionByZero)` - If `self` is zero
+    fn safe_ilog2(self) -> Result<u32, SafeMathError>;
+
+    /// Returns the base 10 logarithm of `self`, rounded down.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - `self.ilog10()`
+    /// * `Err(SafeMathError::DivisionByZero)` - If `self` is zero
+    fn safe_ilog10(self) -> Result<u32, SafeMathError>;
+
+    /// Returns the logarithm of `self` in the given `base`, rounded down.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - `self.ilog(base)`
+    /// * `Err(SafeMathError::DivisionByZero)` - If `self` is zero or `base` is less
+    ///   than 2 (`log(1)`, the divisor in a change of base, is zero)
+    fn safe_ilog(self, base: Self) -> Result<u32, SafeMathError>;
+}
+
+/// Checked length of the range `self..end`.
+///
+/// `end - start` (or `(start..end).len()`) silently assumes `start <= end`, and for
+/// signed or 128-bit indices the length may not fit in `usize`. This reports both.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeRangeLen};
+///
+/// assert_eq!(3usize.safe_range_len(10), Ok(7));
+/// assert_eq!((-128i8).safe_range_len(127), Ok(255));
+/// assert_eq!(10usize.safe_range_len(3), Err(SafeMathError::InvalidBounds));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeRangeLen: Copy {
+    /// Returns the number of values in `self..end`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(len)` - `end - self`, which is `0` for an empty range
+    /// * `Err(SafeMathError::InvalidBounds)` - If `end < self`
+    /// * `Err(SafeMathError::Overflow)` - If the length doesn't fit in `usize`
+    fn safe_range_len(self, end: Self) -> Result<usize, SafeMathError>;
+}
+
+/// Safe fused multiply-add, `self * a + b`.
+///
+/// For floats this is [`f64::mul_add`], computed with a single rounding, and fails if
+/// the result is not finite. For integers both the multiplication and the addition are
+/// checked, the multiplication first.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeMulAdd};
+///
+/// assert_eq!(3u8.safe_mul_add(4, 5), Ok(17));
+/// assert_eq!(16u8.safe_mul_add(16, 0), Err(SafeMathError::Overflow));
+/// assert_eq!(2.0f64.safe_mul_add(3.0, 1.0), Ok(7.0));
+/// assert_eq!(f64::MAX.safe_mul_add(2.0, 0.0), Err(SafeMathError::InfiniteOrNaN));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeMulAdd: Copy {
+    /// Computes `self * a + b`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The result of the fused multiply-add
+    /// * `Err(SafeMathError::Overflow)` - If either integer step overflows
+    /// * `Err(SafeMathError::InfiniteOrNaN)` - If the float result is not finite
+    fn safe_mul_add(self, a: Self, b: Self) -> Result<Self, SafeMathError>;
+}
+
+/// Safe scaling by a ratio, `self * num / den`, without intermediate overflow below
+/// 128 bits.
+///
+/// Percentages and fixed-point conversions compute `value * num / den`, whose product
+/// overflows long before the result does. This trait computes the product in the next
+/// wider integer type, so it only fails where the quotient itself doesn't fit. The
+/// quotient is rounded toward zero, like the `/` operator.
+///
+/// `u128` and `i128` have no wider type: their product must fit on its own.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeMulDiv};
+///
+/// // 75% of a balance too large to be multiplied by 75.
+/// assert_eq!((u64::MAX / 2).safe_mul_div(75, 100), Ok(6_917_529_027_641_081_855));
+/// assert_eq!((-100i8).safe_mul_div(3, 4), Ok(-75));
+/// assert_eq!(200u8.safe_mul_div(3, 2), Err(SafeMathError::Overflow));
+/// assert_eq!(1u32.safe_mul_div(1, 0), Err(SafeMathError::DivisionByZero));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeMulDiv: Copy {
+    /// Computes `self * num / den`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - The quotient, rounded toward zero
+    /// * `Err(SafeMathError::DivisionByZero)` - If `den` is zero
+    /// * `Err(SafeMathError::Overflow)` - If the quotient (or, for `u128` and `i128`, the
+    ///   product) doesn't fit
+    fn safe_mul_div(self, num: Self, den: Self) -> Result<Self, SafeMathError>;
+}
+
+/// Rounding of a quotient by [`SafeDivRound::safe_div_round`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Toward zero, like the `/` operator.
+    Trunc,
+    /// Toward negative infinity.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// To the nearest integer, with halves rounded away from zero like [`f64::round`].
+    Round,
+}
+
+/// Safe integer division with a choice of rounding.
+///
+/// Rounding by hand, e.g. `(a + b / 2) / b` to round to nearest, overflows for large
+/// `a`. This trait rounds based on the remainder instead, so it only fails where the
+/// quotient itself doesn't fit.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{Rounding, SafeDivRound, SafeMathError};
+///
+/// assert_eq!(7u32.safe_div_round(2, Rounding::Trunc), Ok(3));
+/// assert_eq!(7u32.safe_div_round(2, Rounding::Round), Ok(4));
+/// assert_eq!((-7i32).safe_div_round(2, Rounding::Floor), Ok(-4));
+/// assert_eq!((-7i32).safe_div_round(2, Rounding::Ceil), Ok(-3));
+/// assert_eq!(255u8.safe_div_round(2, Rounding::Round), Ok(128));
+/// assert_eq!(1u8.safe_div_round(0, Rounding::Ceil), Err(SafeMathError::DivisionByZero));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeDiv`] - Checked division, rounding toward zero
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeDivRound: Copy {
+    /// Divides `self` by `rhs`, rounding the quotient with `mode`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(quotient)` - The rounded quotient
+    /// * `Err(SafeMathError::DivisionByZero)` - If `rhs` is zero
+    /// * `Err(SafeMathError::Overflow)` - If the quotient overflows (`MIN / -1`)
+    fn safe_div_round(self, rhs: Self, mode: Rounding) -> Result<Self, SafeMathError>;
+}
+
+/// Average of two values without intermediate overflow.
+///
+/// The classic `(a + b) / 2` of binary searches overflows once `a + b` doesn't fit,
+/// even though the average always does. This trait computes it without the sum, like
+/// the `midpoint` methods of recent standard libraries: integers round toward negative
+/// infinity if unsigned and toward zero if signed.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{SafeMathError, SafeMidpoint};
+///
+/// assert_eq!(u32::MAX.safe_midpoint(u32::MAX), Ok(u32::MAX));
+/// assert_eq!(0u32.safe_midpoint(u32::MAX), Ok(u32::MAX / 2));
+/// assert_eq!((-3i8).safe_midpoint(0), Ok(-1));
+/// assert_eq!(f64::MAX.safe_midpoint(f64::MAX), Ok(f64::MAX));
+/// assert_eq!(f64::NAN.safe_midpoint(1.0), Err(SafeMathError::InfiniteOrNaN));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMathError`] - Error type returned on arithmetic failures
+pub trait SafeMidpoint: Copy {
+    /// Returns the average of `self` and `rhs`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(midpoint)` - Always for integers and finite floats
+    /// * `Err(SafeMathError::InfiniteOrNaN)` - If a float operand is not finite
+    fn safe_midpoint(self, rhs: Self) -> Result<Self, SafeMathError>;
+}
+
+/// Safe multiplication by a scalar of a different type.
+///
+/// [`SafeMul`] requires both operands to have the same type, which is awkward for
+/// quantities such as `Meters(5) * 3`. `SafeScale` multiplies a value by a bare
+/// scalar instead. It is typically generated by listing `scale` in
+/// `#[SafeMathOps(...)]`, which scales the first field of the struct.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_mul, SafeMathError, SafeScale};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Meters(u32);
+///
+/// impl SafeScale<u32> for Meters {
+///     fn safe_mul_scalar(self, k: u32) -> Result<Self, SafeMathError> {
+///         Ok(Meters(safe_mul(self.0, k)?))
+///     }
+/// }
+///
+/// assert_eq!(Meters(5).safe_mul_scalar(3), Ok(Meters(15)));
+/// assert_eq!(Meters(u32::MAX).safe_mul_scalar(2), Err(SafeMathError::Overflow));
+/// ```
+///
+/// # See also
+///
+/// * [`SafeMul`] - Checked multiplication of two values of the same type
+pub trait SafeScale<K>: Copy {
+    /// Multiplies `self` by the scalar `k`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(result)` - `self` scaled by `k`
+    /// * `Err(SafeMathError::Overflow)` - If the multiplication would overflow
+    fn safe_mul_scalar(self, k: K) -> Result<Self, SafeMathError>;
+}
+
+/// Arithmetic that panics on failure, used by `#[safe_math(mode = strict)]`.
+///
+/// Implemented for the primitive integer types. On toolchains providing them (Rust 1.91
+/// and later), the methods delegate to the inherent `strict_*` methods; otherwise they
+/// panic with the same messages through the `checked_*` methods. Either way they panic
+/// in every build profile, at the location of the caller.
+///
+/// This trait is only available when the `strict` feature is enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::SafeStrict;
+///
+/// assert_eq!(250u8.add_strict(5), 255);
+/// ```
+///
+/// ```rust,should_panic
+/// use safe_math::SafeStrict;
+///
+/// 250u8.add_strict(6); // panics with "attempt to add with overflow"
+/// ```
+#[cfg(feature = "strict")]
+#[diagnostic::on_unimplemented(
+    message = "Type `{Self}` cannot perform strict arithmetic.",
+    note = "`mode = strict` only supports primitive integers, use `mode = panic` for other types."
+)]
+pub trait SafeStrict: Copy {
+    /// Addition, panicking on overflow.
+    fn add_strict(self, rhs: Self) -> Self;
+    /// Subtraction, panicking on overflow.
+    fn sub_strict(self, rhs: Self) -> Self;
+    /// Multiplication, panicking on overflow.
+    fn mul_strict(self, rhs: Self) -> Self;
+    /// Division, panicking on division by zero or overflow.
+    fn div_strict(self, rhs: Self) -> Self;
+    /// Remainder, panicking on division by zero or overflow.
+    fn rem_strict(self, rhs: Self) -> Self;
+}
@@ -0,0 +1,57 @@
+//! Parsing of untrusted numeric strings into [`SafeMathError`] results.
+//!
+//! Parsing and the arithmetic that follows usually share one error path;
+//! [`safe_parse`] reports parse failures with the same error type as the
+//! safe operations, so both can be propagated with a single `?`.
+
+use crate::error::SafeMathError;
+use crate::impls::reported;
+use core::num::{IntErrorKind, ParseIntError};
+use num_traits::Num;
+
+/// Parses an integer from a string in the given radix.
+///
+/// Wraps `from_str_radix`, mapping a value that doesn't fit in `T` to
+/// `Overflow` and any other failure to `ParseError`.
+///
+/// # Arguments
+///
+/// * `s` - String to parse, with an optional leading `+` or `-` sign.
+/// * `radix` - Base of the digits, from 2 to 36.
+///
+/// # Returns
+///
+/// * `Ok(value)` - The parsed value
+/// * `Err(SafeMathError::Overflow)` - If the value is out of the range of `T`
+/// * `Err(SafeMathError::ParseError)` - If `s` is empty or contains invalid digits,
+///   or if `radix` is not in the range from 2 to 36
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_math, safe_parse, SafeMathError};
+///
+/// #[safe_math]
+/// fn double(input: &str) -> Result<u8, SafeMathError> {
+///     Ok(safe_parse::<u8>(input, 10)? * 2)
+/// }
+///
+/// assert_eq!(double("21"), Ok(42));
+/// assert_eq!(double("200"), Err(SafeMathError::Overflow));
+/// assert_eq!(double("300"), Err(SafeMathError::Overflow));
+/// assert_eq!(double("abc"), Err(SafeMathError::ParseError));
+/// ```
+pub fn safe_parse<T>(s: &str, radix: u32) -> Result<T, SafeMathError>
+where
+    T: Num<FromStrRadixErr = ParseIntError>,
+{
+    // `from_str_radix` would panic instead.
+    if !(2..=36).contains(&radix) {
+        return reported("safe_parse", Err(SafeMathError::ParseError));
+    }
+    let res = T::from_str_radix(s, radix).map_err(|err| match err.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => SafeMathError::Overflow,
+        _ => SafeMathError::ParseError,
+    });
+    reported("safe_parse", res)
+}
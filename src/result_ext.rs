@@ -0,0 +1,71 @@
+//! Chaining helpers for results of safe arithmetic operations.
+//!
+//! Outside of a `#[safe_math]` function, composing several checked operations
+//! normally requires a `?` after each step. [`SafeResultExt`] lets the steps be
+//! chained directly on the `Result`, short-circuiting on the first error.
+
+use crate::error::{SafeMathError, SafeMathResult};
+use crate::ops::SafeMathOps;
+
+/// Extension trait to chain safe arithmetic operations on a [`SafeMathResult`].
+///
+/// Each method applies the operation to the `Ok` value and returns the new result.
+/// If `self` is already an `Err`, the error is propagated unchanged and the
+/// operation is not performed.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_add, SafeMathError, SafeResultExt};
+///
+/// assert_eq!(safe_add(1u8, 2).safe_mul(10).safe_sub(5), Ok(25));
+///
+/// // The multiplication overflows, so the subtraction never runs.
+/// assert_eq!(
+///     safe_add(100u8, 100).safe_mul(2).safe_sub(5),
+///     Err(SafeMathError::Overflow)
+/// );
+/// ```
+pub trait SafeResultExt<T>: sealed::Sealed {
+    /// Chains a safe addition onto the result.
+    fn safe_add(self, rhs: T) -> SafeMathResult<T>;
+    /// Chains a safe subtraction onto the result.
+    fn safe_sub(self, rhs: T) -> SafeMathResult<T>;
+    /// Chains a safe multiplication onto the result.
+    fn safe_mul(self, rhs: T) -> SafeMathResult<T>;
+    /// Chains a safe division onto the result.
+    fn safe_div(self, rhs: T) -> SafeMathResult<T>;
+    /// Chains a safe remainder onto the result.
+    fn safe_rem(self, rhs: T) -> SafeMathResult<T>;
+}
+
+impl<T: SafeMathOps> SafeResultExt<T> for Result<T, SafeMathError> {
+    #[inline(always)]
+    fn safe_add(self, rhs: T) -> SafeMathResult<T> {
+        self.and_then(|lhs| lhs.safe_add(rhs))
+    }
+    #[inline(always)]
+    fn safe_sub(self, rhs: T) -> SafeMathResult<T> {
+        self.and_then(|lhs| lhs.safe_sub(rhs))
+    }
+    #[inline(always)]
+    fn safe_mul(self, rhs: T) -> SafeMathResult<T> {
+        self.and_then(|lhs| lhs.safe_mul(rhs))
+    }
+    #[inline(always)]
+    fn safe_div(self, rhs: T) -> SafeMathResult<T> {
+        self.and_then(|lhs| lhs.safe_div(rhs))
+    }
+    #[inline(always)]
+    fn safe_rem(self, rhs: T) -> SafeMathResult<T> {
+        self.and_then(|lhs| lhs.safe_rem(rhs))
+    }
+}
+
+mod sealed {
+    use crate::error::SafeMathError;
+
+    pub trait Sealed {}
+
+    impl<T> Sealed for Result<T, SafeMathError> {}
+}
@@ -0,0 +1,150 @@
+//! Element-wise safe arithmetic on slices and arrays.
+//!
+//! Each function combines two equal-length slices (or two arrays of the same
+//! length) element by element, stopping at the first failing element. Errors are
+//! reported as [`SliceError`], which carries the index of the failing element next
+//! to the [`SafeMathError`].
+
+use crate::error::SafeMathError;
+use crate::impls::{safe_add, safe_div, safe_mul, safe_rem, safe_sub};
+use crate::ops::{SafeAdd, SafeDiv, SafeMul, SafeRem, SafeSub};
+use core::fmt;
+
+/// Error returned by the element-wise slice operations.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{safe_add_slices, SafeMathError, SliceError};
+///
+/// assert_eq!(safe_add_slices(&[1u8, 2], &[3, 4]), Ok(vec![4, 6]));
+/// assert_eq!(
+///     safe_add_slices(&[1u8, 250], &[3, 10]),
+///     Err(SliceError::Element { index: 1, error: SafeMathError::Overflow })
+/// );
+/// assert_eq!(
+///     safe_add_slices(&[1u8, 2], &[3]),
+///     Err(SliceError::LengthMismatch { left: 2, right: 1 })
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceError {
+    /// The two slices have different lengths.
+    LengthMismatch {
+        /// Length of the left-hand slice.
+        left: usize,
+        /// Length of the right-hand slice.
+        right: usize,
+    },
+    /// The operation failed on the element at `index`.
+    Element {
+        /// Index of the first failing element.
+        index: usize,
+        /// Error reported for that element.
+        error: SafeMathError,
+    },
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SliceError::LengthMismatch { left, right } => {
+                write!(f, "slice length mismatch: {left} != {right}")
+            }
+            SliceError::Element { index, error } => write!(f, "{error} at index {index}"),
+        }
+    }
+}
+
+impl std::error::Error for SliceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SliceError::LengthMismatch { .. } => None,
+            SliceError::Element { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Applies `op` to each pair of elements of `a` and `b`.
+fn zip_with<T: Copy>(
+    a: &[T],
+    b: &[T],
+    op: fn(T, T) -> Result<T, SafeMathError>,
+) -> Result<Vec<T>, SliceError> {
+    if a.len() != b.len() {
+        return Err(SliceError::LengthMismatch {
+            left: a.len(),
+            right: b.len(),
+        });
+    }
+    a.iter()
+        .zip(b)
+        .enumerate()
+        .map(|(index, (&x, &y))| op(x, y).map_err(|error| SliceError::Element { index, error }))
+        .collect()
+}
+
+/// Applies `op` to each pair of elements of `a` and `b`, in place of `a`.
+fn zip_arrays_with<T: Copy, const N: usize>(
+    mut a: [T; N],
+    b: [T; N],
+    op: fn(T, T) -> Result<T, SafeMathError>,
+) -> Result<[T; N], SliceError> {
+    for (index, (x, y)) in a.iter_mut().zip(b).enumerate() {
+        *x = op(*x, y).map_err(|error| SliceError::Element { index, error })?;
+    }
+    Ok(a)
+}
+
+macro_rules! impl_slice_ops {
+    ($($fn_name:ident, $array_fn_name:ident => ($op:ident, $trait:ident, $desc:literal)),* $(,)?) => {
+        $(
+            #[doc = concat!("Element-wise safe ", $desc, " of two slices.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `a` - Left-hand operands.
+            /// * `b` - Right-hand operands, same length as `a`.
+            ///
+            /// # Returns
+            ///
+            /// * `Ok(values)` - The results, in order
+            /// * `Err(SliceError::LengthMismatch { .. })` - If the slices have different lengths
+            /// * `Err(SliceError::Element { index, error })` - For the first failing element
+            #[inline]
+            pub fn $fn_name<T: $trait>(a: &[T], b: &[T]) -> Result<Vec<T>, SliceError> {
+                zip_with(a, b, $op)
+            }
+
+            #[doc = concat!("Element-wise safe ", $desc, " of two arrays.")]
+            ///
+            /// The arrays can't be used with the operators directly, as `[T; N]` doesn't
+            /// implement them (nor, therefore, the `Safe*` traits).
+            ///
+            /// # Arguments
+            ///
+            /// * `a` - Left-hand operands.
+            /// * `b` - Right-hand operands.
+            ///
+            /// # Returns
+            ///
+            /// * `Ok(values)` - The results, in order
+            /// * `Err(SliceError::Element { index, error })` - For the first failing element
+            #[inline]
+            pub fn $array_fn_name<T: $trait, const N: usize>(
+                a: [T; N],
+                b: [T; N],
+            ) -> Result<[T; N], SliceError> {
+                zip_arrays_with(a, b, $op)
+            }
+        )*
+    };
+}
+
+impl_slice_ops!(
+    safe_add_slices, safe_add_arrays => (safe_add, SafeAdd, "addition"),
+    safe_sub_slices, safe_sub_arrays => (safe_sub, SafeSub, "subtraction"),
+    safe_mul_slices, safe_mul_arrays => (safe_mul, SafeMul, "multiplication"),
+    safe_div_slices, safe_div_arrays => (safe_div, SafeDiv, "division"),
+    safe_rem_slices, safe_rem_arrays => (safe_rem, SafeRem, "remainder"),
+);
@@ -0,0 +1,90 @@
+//! Arithmetic with a sticky overflow flag.
+//!
+//! [`Checked`] keeps computing after an overflow, using the wrapped result, and
+//! remembers that an overflow happened somewhere along the chain. This suits
+//! telemetry and pipelines that should not abort at the first overflow but must
+//! still report it at the end.
+
+use crate::error::SafeMathError;
+use num_traits::ops::overflowing::{OverflowingAdd, OverflowingMul, OverflowingSub};
+
+/// A value together with a flag recording whether any operation producing it overflowed.
+///
+/// Once set, the `overflowed` flag stays set for every value computed from this one.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{Checked, SafeMathError};
+///
+/// let total = Checked::new(200u8).safe_add(100).safe_sub(50).safe_mul(2);
+/// // 200 + 100 wraps to 44, then (44 - 50) wraps to 250 and 250 * 2 wraps to 244.
+/// assert_eq!(total.value, 244);
+/// assert!(total.overflowed);
+/// assert_eq!(total.into_result(), Err(SafeMathError::Overflow));
+///
+/// assert_eq!(Checked::new(2u8).safe_mul(3).into_result(), Ok(6));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Checked<T> {
+    /// Result of the operations, wrapped around on overflow.
+    pub value: T,
+    /// Whether any operation leading to `value` overflowed.
+    pub overflowed: bool,
+}
+
+impl<T> Checked<T> {
+    /// Wraps a value that has not overflowed.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            overflowed: false,
+        }
+    }
+
+    /// Returns the value, or `SafeMathError::Overflow` if any operation overflowed.
+    pub fn into_result(self) -> Result<T, SafeMathError> {
+        if self.overflowed {
+            Err(SafeMathError::Overflow)
+        } else {
+            Ok(self.value)
+        }
+    }
+
+    fn with(self, (value, overflowed): (T, bool)) -> Self {
+        Self {
+            value,
+            overflowed: self.overflowed || overflowed,
+        }
+    }
+}
+
+impl<T: OverflowingAdd> Checked<T> {
+    /// Adds `rhs`, wrapping and setting the flag on overflow.
+    pub fn safe_add(self, rhs: T) -> Self {
+        let res = self.value.overflowing_add(&rhs);
+        self.with(res)
+    }
+}
+
+impl<T: OverflowingSub> Checked<T> {
+    /// Subtracts `rhs`, wrapping and setting the flag on overflow.
+    pub fn safe_sub(self, rhs: T) -> Self {
+        let res = self.value.overflowing_sub(&rhs);
+        self.with(res)
+    }
+}
+
+impl<T: OverflowingMul> Checked<T> {
+    /// Multiplies by `rhs`, wrapping and setting the flag on overflow.
+    pub fn safe_mul(self, rhs: T) -> Self {
+        let res = self.value.overflowing_mul(&rhs);
+        self.with(res)
+    }
+}
+
+impl<T> From<T> for Checked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
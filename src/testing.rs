@@ -0,0 +1,56 @@
+//! Helpers for testing code that uses `#[safe_math]`.
+//!
+//! A `#[safe_math]` function is meant to behave exactly like the same computation
+//! written by hand with the checked helpers. [`assert_safe_equivalent!`] checks that
+//! for a set of inputs, like the crate's own generated tests do for every operator.
+
+/// Asserts that two functions return equal results for each of the given inputs.
+///
+/// The first argument is typically a `#[safe_math]` function, and the second the same
+/// computation written with [`safe_add`](crate::safe_add) and friends (or any other
+/// reference implementation). Each following argument is a parenthesized list of
+/// arguments, passed to both functions; it is evaluated once per function.
+///
+/// # Panics
+///
+/// Panics if the results differ for any of the inputs, naming the inputs.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{assert_safe_equivalent, safe_add, safe_math, safe_mul, SafeMathError};
+///
+/// #[safe_math]
+/// fn total(price: u8, quantity: u8, fee: u8) -> Result<u8, SafeMathError> {
+///     Ok(price * quantity + fee)
+/// }
+///
+/// fn total_checked(price: u8, quantity: u8, fee: u8) -> Result<u8, SafeMathError> {
+///     safe_add(safe_mul(price, quantity)?, fee)
+/// }
+///
+/// assert_safe_equivalent!(total, total_checked, (10, 3, 5), (100, 3, 0), (50, 5, 6));
+///
+/// // Closures work as well.
+/// assert_safe_equivalent!(
+///     total,
+///     |p: u8, q: u8, f: u8| safe_add(safe_mul(p, q)?, f),
+///     (10, 3, 5),
+///     (0, 0, 0),
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_safe_equivalent {
+    ($safe:expr, $checked:expr $(, ($($arg:expr),* $(,)?))* $(,)?) => {{
+        let safe = $safe;
+        let checked = $checked;
+        $(
+            assert_eq!(
+                safe($($arg),*),
+                checked($($arg),*),
+                "results differ for inputs ({})",
+                stringify!($($arg),*),
+            );
+        )*
+    }};
+}
@@ -0,0 +1,87 @@
+//! Checked operators on a wrapper type.
+//!
+//! [`Safe`] implements the arithmetic operators with checked semantics, returning a
+//! [`SafeMathResult`] instead of overflowing. This gives the behavior of
+//! `#[safe_math]` to code that prefers not to use the attribute macro.
+
+use crate::error::SafeMathResult;
+use crate::impls::{safe_add, safe_div, safe_mul, safe_rem, safe_sub};
+use crate::ops::{SafeAdd, SafeDiv, SafeMul, SafeRem, SafeSub};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Rem, Sub};
+
+/// A value whose arithmetic operators are checked.
+///
+/// `Safe(a) + Safe(b)` returns a `SafeMathResult<Safe<T>>`. The operators are also
+/// implemented with such a result on the left-hand side, so chains short-circuit on
+/// the first error without a `?` after each step. A result on the right-hand side,
+/// e.g. a parenthesized `(Safe(b) * Safe(c))`, needs a `?` first.
+///
+/// # Examples
+///
+/// ```rust
+/// use safe_math::{Safe, SafeMathError};
+///
+/// let total = Safe(200u8) + Safe(50) - Safe(10);
+/// assert_eq!(total, Ok(Safe(240)));
+///
+/// // The multiplication overflows, so the division never runs.
+/// assert_eq!(Safe(200u8) * Safe(2) / Safe(4), Err(SafeMathError::Overflow));
+/// assert_eq!(Safe(1u8) / Safe(0), Err(SafeMathError::DivisionByZero));
+///
+/// let value: u8 = (Safe(6u8) * Safe(7))?.into_inner();
+/// # Ok::<(), SafeMathError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Safe<T>(pub T);
+
+impl<T> Safe<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Safe<T> {
+    fn from(value: T) -> Self {
+        Safe(value)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Safe<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_safe_operators {
+    ($($op_trait:ident, $op:ident, $trait:ident, $safe:ident);* $(;)?) => {
+        $(
+            impl<T: $trait> $op_trait for Safe<T> {
+                type Output = SafeMathResult<Safe<T>>;
+
+                #[inline(always)]
+                fn $op(self, rhs: Self) -> Self::Output {
+                    $safe(self.0, rhs.0).map(Safe)
+                }
+            }
+
+            impl<T: $trait> $op_trait<Safe<T>> for SafeMathResult<Safe<T>> {
+                type Output = SafeMathResult<Safe<T>>;
+
+                #[inline(always)]
+                fn $op(self, rhs: Safe<T>) -> Self::Output {
+                    self.and_then(|lhs| lhs.$op(rhs))
+                }
+            }
+        )*
+    };
+}
+
+impl_safe_operators!(
+    Add, add, SafeAdd, safe_add;
+    Sub, sub, SafeSub, safe_sub;
+    Mul, mul, SafeMul, safe_mul;
+    Div, div, SafeDiv, safe_div;
+    Rem, rem, SafeRem, safe_rem;
+);
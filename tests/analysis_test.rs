@@ -0,0 +1,78 @@
+#![cfg(feature = "analysis")]
+
+use safe_math::{overflow_count, reset_overflow_count, safe_math};
+
+#[safe_math(mode = analysis)]
+fn accumulate(values: &[u8]) -> u8 {
+    let mut acc = 0;
+    for &v in values {
+        acc += v;
+    }
+    acc
+}
+
+#[safe_math(mode = analysis)]
+fn ratio(a: i32, b: i32) -> i32 {
+    a / b
+}
+
+#[safe_math(mode = analysis)]
+fn scale(x: f64, k: f64) -> f64 {
+    x * k
+}
+
+#[test]
+fn test_counts_every_overflow_in_a_loop() {
+    reset_overflow_count();
+    // 0 + 100 * 10 wraps around 256 three times: at the 3rd, 6th and 8th addition.
+    let result = accumulate(&[100; 10]);
+    assert_eq!(result, (1000 % 256) as u8);
+    assert_eq!(overflow_count(), 3);
+}
+
+#[test]
+fn test_results_match_wrapping_arithmetic() {
+    reset_overflow_count();
+    for n in 0..=u8::MAX {
+        assert_eq!(accumulate(&[n, 200]), n.wrapping_add(200));
+    }
+    // Every `n >= 56` overflows.
+    assert_eq!(overflow_count(), 200);
+}
+
+#[test]
+fn test_division_failures() {
+    reset_overflow_count();
+    assert_eq!(ratio(7, 2), 3);
+    assert_eq!(ratio(7, 0), 0);
+    assert_eq!(ratio(i32::MIN, -1), i32::MIN);
+    assert_eq!(overflow_count(), 2);
+}
+
+#[test]
+fn test_float_failures() {
+    reset_overflow_count();
+    assert_eq!(scale(2.0, 3.0), 6.0);
+    assert_eq!(scale(f64::MAX, 2.0), f64::INFINITY);
+    assert_eq!(overflow_count(), 1);
+}
+
+#[test]
+fn test_reset_returns_previous_count() {
+    reset_overflow_count();
+    accumulate(&[255, 1, 255, 1]);
+    assert_eq!(reset_overflow_count(), 2);
+    assert_eq!(overflow_count(), 0);
+}
+
+#[test]
+fn test_counter_is_thread_local() {
+    reset_overflow_count();
+    std::thread::spawn(|| {
+        accumulate(&[255, 1]);
+        assert_eq!(overflow_count(), 1);
+    })
+    .join()
+    .unwrap();
+    assert_eq!(overflow_count(), 0);
+}
@@ -0,0 +1,75 @@
+use safe_math::{safe_math, SafeMathError, SafeMathResult};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Minimal executor: the futures under test never wait on anything, so
+/// busy-polling until they resolve is enough.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+async fn fetch(value: u64) -> u64 {
+    value
+}
+
+#[safe_math]
+async fn price(a: u64, b: u64) -> Result<u64, SafeMathError> {
+    Ok(fetch(a).await * b)
+}
+
+#[safe_math]
+async fn total(a: u64, b: u64) -> Result<u64, SafeMathError> {
+    let mut sum = fetch(a).await;
+    sum += fetch(b).await;
+    Ok(sum - fetch(1).await)
+}
+
+/// `async fn` declaring its output through a `Result` alias.
+#[safe_math]
+async fn area(w: u32, h: u32) -> SafeMathResult<u32> {
+    Ok(fetch(w as u64).await as u32 * h)
+}
+
+/// Plain fn returning `impl Future`: the `?` leaves the `async` block, whose
+/// output is the `Result`.
+#[safe_math]
+#[allow(clippy::manual_async_fn)]
+fn deferred_sum(a: u8, b: u8) -> impl Future<Output = Result<u8, SafeMathError>> {
+    async move { Ok(a + b) }
+}
+
+#[test]
+fn test_async_fn() {
+    assert_eq!(block_on(price(6, 7)), Ok(42));
+    assert_eq!(block_on(total(2, 3)), Ok(4));
+}
+
+#[test]
+fn test_async_fn_overflow() {
+    assert_eq!(block_on(price(u64::MAX, 2)), Err(SafeMathError::Overflow));
+    assert_eq!(block_on(total(u64::MAX, 1)), Err(SafeMathError::Overflow));
+    assert_eq!(block_on(total(0, 0)), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_result_alias_and_impl_future() {
+    assert_eq!(block_on(area(6, 7)), Ok(42));
+    assert_eq!(block_on(area(u32::MAX, 2)), Err(SafeMathError::Overflow));
+    assert_eq!(block_on(deferred_sum(1, 2)), Ok(3));
+    assert_eq!(block_on(deferred_sum(255, 1)), Err(SafeMathError::Overflow));
+}
@@ -0,0 +1,46 @@
+use safe_math::{safe_add, safe_div, safe_mul, safe_sub, SafeBatch, SafeMathError};
+
+#[test]
+fn test_batch_collects_all_errors() {
+    let mut batch = SafeBatch::new();
+
+    let values = [
+        batch.try_add(safe_add(250u8, 10)),
+        batch.try_add(safe_sub(10u8, 3)),
+        batch.try_add(safe_div(1u8, 0)),
+        batch.try_add(safe_mul(16u8, 16)),
+        batch.try_add(safe_add(1u8, 1)),
+    ];
+
+    assert_eq!(values, [None, Some(7), None, None, Some(2)]);
+    assert!(!batch.is_ok());
+    assert_eq!(batch.error_count(), 3);
+    assert_eq!(
+        batch.errors(),
+        &[
+            SafeMathError::Overflow,
+            SafeMathError::DivisionByZero,
+            SafeMathError::Overflow
+        ]
+    );
+    assert_eq!(
+        batch.into_result(),
+        Err(vec![
+            SafeMathError::Overflow,
+            SafeMathError::DivisionByZero,
+            SafeMathError::Overflow
+        ])
+    );
+}
+
+#[test]
+fn test_batch_without_errors() {
+    let mut batch = SafeBatch::new();
+
+    assert_eq!(batch.try_add(safe_add(1i32, 2)), Some(3));
+    assert_eq!(batch.try_add(safe_mul(2.0f64, 3.0)), Some(6.0));
+
+    assert!(batch.is_ok());
+    assert_eq!(batch.error_count(), 0);
+    assert_eq!(batch.into_result(), Ok(()));
+}
@@ -0,0 +1,86 @@
+use safe_math::{safe_math, safe_math_block, SafeMathError};
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Math(SafeMathError),
+    Parse(ParseIntError),
+}
+
+impl From<SafeMathError> for AppError {
+    fn from(err: SafeMathError) -> Self {
+        AppError::Math(err)
+    }
+}
+
+impl From<ParseIntError> for AppError {
+    fn from(err: ParseIntError) -> Self {
+        AppError::Parse(err)
+    }
+}
+
+fn total_price(quantity: &str, unit_price: u32) -> Result<u32, AppError> {
+    let total = safe_math_block!({
+        // A user `?` on a non-math `Result` next to rewritten arithmetic.
+        let quantity: u32 = quantity.parse()?;
+        quantity * unit_price + 1
+    });
+    Ok(total)
+}
+
+#[test]
+fn test_user_question_mark_in_block() {
+    assert_eq!(total_price("3", 10), Ok(31));
+}
+
+#[test]
+fn test_user_error_propagates() {
+    let parse_err = "x".parse::<u32>().unwrap_err();
+    assert_eq!(total_price("x", 10), Err(AppError::Parse(parse_err)));
+}
+
+#[test]
+fn test_math_error_propagates() {
+    assert_eq!(
+        total_price("2", u32::MAX),
+        Err(AppError::Math(SafeMathError::Overflow))
+    );
+}
+
+fn lookup(key: &str) -> Result<u32, AppError> {
+    Ok(key.parse()?)
+}
+
+#[safe_math]
+fn mixed(key: &str, extra: u32) -> Result<u32, AppError> {
+    let base = lookup(key)?;
+    let parsed: u32 = key.parse()?;
+    Ok(base * 2 + parsed + extra)
+}
+
+#[safe_math]
+fn inline_operand(key: &str, extra: u32) -> Result<u32, AppError> {
+    // The user's `?` is itself an operand of the rewritten operator.
+    Ok(lookup(key)? + extra)
+}
+
+#[test]
+fn test_user_question_mark_in_function() {
+    assert_eq!(mixed("5", 1), Ok(16));
+    assert_eq!(inline_operand("5", 1), Ok(6));
+}
+
+#[test]
+fn test_user_and_math_errors_in_function() {
+    let parse_err = "x".parse::<u32>().unwrap_err();
+    assert_eq!(mixed("x", 1), Err(AppError::Parse(parse_err.clone())));
+    assert_eq!(inline_operand("x", 1), Err(AppError::Parse(parse_err)));
+    assert_eq!(
+        mixed("5", u32::MAX),
+        Err(AppError::Math(SafeMathError::Overflow))
+    );
+    assert_eq!(
+        inline_operand("1", u32::MAX),
+        Err(AppError::Math(SafeMathError::Overflow))
+    );
+}
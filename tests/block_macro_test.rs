@@ -0,0 +1,93 @@
+use safe_math::{safe_math, safe_math_block, SafeMathError};
+
+fn average3(a: u8, b: u8, c: u8) -> Result<u8, SafeMathError> {
+    Ok(safe_math_block!((a + b + c) / 3))
+}
+
+fn average(values: &[u32], count: u32) -> Result<u32, SafeMathError> {
+    let mean = safe_math_block!({
+        let mut sum = 0;
+        for &v in values {
+            sum += v;
+        }
+        sum / count
+    });
+    Ok(mean)
+}
+
+#[safe_math]
+fn average3_attr(a: u8, b: u8, c: u8) -> Result<u8, SafeMathError> {
+    Ok((a + b + c) / 3)
+}
+
+#[test]
+fn test_block_average() {
+    assert_eq!(average3(10, 20, 30), Ok(20));
+    assert_eq!(average3(85, 85, 85), Ok(85));
+    // The sum overflows even though the average would fit.
+    assert_eq!(average3(100, 100, 100), Err(SafeMathError::Overflow));
+    assert_eq!(average3(255, 1, 0), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_block_average_matches_attribute() {
+    for (a, b, c) in [(10, 20, 30), (85, 85, 85), (100, 100, 100), (0, 255, 1)] {
+        assert_eq!(average3(a, b, c), average3_attr(a, b, c));
+    }
+}
+
+#[test]
+fn test_block_average_with_statements() {
+    assert_eq!(average(&[1, 2, 3, 6], 4), Ok(3));
+    assert_eq!(average(&[u32::MAX, 1], 2), Err(SafeMathError::Overflow));
+    // The count is only divided by once the sum succeeded.
+    assert_eq!(average(&[], 0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(average(&[u32::MAX, 1], 0), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_block_average_in_closure() {
+    let mean = |a: i32, b: i32, n: i32| -> Result<i32, SafeMathError> {
+        Ok(safe_math_block!((a + b) / n))
+    };
+
+    assert_eq!(mean(-4, 10, 2), Ok(3));
+    assert_eq!(mean(i32::MAX, 1, 2), Err(SafeMathError::Overflow));
+    assert_eq!(mean(1, 2, 0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(mean(i32::MIN, 0, -1), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_saturating_block() {
+    // No `Result` needed: nothing in the block can fail.
+    fn clamp_sum(a: u8, b: u8) -> u8 {
+        safe_math_block!(saturating, { a + b })
+    }
+
+    assert_eq!(safe_math_block!(saturating, { 255u8 + 10 }), 255);
+    assert_eq!(clamp_sum(200, 100), 255);
+    assert_eq!(clamp_sum(20, 10), 30);
+
+    let depth = safe_math_block!(saturating, {
+        let mut depth = 3u8;
+        depth -= 5;
+        depth * 2
+    });
+    assert_eq!(depth, 0);
+}
+
+#[test]
+fn test_wrapping_block() {
+    assert_eq!(safe_math_block!(wrapping, { 255u8 + 10 }), 9);
+    assert_eq!(safe_math_block!(wrapping, 0u32 - 1), u32::MAX);
+}
+
+#[test]
+fn test_policy_block_division_is_checked() {
+    fn ratio(a: u8, b: u8, divisor: u8) -> Result<u8, SafeMathError> {
+        Ok(safe_math_block!(saturating, { (a + b) / divisor }))
+    }
+
+    assert_eq!(ratio(200, 100, 5), Ok(51));
+    assert_eq!(ratio(1, 2, 0), Err(SafeMathError::DivisionByZero));
+}
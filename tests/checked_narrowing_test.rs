@@ -0,0 +1,106 @@
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math(checked_narrowing)]
+fn to_byte(big: u32) -> Result<u8, SafeMathError> {
+    let small: u8 = big;
+    Ok(small)
+}
+
+#[safe_math(checked_narrowing)]
+fn average(total: u64, count: u64) -> Result<u16, SafeMathError> {
+    // The division is checked first, then the conversion of its result.
+    let avg: u16 = total / count;
+    Ok(avg)
+}
+
+#[test]
+fn test_checked_narrowing() {
+    assert_eq!(to_byte(200), Ok(200));
+    assert_eq!(to_byte(255), Ok(255));
+    assert_eq!(to_byte(256), Err(SafeMathError::Overflow));
+    assert_eq!(to_byte(u32::MAX), Err(SafeMathError::Overflow));
+    assert_eq!(average(3000, 3), Ok(1000));
+    assert_eq!(average(u64::MAX, 1), Err(SafeMathError::Overflow));
+    assert_eq!(average(1, 0), Err(SafeMathError::DivisionByZero));
+}
+
+#[test]
+fn test_checked_narrowing_sign_change() {
+    #[safe_math(checked_narrowing)]
+    fn to_unsigned(value: i32) -> Result<u32, SafeMathError> {
+        let unsigned: u32 = value;
+        Ok(unsigned)
+    }
+
+    #[safe_math(checked_narrowing)]
+    fn to_signed(value: u64) -> Result<i64, SafeMathError> {
+        let signed: i64 = value;
+        Ok(signed)
+    }
+
+    assert_eq!(to_unsigned(7), Ok(7));
+    assert_eq!(to_unsigned(-1), Err(SafeMathError::Overflow));
+    assert_eq!(to_signed(7), Ok(7));
+    assert_eq!(to_signed(u64::MAX), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_checked_narrowing_same_and_wider_types() {
+    #[safe_math(checked_narrowing)]
+    fn widen(a: u8, b: u8) -> Result<(u8, u64), SafeMathError> {
+        let same: u8 = a + b;
+        let wide: u64 = a;
+        let literal: u8 = 200;
+        Ok((same, wide + u64::from(literal)))
+    }
+
+    assert_eq!(widen(1, 2), Ok((3, 201)));
+    assert_eq!(widen(255, 1), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_checked_narrowing_literals_keep_their_type() {
+    #[safe_math(checked_narrowing)]
+    fn constants() -> Result<(u64, u64, i64), SafeMathError> {
+        // Typed by the annotation, not by the `i32` fallback.
+        let big: u64 = 5_000_000_000;
+        let mask: u64 = 1 << 40;
+        let sum: i64 = -(3_000_000_000 + 1);
+        Ok((big, mask, sum))
+    }
+
+    assert_eq!(constants(), Ok((5_000_000_000, 1 << 40, -3_000_000_001)));
+}
+
+#[test]
+fn test_checked_narrowing_other_lets_untouched() {
+    #[safe_math(checked_narrowing)]
+    fn lets(values: &[u32]) -> Result<usize, SafeMathError> {
+        // Neither annotated with an integer type, so not converted.
+        let inferred = values.len();
+        let parsed: Vec<u8> = values.iter().map(|&v| v as u8).collect();
+        Ok(inferred + parsed.len())
+    }
+
+    assert_eq!(lets(&[1, 300]), Ok(4));
+}
+
+#[test]
+fn test_checked_narrowing_option_and_panic_modes() {
+    #[safe_math(checked_narrowing)]
+    fn to_byte(big: u32) -> Option<u8> {
+        let small: u8 = big;
+        Some(small)
+    }
+
+    #[safe_math(mode = panic, checked_narrowing)]
+    fn to_byte_or_panic(big: u32) -> u8 {
+        let small: u8 = big;
+        small
+    }
+
+    assert_eq!(to_byte(7), Some(7));
+    assert_eq!(to_byte(256), None);
+    assert_eq!(to_byte_or_panic(7), 7);
+    assert!(std::panic::catch_unwind(|| to_byte_or_panic(256)).is_err());
+}
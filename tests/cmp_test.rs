@@ -0,0 +1,31 @@
+use safe_math::*;
+use std::cmp::Ordering;
+
+#[test]
+fn test_cmp_products() {
+    assert_eq!(safe_cmp_products(3u32, 4, 2, 6), Ok(Ordering::Equal));
+    assert_eq!(safe_cmp_products(3u32, 4, 2, 7), Ok(Ordering::Less));
+    // 16 * 16 overflows `u8` while 15 * 17 = 255 doesn't.
+    assert_eq!(safe_cmp_products(16u8, 16, 15, 17), Ok(Ordering::Greater));
+    assert_eq!(safe_cmp_products(15u8, 17, 16, 16), Ok(Ordering::Less));
+    assert_eq!(
+        safe_cmp_products(u64::MAX, 2, u64::MAX, 3),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_cmp_results() {
+    assert_eq!(
+        safe_cmp(safe_sub(5u8, 1), safe_add(2u8, 2)),
+        Ok(Ordering::Equal)
+    );
+    assert_eq!(
+        safe_cmp(safe_div(1u8, 0), safe_add(255u8, 1)),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(
+        safe_cmp(safe_add(255u8, 1), safe_div(1u8, 0)),
+        Err(SafeMathError::DivisionByZero)
+    );
+}
@@ -1,14 +1,16 @@
 #![allow(dead_code)]
 
 use proptest::prelude::*;
-use safe_math::{safe_math, SafeAdd, SafeDiv, SafeMathOps, SafeMul, SafeRem, SafeSub};
+use safe_math::{
+    safe_math, SafeAdd, SafeDiv, SafeMathError, SafeMathOps, SafeMul, SafeRem, SafeSub,
+};
 
 // Basic test operations
 macro_rules! test_operation_macro {
     ($(($name:ident, $op:tt, $trait:ident)),*) => {
         $(
             #[safe_math]
-            pub fn $name<T: SafeMathOps + $trait>(a: T, b: T) -> Result<T, ()> {
+            pub fn $name<T: SafeMathOps + $trait>(a: T, b: T) -> Result<T, SafeMathError> {
                 Ok(a $op b)
             }
         )*
@@ -66,13 +68,13 @@ impl Arbitrary for Expression {
 
 impl Expression {
     #[safe_math]
-    pub fn evaluate_expression(&self) -> Result<u8, ()> {
+    pub fn evaluate_expression(&self) -> Result<u8, SafeMathError> {
         match self {
             Expression::Add(left, right) => {
                 let left = left.evaluate_expression()?;
                 let right = right.evaluate_expression()?;
                 let result1 = left + right;
-                let result2 = left.checked_add(right).ok_or(())?;
+                let result2 = left.checked_add(right).ok_or(SafeMathError::Overflow)?;
                 assert_eq!(result1, result2);
                 Ok(result1)
             }
@@ -80,7 +82,7 @@ impl Expression {
                 let left = left.evaluate_expression()?;
                 let right = right.evaluate_expression()?;
                 let result1 = left - right;
-                let result2 = left.checked_sub(right).ok_or(())?;
+                let result2 = left.checked_sub(right).ok_or(SafeMathError::Overflow)?;
                 assert_eq!(result1, result2);
                 Ok(result1)
             }
@@ -88,7 +90,7 @@ impl Expression {
                 let left = left.evaluate_expression()?;
                 let right = right.evaluate_expression()?;
                 let result1 = left * right;
-                let result2 = left.checked_mul(right).ok_or(())?;
+                let result2 = left.checked_mul(right).ok_or(SafeMathError::Overflow)?;
                 assert_eq!(result1, result2);
                 Ok(result1)
             }
@@ -96,7 +98,7 @@ impl Expression {
                 let left = left.evaluate_expression()?;
                 let right = right.evaluate_expression()?;
                 let result1 = left / right;
-                let result2 = left.checked_div(right).ok_or(())?;
+                let result2 = left.checked_div(right).ok_or(SafeMathError::Overflow)?;
                 assert_eq!(result1, result2);
                 Ok(result1)
             }
@@ -104,7 +106,7 @@ impl Expression {
                 let left = left.evaluate_expression()?;
                 let right = right.evaluate_expression()?;
                 let result1 = left % right;
-                let result2 = left.checked_rem(right).ok_or(())?;
+                let result2 = left.checked_rem(right).ok_or(SafeMathError::Overflow)?;
                 assert_eq!(result1, result2);
                 Ok(result1)
             }
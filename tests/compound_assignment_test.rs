@@ -1,4 +1,4 @@
-use safe_math::safe_math;
+use safe_math::{safe_math, SafeMathError};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Test that compound assignments don't cause side effects by evaluating
@@ -15,7 +15,7 @@ fn test_compound_assignment_no_side_effects() {
     }
 
     #[safe_math]
-    fn test_add_assign() -> Result<u8, ()> {
+    fn test_add_assign() -> Result<u8, SafeMathError> {
         let mut arr = [10u8, 20u8, 30u8];
         // This should only call get_index() once, not twice
         arr[get_index()] += 5;
@@ -39,7 +39,7 @@ fn test_compound_assignment_no_side_effects() {
 #[test]
 fn test_all_compound_assignments() {
     #[safe_math]
-    fn test_compound_ops() -> Result<(u8, u8, u8, u8, u8), ()> {
+    fn test_compound_ops() -> Result<(u8, u8, u8, u8, u8), SafeMathError> {
         let mut a = 10u8;
         let mut b = 20u8;
         let mut c = 6u8;
@@ -61,7 +61,7 @@ fn test_all_compound_assignments() {
 #[test]
 fn test_compound_assignment_overflow() {
     #[safe_math]
-    fn test_overflow() -> Result<u8, ()> {
+    fn test_overflow() -> Result<u8, SafeMathError> {
         let mut x = 255u8;
         x += 1; // This should overflow and return an error
         Ok(x)
@@ -73,7 +73,7 @@ fn test_compound_assignment_overflow() {
 #[test]
 fn test_compound_assignment_underflow() {
     #[safe_math]
-    fn test_underflow() -> Result<u8, ()> {
+    fn test_underflow() -> Result<u8, SafeMathError> {
         let mut x = 0u8;
         x -= 1; // This should underflow and return an error
         Ok(x)
@@ -81,3 +81,217 @@ fn test_compound_assignment_underflow() {
 
     assert!(test_underflow().is_err());
 }
+
+#[test]
+fn test_compound_assignment_rhs_is_checked() {
+    #[safe_math]
+    fn add_product(acc: &mut u8, a: u8, b: u8) -> Result<(), SafeMathError> {
+        *acc += a * b;
+        Ok(())
+    }
+
+    let mut acc = 1;
+    assert_eq!(
+        add_product(&mut acc, u8::MAX, 2),
+        Err(SafeMathError::Overflow)
+    );
+    // The target is only written once both operations succeeded.
+    assert_eq!(acc, 1);
+
+    let mut acc = 250;
+    assert_eq!(add_product(&mut acc, 3, 2), Err(SafeMathError::Overflow));
+    assert_eq!(acc, 250);
+
+    assert_eq!(add_product(&mut acc, 1, 5), Ok(()));
+    assert_eq!(acc, 255);
+}
+
+#[test]
+fn test_compound_assignment_through_mut_reference() {
+    #[safe_math]
+    fn accumulate(acc: &mut u64, delta: u64, factor: u64) -> Result<(), SafeMathError> {
+        *acc += delta;
+        *acc *= factor;
+        Ok(())
+    }
+
+    #[safe_math]
+    fn accumulate_generic<T: safe_math::SafeAdd + safe_math::SafeMul>(
+        acc: &mut T,
+        x: T,
+    ) -> Result<(), SafeMathError> {
+        *acc += x;
+        *acc *= x;
+        Ok(())
+    }
+
+    #[safe_math]
+    fn accumulate_nested(acc: &mut &mut u64, delta: u64) -> Result<(), SafeMathError> {
+        **acc += delta;
+        Ok(())
+    }
+
+    let mut acc = 10;
+    assert_eq!(accumulate(&mut acc, 5, 3), Ok(()));
+    assert_eq!(acc, 45);
+    assert_eq!(
+        accumulate(&mut acc, u64::MAX, 1),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(acc, 45);
+    // The addition is written back before the multiplication fails.
+    assert_eq!(
+        accumulate(&mut acc, 5, u64::MAX),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(acc, 50);
+
+    let mut acc = 3u16;
+    assert_eq!(accumulate_generic(&mut acc, 4), Ok(()));
+    assert_eq!(acc, 28);
+    assert_eq!(
+        accumulate_generic(&mut acc, 3000),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(acc, 3028);
+
+    let mut value = 1;
+    let mut acc = &mut value;
+    assert_eq!(accumulate_nested(&mut acc, 2), Ok(()));
+    assert_eq!(
+        accumulate_nested(&mut acc, u64::MAX),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(value, 3);
+}
+
+#[test]
+fn test_compound_assignment_mut_reference_place_evaluated_once() {
+    static CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn pick(accs: &mut [u64; 2]) -> &mut u64 {
+        let index = CALL_COUNT.fetch_add(1, Ordering::SeqCst);
+        &mut accs[index % 2]
+    }
+
+    #[safe_math]
+    fn add_then_scale(accs: &mut [u64; 2], x: u64) -> Result<(), SafeMathError> {
+        *pick(accs) += x;
+        *pick(accs) *= x;
+        Ok(())
+    }
+
+    CALL_COUNT.store(0, Ordering::SeqCst);
+    let mut accs = [1, 2];
+    assert_eq!(add_then_scale(&mut accs, 3), Ok(()));
+    assert_eq!(accs, [4, 6]);
+    assert_eq!(CALL_COUNT.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_compound_assignment_rhs_error_comes_first() {
+    #[safe_math]
+    fn add_ratio(acc: &mut u8, a: u8, b: u8) -> Result<(), SafeMathError> {
+        *acc += a / b;
+        Ok(())
+    }
+
+    // Both the division and the addition would fail: the right-hand side is
+    // evaluated (and checked) first, so its error is the one reported.
+    let mut acc = u8::MAX;
+    assert_eq!(
+        add_ratio(&mut acc, 1, 0),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(add_ratio(&mut acc, 2, 1), Err(SafeMathError::Overflow));
+    assert_eq!(acc, u8::MAX);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Invoice {
+    total: u32,
+    items: u32,
+    discount: u32,
+}
+
+impl Invoice {
+    fn per_item(&self) -> u32 {
+        self.total / self.items
+    }
+
+    #[safe_math]
+    fn add_tax(mut self, rate: u32) -> Result<Self, SafeMathError> {
+        self.total = self.total * rate / 100;
+        Ok(self)
+    }
+
+    #[safe_math]
+    fn scale(mut self, factor: u32) -> Result<Self, SafeMathError> {
+        self.total *= factor;
+        self.items *= factor;
+        Ok(self)
+    }
+
+    #[safe_math]
+    fn apply_discount(mut self) -> Result<Self, SafeMathError> {
+        // Reads a sibling field on the right-hand side.
+        self.total -= self.discount;
+        Ok(self)
+    }
+
+    #[safe_math]
+    fn double(mut self) -> Result<Self, SafeMathError> {
+        // Reads the place itself and borrows `self` on the right-hand side.
+        self.total += self.total;
+        self.discount += self.per_item();
+        Ok(self)
+    }
+}
+
+#[test]
+fn test_builder_field_arithmetic() {
+    let invoice = Invoice {
+        total: 200,
+        items: 4,
+        discount: 30,
+    };
+    assert_eq!(invoice.clone().add_tax(110).map(|i| i.total), Ok(220));
+    assert_eq!(
+        invoice.clone().add_tax(u32::MAX),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        invoice.clone().scale(3).and_then(Invoice::apply_discount),
+        Ok(Invoice {
+            total: 570,
+            items: 12,
+            discount: 30,
+        })
+    );
+    assert_eq!(
+        invoice.clone().double(),
+        Ok(Invoice {
+            total: 400,
+            items: 4,
+            discount: 130,
+        })
+    );
+}
+
+#[test]
+fn test_builder_field_compound_assignment_overflow() {
+    let invoice = Invoice {
+        total: u32::MAX / 2 + 1,
+        items: 1,
+        discount: 0,
+    };
+    assert_eq!(invoice.clone().scale(2), Err(SafeMathError::Overflow));
+    assert_eq!(invoice.double(), Err(SafeMathError::Overflow));
+
+    let invoice = Invoice {
+        total: 10,
+        items: 1,
+        discount: 11,
+    };
+    assert_eq!(invoice.apply_discount(), Err(SafeMathError::Overflow));
+}
@@ -0,0 +1,102 @@
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math(debug_wrap)]
+fn add(a: u8, b: u8) -> Result<u8, SafeMathError> {
+    Ok(a + b)
+}
+
+#[safe_math(debug_wrap)]
+fn elapsed(start: u32, end: u32) -> Result<u32, SafeMathError> {
+    Ok(end - start)
+}
+
+#[test]
+fn test_debug_wrap() {
+    assert_eq!(add(1, 2), Ok(3));
+    assert_eq!(add(255, 2), Err(SafeMathError::OverflowWith { wrapped: 1 }));
+    // An underflow by one wraps to `MAX`, unlike a bogus timestamp.
+    assert_eq!(
+        elapsed(11, 10),
+        Err(SafeMathError::OverflowWith {
+            wrapped: u32::MAX as i128
+        })
+    );
+    assert_eq!(
+        elapsed(1_000_000, 10),
+        Err(SafeMathError::OverflowWith {
+            wrapped: (10u32.wrapping_sub(1_000_000)) as i128
+        })
+    );
+}
+
+#[test]
+fn test_debug_wrap_signed_and_division() {
+    #[safe_math(debug_wrap)]
+    fn ops(a: i8, b: i8) -> Result<(i8, i8, i8), SafeMathError> {
+        Ok((a * b, a / b, a % b))
+    }
+
+    assert_eq!(ops(7, 2), Ok((14, 3, 1)));
+    assert_eq!(
+        ops(100, 2),
+        Err(SafeMathError::OverflowWith { wrapped: -56 })
+    );
+    assert_eq!(
+        ops(i8::MIN, -1),
+        Err(SafeMathError::OverflowWith { wrapped: -128 })
+    );
+    // Division by zero has no wrapped result and is reported as usual.
+    assert_eq!(ops(1, 0).err(), Some(SafeMathError::DivisionByZero));
+}
+
+#[test]
+fn test_debug_wrap_compound_assignment() {
+    #[safe_math(debug_wrap)]
+    fn total(values: &[u16]) -> Result<u16, SafeMathError> {
+        let mut sum = 0;
+        for &v in values {
+            sum += v;
+        }
+        Ok(sum)
+    }
+
+    assert_eq!(total(&[1, 2, 3]), Ok(6));
+    assert_eq!(
+        total(&[u16::MAX, 5]),
+        Err(SafeMathError::OverflowWith { wrapped: 4 })
+    );
+}
+
+#[test]
+fn test_debug_wrap_u128() {
+    #[safe_math(debug_wrap)]
+    fn double(a: u128) -> Result<u128, SafeMathError> {
+        Ok(a * 2)
+    }
+
+    // Wrapped `u128` values above `i128::MAX` keep their bits.
+    let Err(SafeMathError::OverflowWith { wrapped }) = double(u128::MAX) else {
+        panic!("expected an overflow");
+    };
+    assert_eq!(wrapped as u128, u128::MAX - 1);
+}
+
+#[test]
+fn test_debug_wrap_error() {
+    let err = SafeMathError::OverflowWith { wrapped: -3 };
+    assert_eq!(err.to_string(), "arithmetic overflow (wrapped result: -3)");
+    assert_eq!(err.code(), 9);
+    assert_eq!(err.severity(), SafeMathError::Overflow.severity());
+    assert_eq!(SafeMathError::from_code(9), None);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow (wrapped result: 1)")]
+fn test_debug_wrap_panic_mode() {
+    #[safe_math(mode = panic, debug_wrap)]
+    fn add(a: u8, b: u8) -> u8 {
+        a + b
+    }
+
+    add(255, 2);
+}
@@ -0,0 +1,66 @@
+//! Everything `#[derive(SafeMathOps)]` generates must be documented, so that public
+//! types deriving it compile in crates denying `missing_docs`.
+#![cfg(feature = "derive")]
+#![deny(missing_docs)]
+
+/// Public types deriving every option of `#[SafeMathOps]` that adds inherent items.
+pub mod money {
+    use num_traits::{CheckedAdd, Zero};
+    use safe_math::{SafeMathError, SafeMathOps};
+    use std::ops::Add;
+
+    /// Error of the money domain.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum MoneyError {
+        /// An arithmetic operation failed.
+        Arithmetic(SafeMathError),
+    }
+
+    impl From<SafeMathError> for MoneyError {
+        fn from(err: SafeMathError) -> Self {
+            MoneyError::Arithmetic(err)
+        }
+    }
+
+    /// An amount of cents.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, SafeMathOps)]
+    #[SafeMathOps(add, checked_sum, scale, cmp, error = MoneyError)]
+    pub struct Cents(pub u32);
+
+    impl Add for Cents {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Cents(self.0 + rhs.0)
+        }
+    }
+
+    impl CheckedAdd for Cents {
+        fn checked_add(&self, rhs: &Self) -> Option<Self> {
+            self.0.checked_add(rhs.0).map(Cents)
+        }
+    }
+
+    impl Zero for Cents {
+        fn zero() -> Self {
+            Cents(0)
+        }
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+}
+
+#[test]
+fn test_documented_derive_items() {
+    use money::{Cents, MoneyError};
+    use safe_math::{SafeMathError, SafeScale};
+    use std::cmp::Ordering;
+
+    assert_eq!(Cents::checked_sum([Cents(1), Cents(2)]), Ok(Cents(3)));
+    assert_eq!(
+        Cents(u32::MAX).safe_add(Cents(1)),
+        Err(MoneyError::Arithmetic(SafeMathError::Overflow))
+    );
+    assert_eq!(Cents(2).safe_mul_scalar(3), Ok(Cents(6)));
+    assert_eq!(Cents(1).safe_cmp(&Cents(2)), Ordering::Less);
+}
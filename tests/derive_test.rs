@@ -0,0 +1,591 @@
+#![cfg(feature = "derive")]
+
+use num_traits::{
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub, SaturatingAdd, SaturatingMul,
+    SaturatingSub, WrappingAdd, WrappingMul, WrappingSub, Zero,
+};
+use safe_math::{safe_math, SafeMathError, SafeMathOps, SafeMathOpsRef, SafeScale};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct MetersUnit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct FeetUnit;
+
+/// Units-of-measure newtype: the marker only exists at the type level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, SafeMathOps)]
+#[SafeMathOps(add, sub, mul, div, scale, cmp)]
+struct Length<U>(i64, PhantomData<U>);
+
+impl<U> Length<U> {
+    fn new(value: i64) -> Self {
+        Length(value, PhantomData)
+    }
+}
+
+macro_rules! impl_length_op {
+    ($($op_trait:ident, $op:ident, $checked_trait:ident, $checked:ident);*) => {
+        $(
+            impl<U> $op_trait for Length<U> {
+                type Output = Self;
+                fn $op(self, rhs: Self) -> Self {
+                    Length::new(self.0.$op(rhs.0))
+                }
+            }
+
+            impl<U> $checked_trait for Length<U> {
+                fn $checked(&self, rhs: &Self) -> Option<Self> {
+                    self.0.$checked(rhs.0).map(Length::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_length_op!(
+    Add, add, CheckedAdd, checked_add;
+    Sub, sub, CheckedSub, checked_sub;
+    Mul, mul, CheckedMul, checked_mul;
+    Div, div, CheckedDiv, checked_div
+);
+
+#[safe_math]
+fn total<U: Copy + Default + PartialEq>(
+    a: Length<U>,
+    b: Length<U>,
+) -> Result<Length<U>, SafeMathError> {
+    Ok(a + b)
+}
+
+#[safe_math]
+fn scale(
+    a: Length<MetersUnit>,
+    k: Length<MetersUnit>,
+) -> Result<Length<MetersUnit>, SafeMathError> {
+    Ok(a * k / Length::new(2) - Length::new(1))
+}
+
+#[test]
+fn test_phantom_marker_arithmetic() {
+    let a: Length<MetersUnit> = Length::new(5);
+    let b = Length::new(7);
+    // The marker type is preserved through the generated impls.
+    let sum: Length<MetersUnit> = total(a, b).unwrap();
+    assert_eq!(sum, Length::new(12));
+
+    let feet: Length<FeetUnit> = total(Length::new(1), Length::new(2)).unwrap();
+    assert_eq!(feet, Length::new(3));
+
+    assert_eq!(scale(Length::new(4), Length::new(3)), Ok(Length::new(5)));
+}
+
+#[test]
+fn test_phantom_marker_errors() {
+    assert_eq!(
+        total::<MetersUnit>(Length::new(i64::MAX), Length::new(1)),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        Length::<FeetUnit>::new(1).safe_div(Length::new(0)),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(
+        Length::<FeetUnit>::new(1).safe_rem(Length::new(1)),
+        Err(SafeMathError::NotImplemented)
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SafeMathOps)]
+#[SafeMathOps(add, checked_sum)]
+struct Points(u8);
+
+impl Add for Points {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Points(self.0 + rhs.0)
+    }
+}
+
+impl CheckedAdd for Points {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Points)
+    }
+}
+
+impl Zero for Points {
+    fn zero() -> Self {
+        Points(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+#[test]
+fn test_checked_sum() {
+    let points = vec![Points(100), Points(100), Points(55)];
+    assert_eq!(Points::checked_sum(points), Ok(Points(255)));
+    assert_eq!(Points::checked_sum(Vec::new()), Ok(Points(0)));
+}
+
+#[test]
+fn test_checked_sum_overflow() {
+    let points = vec![Points(100), Points(100), Points(56)];
+    assert_eq!(Points::checked_sum(points), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_scale_by_scalar() {
+    let length: Length<MetersUnit> = Length::new(5);
+    assert_eq!(length.safe_mul_scalar(3), Ok(Length::new(15)));
+    assert_eq!(length.safe_mul_scalar(-2), Ok(Length::new(-10)));
+    assert_eq!(
+        Length::<MetersUnit>::new(i64::MAX / 2 + 1).safe_mul_scalar(2),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SafeMathOps)]
+#[SafeMathOps(scale)]
+struct Price {
+    cents: u32,
+    currency: char,
+}
+
+#[test]
+fn test_scale_named_field() {
+    let price = Price {
+        cents: 250,
+        currency: '€',
+    };
+    assert_eq!(
+        price.safe_mul_scalar(4),
+        Ok(Price {
+            cents: 1000,
+            currency: '€'
+        })
+    );
+    assert_eq!(
+        price.safe_mul_scalar(u32::MAX),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum MoneyError {
+    Arithmetic(SafeMathError),
+}
+
+impl From<SafeMathError> for MoneyError {
+    fn from(err: SafeMathError) -> Self {
+        MoneyError::Arithmetic(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SafeMathOps)]
+#[SafeMathOps(add, sub, cmp, error = MoneyError)]
+struct Cents(u32);
+
+impl Add for Cents {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Cents(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Cents {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Cents(self.0 - rhs.0)
+    }
+}
+
+impl CheckedAdd for Cents {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Cents)
+    }
+}
+
+impl CheckedSub for Cents {
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Cents)
+    }
+}
+
+#[safe_math]
+fn balance(deposit: Cents, withdrawal: Cents) -> Result<Cents, MoneyError> {
+    Ok(Cents(100) + deposit - withdrawal)
+}
+
+#[test]
+fn test_custom_error_type() {
+    // Method calls return the domain error...
+    assert_eq!(Cents(1).safe_add(Cents(2)), Ok(Cents(3)));
+    assert_eq!(
+        Cents(1).safe_sub(Cents(2)),
+        Err(MoneyError::Arithmetic(SafeMathError::Overflow))
+    );
+    // ...while the trait impls keep returning `SafeMathError`.
+    assert_eq!(
+        <Cents as SafeMathOps>::safe_sub(Cents(1), Cents(2)),
+        Err(SafeMathError::Overflow)
+    );
+
+    assert_eq!(balance(Cents(50), Cents(25)), Ok(Cents(125)));
+    assert_eq!(
+        balance(Cents(0), Cents(101)),
+        Err(MoneyError::Arithmetic(SafeMathError::Overflow))
+    );
+}
+
+#[test]
+fn test_cmp_derive() {
+    use std::cmp::Ordering;
+
+    assert_eq!(Cents(1).safe_cmp(&Cents(2)), Ordering::Less);
+    assert_eq!(Cents(2).safe_cmp(&Cents(2)), Ordering::Equal);
+    let total = Cents(100).safe_add(Cents(50)).unwrap();
+    assert_eq!(total.safe_cmp(&Cents(120)), Ordering::Greater);
+
+    // Generic, with a `PhantomData` field compared as equal.
+    let short: Length<MetersUnit> = Length::new(-3);
+    let long = Length::new(7);
+    assert_eq!(short.safe_cmp(&long), Ordering::Less);
+    assert_eq!(long.safe_cmp(&short), Ordering::Greater);
+    assert_eq!(long.safe_cmp(&Length::new(7)), Ordering::Equal);
+
+    let mut sorted = [Cents(3), Cents(1), Cents(2)];
+    sorted.sort_by(Cents::safe_cmp);
+    assert_eq!(sorted, [Cents(1), Cents(2), Cents(3)]);
+}
+
+/// Heap-backed value without `Copy`, deriving the by-reference trait.
+#[derive(Debug, Clone, PartialEq, Eq, Default, SafeMathOps)]
+#[SafeMathOps(add, sub, div, cmp, by_ref)]
+struct Digits(Vec<u8>);
+
+impl Digits {
+    fn new(value: u32) -> Self {
+        Digits(value.to_le_bytes().to_vec())
+    }
+
+    fn value(&self) -> u32 {
+        u32::from_le_bytes(self.0[..4].try_into().unwrap())
+    }
+}
+
+impl Add for Digits {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Digits::new(self.value() + rhs.value())
+    }
+}
+
+impl Sub for Digits {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Digits::new(self.value() - rhs.value())
+    }
+}
+
+impl Div for Digits {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Digits::new(self.value() / rhs.value())
+    }
+}
+
+impl CheckedAdd for Digits {
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.value().checked_add(rhs.value()).map(Digits::new)
+    }
+}
+
+impl CheckedSub for Digits {
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.value().checked_sub(rhs.value()).map(Digits::new)
+    }
+}
+
+impl CheckedDiv for Digits {
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        if rhs.0.is_empty() {
+            return None;
+        }
+        self.value().checked_div(rhs.value()).map(Digits::new)
+    }
+}
+
+#[test]
+fn test_by_ref_derive_for_non_copy_type() {
+    let a = Digits::new(10);
+    let b = Digits::new(4);
+    assert_eq!(a.safe_add_ref(&b), Ok(Digits::new(14)));
+    assert_eq!(a.safe_sub_ref(&b), Ok(Digits::new(6)));
+    assert_eq!(b.safe_sub_ref(&a), Err(SafeMathError::Overflow));
+    assert_eq!(a.safe_div_ref(&b), Ok(Digits::new(2)));
+    // `Default` is the empty vector, the zero divisor of this type.
+    assert_eq!(
+        a.safe_div_ref(&Digits::default()),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(a.safe_mul_ref(&b), Err(SafeMathError::NotImplemented));
+    // Both operands are still usable.
+    assert_eq!(a.value() + b.value(), 14);
+    assert_eq!(a.safe_cmp(&b), std::cmp::Ordering::Greater);
+}
+
+/// Signed values deriving `rem` by value, which needs no `Default`, and by reference,
+/// which tells `MIN % -1` apart from a zero divisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SafeMathOps)]
+#[SafeMathOps(rem)]
+struct Delta(i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, SafeMathOps)]
+#[SafeMathOps(rem, by_ref)]
+struct DeltaRef(i32);
+
+macro_rules! impl_delta_rem {
+    ($($t:ident),*) => {
+        $(
+            impl Rem for $t {
+                type Output = Self;
+                fn rem(self, rhs: Self) -> Self {
+                    $t(self.0 % rhs.0)
+                }
+            }
+
+            impl CheckedRem for $t {
+                fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+                    self.0.checked_rem(rhs.0).map($t)
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_rem!(Delta, DeltaRef);
+
+#[test]
+fn test_signed_rem_derive() {
+    assert_eq!(Delta(-7).safe_rem(Delta(3)), Ok(Delta(-1)));
+    assert_eq!(
+        Delta(1).safe_rem(Delta(0)),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(DeltaRef(-7).safe_rem_ref(&DeltaRef(3)), Ok(DeltaRef(-1)));
+    assert_eq!(
+        DeltaRef(i32::MIN).safe_rem_ref(&DeltaRef(-1)),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        DeltaRef(1).safe_rem_ref(&DeltaRef(0)),
+        Err(SafeMathError::DivisionByZero)
+    );
+}
+
+/// Type whose checked operations don't follow the `num_traits` naming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, SafeMathOps)]
+#[SafeMathOps(add = checked_addition, mul = Scaled::checked_times, div)]
+struct Tokens(u16);
+
+trait Scaled: Sized {
+    fn checked_times(self, rhs: Self) -> Option<Self>;
+}
+
+impl Tokens {
+    fn checked_addition(&self, rhs: Tokens) -> Option<Tokens> {
+        self.0.checked_add(rhs.0).map(Tokens)
+    }
+}
+
+impl Scaled for Tokens {
+    fn checked_times(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).map(Tokens)
+    }
+}
+
+impl Add for Tokens {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Tokens(self.0 + rhs.0)
+    }
+}
+
+impl Mul for Tokens {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Tokens(self.0 * rhs.0)
+    }
+}
+
+impl Div for Tokens {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Tokens(self.0 / rhs.0)
+    }
+}
+
+impl CheckedDiv for Tokens {
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        self.0.checked_div(rhs.0).map(Tokens)
+    }
+}
+
+#[safe_math]
+fn tokens_per_worker(
+    base: Tokens,
+    bonus: Tokens,
+    rate: Tokens,
+    workers: Tokens,
+) -> Result<Tokens, SafeMathError> {
+    Ok((base + bonus) * rate / workers)
+}
+
+#[test]
+fn test_checked_method_override() {
+    assert_eq!(Tokens(1).safe_add(Tokens(2)), Ok(Tokens(3)));
+    assert_eq!(
+        Tokens(300).safe_mul(Tokens(300)),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        tokens_per_worker(Tokens(10), Tokens(2), Tokens(3), Tokens(4)),
+        Ok(Tokens(9))
+    );
+    assert_eq!(
+        tokens_per_worker(Tokens(u16::MAX), Tokens(1), Tokens(1), Tokens(1)),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        tokens_per_worker(Tokens(1), Tokens(1), Tokens(1), Tokens(0)),
+        Err(SafeMathError::DivisionByZero)
+    );
+}
+
+/// Float newtype: no `checked_*` methods, the results are checked for finiteness.
+#[derive(Debug, Clone, Copy, PartialEq, SafeMathOps)]
+#[SafeMathOps(add, sub, mul, div, rem, float)]
+struct Rate(f64);
+
+macro_rules! impl_rate_op {
+    ($($op_trait:ident, $op:ident);*) => {
+        $(
+            impl $op_trait for Rate {
+                type Output = Self;
+                fn $op(self, rhs: Self) -> Self {
+                    Rate(self.0.$op(rhs.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_rate_op!(Add, add; Sub, sub; Mul, mul; Div, div; Rem, rem);
+
+#[safe_math]
+fn compound(principal: Rate, rate: Rate, periods: Rate) -> Result<Rate, SafeMathError> {
+    Ok(principal * (Rate(1.0) + rate / periods))
+}
+
+#[test]
+fn test_float_derive() {
+    assert_eq!(Rate(1.5).safe_add(Rate(2.0)), Ok(Rate(3.5)));
+    assert_eq!(Rate(1.5).safe_sub(Rate(2.0)), Ok(Rate(-0.5)));
+    assert_eq!(Rate(7.0).safe_rem(Rate(4.0)), Ok(Rate(3.0)));
+    assert_eq!(compound(Rate(100.0), Rate(0.5), Rate(2.0)), Ok(Rate(125.0)));
+
+    assert_eq!(
+        Rate(f64::MAX).safe_mul(Rate(2.0)),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(
+        Rate(f64::MAX).safe_add(Rate(f64::MAX)),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(
+        Rate(0.0).safe_div(Rate(0.0)),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(
+        compound(Rate(100.0), Rate(0.5), Rate(0.0)),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(
+        Rate(f64::NAN).safe_sub(Rate(1.0)),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(
+        Rate(1.0).safe_rem(Rate(0.0)),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+}
+
+/// Counter newtype that can also saturate or wrap, e.g. inside `wrapping!(..)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SafeMathOps)]
+#[SafeMathOps(add, sub, mul, saturating, wrapping)]
+struct Ticks<U>(u8, PhantomData<U>);
+
+impl<U> Ticks<U> {
+    fn new(value: u8) -> Self {
+        Ticks(value, PhantomData)
+    }
+}
+
+macro_rules! impl_ticks_op {
+    ($($op_trait:ident, $op:ident, $checked_trait:ident, $checked:ident);*) => {
+        $(
+            impl<U> $op_trait for Ticks<U> {
+                type Output = Self;
+                fn $op(self, rhs: Self) -> Self {
+                    Ticks::new(self.0.$op(rhs.0))
+                }
+            }
+
+            impl<U> $checked_trait for Ticks<U> {
+                fn $checked(&self, rhs: &Self) -> Option<Self> {
+                    self.0.$checked(rhs.0).map(Ticks::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_ticks_op!(
+    Add, add, CheckedAdd, checked_add;
+    Sub, sub, CheckedSub, checked_sub;
+    Mul, mul, CheckedMul, checked_mul
+);
+
+#[safe_math]
+fn elapsed(
+    start: Ticks<MetersUnit>,
+    end: Ticks<MetersUnit>,
+    laps: Ticks<MetersUnit>,
+) -> Result<Ticks<MetersUnit>, SafeMathError> {
+    Ok(wrapping!(end - start) + saturating!(laps * laps))
+}
+
+#[test]
+fn test_saturating_and_wrapping_derive() {
+    let (a, b) = (Ticks::<MetersUnit>::new(250), Ticks::new(10));
+    assert_eq!(a.wrapping_add(&b), Ticks::new(4));
+    assert_eq!(b.wrapping_sub(&a), Ticks::new(16));
+    assert_eq!(a.wrapping_mul(&b), Ticks::new(196));
+    assert_eq!(a.saturating_add(&b), Ticks::new(255));
+    assert_eq!(b.saturating_sub(&a), Ticks::new(0));
+    assert_eq!(a.saturating_mul(&b), Ticks::new(255));
+
+    assert_eq!(
+        elapsed(Ticks::new(250), Ticks::new(4), Ticks::new(3)),
+        Ok(Ticks::new(19))
+    );
+    assert_eq!(
+        elapsed(Ticks::new(0), Ticks::new(1), Ticks::new(16)),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(a.safe_add(b), Err(SafeMathError::Overflow));
+}
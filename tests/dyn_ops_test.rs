@@ -0,0 +1,67 @@
+use safe_math::{DynSafeOps, SafeMathError};
+
+type DynResult = Result<Box<dyn DynSafeOps>, SafeMathError>;
+type DynOp = fn(&dyn DynSafeOps, &dyn DynSafeOps) -> DynResult;
+
+#[test]
+fn test_dyn_dispatch() {
+    let values: Vec<Box<dyn DynSafeOps>> = vec![Box::new(200u8), Box::new(-5i32), Box::new(1.5f64)];
+    let doubled: Vec<DynResult> = values
+        .iter()
+        .map(|value| value.safe_add_dyn(&**value))
+        .collect();
+
+    assert_eq!(doubled[0].as_ref().err(), Some(&SafeMathError::Overflow));
+    let doubled: Vec<_> = doubled[1..].iter().map(|r| r.as_ref().unwrap()).collect();
+    assert_eq!(doubled[0].downcast_ref::<i32>(), Some(&-10));
+    assert_eq!(doubled[1].downcast_ref::<f64>(), Some(&3.0));
+    assert_eq!(doubled[1].downcast_ref::<f32>(), None);
+}
+
+#[test]
+fn test_dyn_operations() {
+    let a: Box<dyn DynSafeOps> = Box::new(7u32);
+    let b: Box<dyn DynSafeOps> = Box::new(2u32);
+    let zero: Box<dyn DynSafeOps> = Box::new(0u32);
+    let ops: [(DynOp, u32); 5] = [
+        (|a, b| a.safe_add_dyn(b), 9),
+        (|a, b| a.safe_sub_dyn(b), 5),
+        (|a, b| a.safe_mul_dyn(b), 14),
+        (|a, b| a.safe_div_dyn(b), 3),
+        (|a, b| a.safe_rem_dyn(b), 1),
+    ];
+    for (op, expected) in ops {
+        let result = op(&*a, &*b).unwrap();
+        assert_eq!(result.downcast_ref::<u32>(), Some(&expected));
+    }
+    assert_eq!(b.safe_sub_dyn(&*a).err(), Some(SafeMathError::Overflow));
+    assert_eq!(
+        a.safe_div_dyn(&*zero).err(),
+        Some(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(
+        a.safe_rem_dyn(&*zero).err(),
+        Some(SafeMathError::DivisionByZero)
+    );
+}
+
+#[test]
+fn test_dyn_type_mismatch() {
+    let a: Box<dyn DynSafeOps> = Box::new(7u32);
+    let b: Box<dyn DynSafeOps> = Box::new(2u64);
+    assert_eq!(a.safe_add_dyn(&*b).err(), Some(SafeMathError::TypeMismatch));
+    assert_eq!(b.safe_mul_dyn(&*a).err(), Some(SafeMathError::TypeMismatch));
+    assert_eq!(
+        SafeMathError::TypeMismatch.to_string(),
+        "operands of different types"
+    );
+}
+
+#[test]
+fn test_dyn_clone() {
+    let price: Box<dyn DynSafeOps> = Box::new(250u32);
+    let copy = price.clone();
+    let total = price.safe_add_dyn(&*copy).unwrap();
+    assert_eq!(total.downcast_ref::<u32>(), Some(&500));
+    assert_eq!(copy.downcast_ref::<u32>(), Some(&250));
+}
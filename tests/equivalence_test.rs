@@ -0,0 +1,41 @@
+use safe_math::{assert_safe_equivalent, safe_div, safe_math, safe_sub, SafeMathError};
+
+#[safe_math]
+fn average_gap(high: u32, low: u32, count: u32) -> Result<u32, SafeMathError> {
+    Ok((high - low) / count)
+}
+
+fn average_gap_checked(high: u32, low: u32, count: u32) -> Result<u32, SafeMathError> {
+    safe_div(safe_sub(high, low)?, count)
+}
+
+#[safe_math]
+fn halve(value: i64) -> Option<i64> {
+    Some(value / 2)
+}
+
+#[test]
+fn test_equivalent_functions() {
+    assert_safe_equivalent!(
+        average_gap,
+        average_gap_checked,
+        (10, 4, 2),
+        (4, 10, 2),
+        (10, 4, 0),
+        (u32::MAX, 0, 1),
+    );
+    assert_safe_equivalent!(halve, |value: i64| value.checked_div(2), (7), (i64::MIN));
+    // No inputs is allowed, and checks nothing.
+    assert_safe_equivalent!(halve, |value: i64| Some(value));
+}
+
+#[test]
+#[should_panic(expected = "results differ for inputs (4, 10, 2)")]
+fn test_different_functions() {
+    assert_safe_equivalent!(
+        average_gap,
+        |high: u32, low: u32, count: u32| Ok(high.wrapping_sub(low) / count),
+        (10, 4, 2),
+        (4, 10, 2),
+    );
+}
@@ -0,0 +1,34 @@
+use safe_math::{safe_math, safe_math_block};
+
+#[safe_math(mode = panic)]
+extern "C" fn ffi_scale(value: u32, factor: u32) -> u32 {
+    value * factor
+}
+
+#[safe_math(mode = panic)]
+extern "C-unwind" fn ffi_scale_unwind(value: u32, factor: u32) -> u32 {
+    value * factor
+}
+
+extern "C" fn ffi_clamped_scale(value: u32, factor: u32, offset: u32) -> u32 {
+    safe_math_block!(saturating!(value * factor + offset))
+}
+
+#[test]
+fn test_extern_fn_panic_mode() {
+    assert_eq!(ffi_scale(6, 7), 42);
+    assert_eq!(ffi_scale_unwind(6, 7), 42);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow in `value * factor`")]
+fn test_extern_fn_panic_mode_overflow() {
+    // Only an "-unwind" ABI lets the panic reach the caller, "C" aborts instead.
+    ffi_scale_unwind(u32::MAX, 2);
+}
+
+#[test]
+fn test_extern_fn_saturating_block() {
+    assert_eq!(ffi_clamped_scale(6, 7, 1), 43);
+    assert_eq!(ffi_clamped_scale(u32::MAX, 2, 1), u32::MAX);
+}
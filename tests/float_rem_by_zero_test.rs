@@ -0,0 +1,36 @@
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math(float_rem_by_zero)]
+fn phase(angle: f64, period: f64) -> Result<f64, SafeMathError> {
+    Ok(angle % period)
+}
+
+#[safe_math(float_rem_by_zero)]
+fn wrap_all(angles: &mut [f32], period: f32) -> Result<(), SafeMathError> {
+    for angle in angles {
+        *angle %= period;
+    }
+    Ok(())
+}
+
+#[safe_math]
+fn phase_default(angle: f64, period: f64) -> Result<f64, SafeMathError> {
+    Ok(angle % period)
+}
+
+#[test]
+fn test_float_rem_by_zero() {
+    assert_eq!(phase(5.0, 3.0), Ok(2.0));
+    assert_eq!(phase(5.0, 0.0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(phase(f64::NAN, 3.0), Err(SafeMathError::InfiniteOrNaN));
+    // Without the flag, `5.0 % 0.0` is a NaN like any other.
+    assert_eq!(phase_default(5.0, 0.0), Err(SafeMathError::InfiniteOrNaN));
+
+    let mut angles = [370.0, -10.0];
+    assert_eq!(wrap_all(&mut angles, 360.0), Ok(()));
+    assert_eq!(angles, [10.0, -10.0]);
+    assert_eq!(
+        wrap_all(&mut angles, 0.0),
+        Err(SafeMathError::DivisionByZero)
+    );
+}
@@ -22,7 +22,8 @@ fn main() {
 
     // Print a build message showing how many test cases were generated
     println!(
-        "cargo:warning=Generated {} test cases",
-        test_generator::NUM_TEST_CASES
+        "cargo:warning=Generated {} checked and {} wrapping test cases per type",
+        test_generator::NUM_TEST_CASES,
+        test_generator::NUM_WRAPPING_TEST_CASES
     );
 }
@@ -1,11 +1,15 @@
 /// This module contains the logic for generating random arithmetic expression tests.
 /// It creates pairs of equivalent expressions using both regular operators and
-/// their checked counterparts to verify the safe_math macro's behavior.
+/// their checked (or, inside a `wrapping!(..)` marker, wrapping) counterparts to
+/// verify the safe_math macro's behavior.
 use rand::Rng;
 
 /// Number of test cases to generate per type
 pub const NUM_TEST_CASES: usize = 50;
 
+/// Number of wrapping-policy test cases to generate per type
+pub const NUM_WRAPPING_TEST_CASES: usize = 10;
+
 /// Basic arithmetic operators that will be used in the unsafe expressions
 const OPERATORS: [&str; 5] = ["+", "-", "*", "/", "%"];
 
@@ -18,6 +22,25 @@ const CHECKED_OPERATORS: [&str; 5] = [
     "checked_rem",
 ];
 
+/// Corresponding wrapping methods, used inside `wrapping!(..)`. Division and
+/// remainder stay checked within the marker, so they have none.
+const WRAPPING_OPERATORS: [Option<&str>; 5] = [
+    Some("wrapping_add"),
+    Some("wrapping_sub"),
+    Some("wrapping_mul"),
+    None,
+    None,
+];
+
+/// Overflow policy of the generated expressions
+#[derive(Clone, Copy, PartialEq)]
+enum Policy {
+    /// Plain `#[safe_math]`: every operation is checked
+    Checked,
+    /// The whole expression is wrapped in `wrapping!(..)`
+    Wrapping,
+}
+
 /// All numeric types that will be tested
 const NUMERIC_TYPES: [&str; 12] = [
     // Unsigned integers
@@ -33,30 +56,49 @@ const NUMERIC_TYPES: [&str; 12] = [
 struct ExpressionBuilder {
     /// The unsafe expression using regular operators (e.g., "a + b")
     expr: String,
-    /// The safe expression using checked methods (e.g., "a.checked_add(b)")
+    /// The safe expression using checked methods (e.g., "a.checked_add(b)"), or
+    /// wrapping methods where the policy allows it
     expr_safe: String,
+    /// Policy that determines the methods used in `expr_safe`
+    policy: Policy,
 }
 
 impl ExpressionBuilder {
     /// Creates a new ExpressionBuilder starting with an initial value
-    fn new(initial: &str) -> Self {
+    fn new(initial: &str, policy: Policy) -> Self {
         Self {
             expr: initial.to_string(),
             expr_safe: initial.to_string(),
+            policy,
         }
     }
 
     /// Adds a new operation to both expressions
     ///
     /// # Arguments
-    /// * `op` - The regular operator (e.g., "+")
-    /// * `checked_op` - The corresponding checked method (e.g., "checked_add")
+    /// * `op_idx` - Index of the operator in `OPERATORS`
     /// * `arg` - The argument to use in the operation
-    fn add_operation(&mut self, op: &str, checked_op: &str, arg: &str) {
+    fn add_operation(&mut self, op_idx: usize, arg: &str) {
+        let op = OPERATORS[op_idx];
         // For unsafe expression, wrap in parentheses to maintain operator precedence
         self.expr = format!("({} {} {})", self.expr, op, arg);
-        // For safe expression, chain the checked method call and convert Option to Result
-        self.expr_safe = format!("{}.{}({}).ok_or(())?", self.expr_safe, checked_op, arg);
+        if self.policy == Policy::Wrapping {
+            if let Some(wrapping_op) = WRAPPING_OPERATORS[op_idx] {
+                // Wrapping methods never fail
+                self.expr_safe = format!("{}.{}({})", self.expr_safe, wrapping_op, arg);
+                return;
+            }
+        }
+        let checked_op = CHECKED_OPERATORS[op_idx];
+        // For safe expression, chain the checked method call and convert Option to Result.
+        // `checked_div`/`checked_rem` also fail on `MIN / -1`, which is an overflow.
+        let error = match op {
+            "/" | "%" => format!(
+                "if {arg} == 0 {{ SafeMathError::DivisionByZero }} else {{ SafeMathError::Overflow }}"
+            ),
+            _ => "SafeMathError::Overflow".to_string(),
+        };
+        self.expr_safe = format!("{}.{}({}).ok_or({})?", self.expr_safe, checked_op, arg, error);
     }
 }
 
@@ -65,21 +107,28 @@ impl ExpressionBuilder {
 /// # Arguments
 /// * `test_number` - The index of this test case, used to generate unique function names
 /// * `numeric_type` - The type of the numeric arguments
-fn generate_single_test(test_number: usize, numeric_type: &str) -> String {
+/// * `policy` - The policy of the generated expression
+fn generate_single_test(test_number: usize, numeric_type: &str, policy: Policy) -> String {
     let mut rng = rand::rng();
 
     // Generate between 2 and 10 arguments
     let num_args = rng.random_range(2..=10);
     let arg_names: Vec<String> = (1..=num_args).map(|i| format!("a{i}")).collect();
 
-    let mut builder = ExpressionBuilder::new(&arg_names[0]);
+    let mut builder = ExpressionBuilder::new(&arg_names[0], policy);
 
     // Add random operations with the remaining arguments
     for arg in arg_names.iter().skip(1) {
         let op_idx = rng.random_range(0..OPERATORS.len());
-        builder.add_operation(OPERATORS[op_idx], CHECKED_OPERATORS[op_idx], arg);
+        builder.add_operation(op_idx, arg);
     }
 
+    // The expression as written in the macro versions, and the name of the test
+    let (macro_expr, test_kind) = match policy {
+        Policy::Checked => (builder.expr.clone(), ""),
+        Policy::Wrapping => (format!("wrapping!({})", builder.expr), "wrapping_"),
+    };
+
     // Generate appropriate random value based on type
     let random_gen = match numeric_type {
         "u8" => "rng.random::<u8>()",
@@ -94,32 +143,40 @@ fn generate_single_test(test_number: usize, numeric_type: &str) -> String {
         "i128" => "rng.random::<i128>()",
         "f32" => "rng.random::<f32>()",
         "f64" => "rng.random::<f64>()",
-        "usize" => "rng.random::<u64>() as usize",
-        "isize" => "rng.random::<i64>() as isize",
+        // Evaluated in the generated test, so this follows the target's pointer width.
+        "usize" => {
+            "(if cfg!(target_pointer_width = \"64\") { rng.random::<u64>() as usize } \
+             else { rng.random::<u32>() as usize })"
+        }
+        "isize" => {
+            "(if cfg!(target_pointer_width = \"64\") { rng.random::<i64>() as isize } \
+             else { rng.random::<i32>() as isize })"
+        }
         _ => unreachable!(),
     };
 
     format!(
         r#"
 #[test]
-fn test_generated_{}_{}_equivalence() {{
+#[allow(clippy::too_many_arguments)]
+fn test_generated_{}{}_{}_equivalence() {{
     // Define the two equivalent functions:
     // 1. Using the safe_math macro
     #[safe_math]
-    fn with_macro({}) -> Result<{}, ()> {{
+    fn with_macro({}) -> Result<{}, SafeMathError> {{
         #[allow(unused_parens)]
         let result = {};
         Ok(result)
     }}
 
-    // 2. Using checked operations directly
-    fn with_checked({}) -> Result<{}, ()> {{
+    // 2. Using checked (or wrapping) operations directly
+    fn with_checked({}) -> Result<{}, SafeMathError> {{
         let result = {};
         Ok(result)
     }}
 
     // 3. Using the safe_math_block macro
-    fn with_function_macro({}) -> Result<{}, ()> {{
+    fn with_function_macro({}) -> Result<{}, SafeMathError> {{
         #[allow(unused_parens)]
         let result = {{
             safe_math_block! {{
@@ -140,15 +197,20 @@ fn test_generated_{}_{}_equivalence() {{
         let checked_result = with_checked({});
         let function_macro_result = with_function_macro({});
 
-        // Verify that both functions produce exactly the same result
-        assert!(
-            macro_result == checked_result &&
-            macro_result == function_macro_result,
-            "safe_math macro and checked operations produced different results for inputs: {{inputs:?}}"
+        // Verify that all functions produce exactly the same result, including the
+        // error variant
+        assert_eq!(
+            macro_result, checked_result,
+            "safe_math macro and hand-written operations produced different results for inputs: {{inputs:?}}"
+        );
+        assert_eq!(
+            macro_result, function_macro_result,
+            "safe_math macro and safe_math_block produced different results for inputs: {{inputs:?}}"
         );
     }}
 }}
 "#,
+        test_kind,
         numeric_type.replace(".", "_"), // Sostituisce il punto con underscore per f32/f64
         test_number,
         // Function arguments for with_macro
@@ -159,7 +221,7 @@ fn test_generated_{}_{}_equivalence() {{
             .join(", "),
         numeric_type,
         // Expression for with_macro
-        builder.expr,
+        macro_expr,
         // Function arguments for with_checked
         arg_names
             .iter()
@@ -177,7 +239,7 @@ fn test_generated_{}_{}_equivalence() {{
             .join(", "),
         numeric_type,
         // Expression for with_function_macro
-        builder.expr,
+        macro_expr,
         // Random input generation
         arg_names
             .iter()
@@ -207,20 +269,24 @@ pub fn generate_test_cases() -> String {
     let mut test_file = String::from(
         r#"
 #[cfg(test)]
-use safe_math::{safe_math, safe_math_block};
+use safe_math::{safe_math, safe_math_block, SafeMathError};
 #[cfg(test)]
 use rand::Rng;
 
 // This file is auto-generated. Do not edit manually.
 // Each test verifies that the safe_math macro produces identical results
-// to using checked arithmetic operations directly.
+// to using checked (or, for the wrapping tests, wrapping) arithmetic operations
+// directly.
 "#,
     );
 
     // Generate test cases for each numeric type
     for type_name in NUMERIC_TYPES.iter() {
         for i in 0..NUM_TEST_CASES {
-            test_file.push_str(&generate_single_test(i, type_name));
+            test_file.push_str(&generate_single_test(i, type_name, Policy::Checked));
+        }
+        for i in 0..NUM_WRAPPING_TEST_CASES {
+            test_file.push_str(&generate_single_test(i, type_name, Policy::Wrapping));
         }
     }
 
@@ -0,0 +1,183 @@
+use safe_math::{safe_math, SafeAdd, SafeDiv, SafeMathError, SafeMul};
+
+// The integer and float impls are selected by the operand type, so a single
+// `SafeAdd` bound covers both.
+#[safe_math]
+fn sum<T: SafeAdd>(a: T, b: T) -> Result<T, SafeMathError> {
+    Ok(a + b)
+}
+
+#[safe_math]
+fn mul_add<T: SafeAdd + SafeMul>(a: T, b: T, c: T) -> Result<T, SafeMathError> {
+    Ok(a * b + c)
+}
+
+#[safe_math]
+fn mean<T: SafeAdd + SafeDiv>(a: T, b: T, two: T) -> Result<T, SafeMathError> {
+    Ok((a + b) / two)
+}
+
+#[test]
+fn test_generic_fn_at_integer_type() {
+    assert_eq!(sum(1u32, 2), Ok(3));
+    assert_eq!(sum(u32::MAX, 1), Err(SafeMathError::Overflow));
+    assert_eq!(mul_add(3u32, 4, 5), Ok(17));
+    assert_eq!(mean(3u32, 5, 2), Ok(4));
+    assert_eq!(mean(3u32, 5, 0), Err(SafeMathError::DivisionByZero));
+}
+
+#[test]
+fn test_generic_fn_at_float_type() {
+    assert_eq!(sum(1.5f64, 2.0), Ok(3.5));
+    assert_eq!(sum(f64::MAX, f64::MAX), Err(SafeMathError::InfiniteOrNaN));
+    assert_eq!(mul_add(3.0f64, 4.0, 0.5), Ok(12.5));
+    assert_eq!(mean(3.0f64, 6.0, 2.0), Ok(4.5));
+    assert_eq!(mean(3.0f64, 5.0, 0.0), Err(SafeMathError::InfiniteOrNaN));
+}
+
+#[test]
+fn test_unsuffixed_literals_are_inferred() {
+    // The literal takes the type of the other operand, even when it is only known
+    // through a trait bound or a later use.
+    #[safe_math]
+    fn next<I: Iterator<Item = u8>>(mut it: I) -> Result<u8, SafeMathError> {
+        Ok(it.next().unwrap_or_default() + 1)
+    }
+
+    #[safe_math]
+    fn widened<T: Into<u32>>(t: T) -> Result<u32, SafeMathError> {
+        Ok(t.into() * 2 + 1)
+    }
+
+    #[safe_math]
+    fn counted() -> Result<u8, SafeMathError> {
+        let mut n = 0;
+        n += 1;
+        let total = 1 + n;
+        let typed: u8 = total;
+        Ok(typed)
+    }
+
+    #[safe_math]
+    fn generic_one<T: SafeAdd + From<u8>>(a: T) -> Result<T, SafeMathError> {
+        Ok(a + T::from(1))
+    }
+
+    assert_eq!(next([254u8].into_iter()), Ok(255));
+    assert_eq!(next([255u8].into_iter()), Err(SafeMathError::Overflow));
+    assert_eq!(widened(7u8), Ok(15));
+    assert_eq!(widened(u32::MAX), Err(SafeMathError::Overflow));
+    assert_eq!(counted(), Ok(2));
+    assert_eq!(generic_one(41u64), Ok(42));
+    assert_eq!(generic_one(1.5f32), Ok(2.5));
+}
+
+#[test]
+fn test_borrowed_operands_are_dereferenced() {
+    // Both operands of a rewritten operator must have the same type, so borrowed
+    // values (e.g. from `iter()`) are dereferenced explicitly.
+    #[safe_math]
+    fn doubled_sum(values: &[u64]) -> Result<u64, SafeMathError> {
+        let mut total = 0;
+        for x in values.iter() {
+            total += *x * 2;
+        }
+        Ok(total)
+    }
+
+    assert_eq!(doubled_sum(&[1, 2, 3]), Ok(12));
+    assert_eq!(doubled_sum(&[u64::MAX]), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_smart_pointer_operands_are_dereferenced() {
+    use std::rc::Rc;
+
+    // Smart pointers aren't `Copy`, so they can't implement the `Safe*` traits
+    // themselves: their contents are operated on instead.
+    struct Node {
+        value: Box<u32>,
+    }
+
+    #[safe_math]
+    fn boxed_sum(a: &Node, b: &Node) -> Result<Node, SafeMathError> {
+        Ok(Node {
+            value: Box::new(*a.value + *b.value),
+        })
+    }
+
+    #[safe_math]
+    fn shared_product<T: SafeMul + Copy>(a: &Rc<T>, b: &Rc<T>) -> Result<T, SafeMathError> {
+        Ok(**a * **b)
+    }
+
+    let node = |value| Node {
+        value: Box::new(value),
+    };
+    assert_eq!(boxed_sum(&node(1), &node(2)).map(|n| *n.value), Ok(3));
+    assert!(boxed_sum(&node(u32::MAX), &node(1)).is_err());
+    assert_eq!(shared_product(&Rc::new(6u8), &Rc::new(7)), Ok(42));
+    assert_eq!(
+        shared_product(&Rc::new(16u8), &Rc::new(16)),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[safe_math]
+fn scaled_first<'a, T>(values: &'a [T], factor: &'a T) -> Result<T, SafeMathError>
+where
+    T: SafeMul + Copy,
+{
+    Ok(values[0] * *factor)
+}
+
+#[safe_math]
+fn weighted<'a, 'b: 'a, T, W, const N: usize>(
+    values: &'a [T; N],
+    weight: &'b W,
+) -> Result<(T, usize), SafeMathError>
+where
+    T: SafeAdd + SafeMul + From<u8>,
+    W: Fn(usize) -> T + ?Sized,
+{
+    let mut total = T::from(0);
+    for (i, v) in values.iter().enumerate() {
+        total += *v * weight(i);
+    }
+    Ok((total, N))
+}
+
+struct Ledger<'a, T> {
+    entries: &'a [T],
+}
+
+impl<'a, T: SafeAdd> Ledger<'a, T> {
+    #[safe_math]
+    fn total_with<'b>(&'b self, extra: T) -> Result<T, SafeMathError>
+    where
+        'a: 'b,
+    {
+        let mut total = extra;
+        for entry in self.entries {
+            total += *entry;
+        }
+        Ok(total)
+    }
+}
+
+#[test]
+fn test_lifetimes_and_where_clauses_survive() {
+    assert_eq!(scaled_first(&[7u8, 1], &3), Ok(21));
+    assert_eq!(scaled_first(&[128u8], &2), Err(SafeMathError::Overflow));
+
+    let weight = |i: usize| i as u32 + 1;
+    assert_eq!(weighted(&[10u32, 20, 30], &weight), Ok((140, 3)));
+    let weight = |_: usize| 128u8;
+    assert_eq!(weighted(&[1u8, 1], &weight), Err(SafeMathError::Overflow));
+
+    let ledger = Ledger {
+        entries: &[100u8, 100],
+    };
+    assert_eq!(ledger.total_with(50), Ok(250));
+    assert_eq!(ledger.total_with(56), Err(SafeMathError::Overflow));
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "hooks")]
+
+use safe_math::{
+    safe_add, safe_cast, safe_div, safe_math, safe_mul_add, safe_parse, safe_rem_nonzero,
+    set_overflow_hook, SafeMathError,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static OVERFLOWS: AtomicUsize = AtomicUsize::new(0);
+static DIVISIONS_BY_ZERO: AtomicUsize = AtomicUsize::new(0);
+// The hook is global, so tests observing the counters must not run concurrently.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn install_hook() {
+    let _ = set_overflow_hook(Box::new(|err| match err {
+        SafeMathError::Overflow => {
+            OVERFLOWS.fetch_add(1, Ordering::SeqCst);
+        }
+        SafeMathError::DivisionByZero => {
+            DIVISIONS_BY_ZERO.fetch_add(1, Ordering::SeqCst);
+        }
+        _ => {}
+    }));
+}
+
+#[test]
+fn test_hook_fires_on_error() {
+    let _guard = LOCK.lock().unwrap();
+    install_hook();
+    let overflows = OVERFLOWS.load(Ordering::SeqCst);
+    let divisions = DIVISIONS_BY_ZERO.load(Ordering::SeqCst);
+
+    assert_eq!(safe_add(255u8, 1), Err(SafeMathError::Overflow));
+    assert_eq!(safe_div(1u8, 0), Err(SafeMathError::DivisionByZero));
+
+    assert_eq!(OVERFLOWS.load(Ordering::SeqCst), overflows + 1);
+    assert_eq!(DIVISIONS_BY_ZERO.load(Ordering::SeqCst), divisions + 1);
+}
+
+#[test]
+fn test_hook_not_called_on_success() {
+    let _guard = LOCK.lock().unwrap();
+    install_hook();
+    let overflows = OVERFLOWS.load(Ordering::SeqCst);
+
+    assert_eq!(safe_add(1u8, 1), Ok(2));
+
+    assert_eq!(OVERFLOWS.load(Ordering::SeqCst), overflows);
+}
+
+#[test]
+fn test_hook_fires_from_macro() {
+    #[safe_math]
+    fn mul(a: u16, b: u16) -> Result<u16, SafeMathError> {
+        Ok(a * b)
+    }
+
+    let _guard = LOCK.lock().unwrap();
+    install_hook();
+    let overflows = OVERFLOWS.load(Ordering::SeqCst);
+
+    assert_eq!(mul(1000, 1000), Err(SafeMathError::Overflow));
+
+    assert_eq!(OVERFLOWS.load(Ordering::SeqCst), overflows + 1);
+}
+
+#[test]
+fn test_hook_fires_for_float_rem_by_zero() {
+    #[safe_math(float_rem_by_zero)]
+    fn rem(a: f64, b: f64) -> Result<f64, SafeMathError> {
+        Ok(a % b)
+    }
+
+    let _guard = LOCK.lock().unwrap();
+    install_hook();
+    let divisions = DIVISIONS_BY_ZERO.load(Ordering::SeqCst);
+
+    assert_eq!(rem(5.0, 0.0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(safe_rem_nonzero(5u8, 0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(rem(5.0, 3.0), Ok(2.0));
+
+    assert_eq!(DIVISIONS_BY_ZERO.load(Ordering::SeqCst), divisions + 2);
+}
+
+#[test]
+fn test_hook_fires_for_other_helpers() {
+    #[safe_math(checked_narrowing)]
+    fn narrow(a: u32) -> Result<u8, SafeMathError> {
+        let b: u8 = a;
+        Ok(b)
+    }
+
+    let _guard = LOCK.lock().unwrap();
+    install_hook();
+    let overflows = OVERFLOWS.load(Ordering::SeqCst);
+
+    assert_eq!(safe_cast::<u32, u8>(300), Err(SafeMathError::Overflow));
+    assert_eq!(safe_mul_add(16u8, 16, 1), Err(SafeMathError::Overflow));
+    assert_eq!(safe_parse::<u8>("256", 10), Err(SafeMathError::Overflow));
+    assert_eq!(narrow(300), Err(SafeMathError::Overflow));
+
+    assert_eq!(OVERFLOWS.load(Ordering::SeqCst), overflows + 4);
+}
+
+#[test]
+fn test_hook_can_only_be_set_once() {
+    install_hook();
+    assert!(set_overflow_hook(Box::new(|_| {})).is_err());
+}
@@ -0,0 +1,48 @@
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math]
+fn identities(x: u8) -> Result<(u8, u8, u8, u8), SafeMathError> {
+    Ok((x + 0, 0 + x, x * 1, 1u8 * x))
+}
+
+#[safe_math]
+fn constants() -> Result<(u32, i8, u64), SafeMathError> {
+    Ok((60u32 * 60, 2i8 - 7, 1000u64 / 3 % 7))
+}
+
+#[test]
+fn test_identities_keep_value_and_type() {
+    assert_eq!(identities(0), Ok((0, 0, 0, 0)));
+    assert_eq!(identities(u8::MAX), Ok((255, 255, 255, 255)));
+    let (sum, ..) = identities(7).unwrap();
+    let _: u8 = sum;
+}
+
+#[test]
+fn test_identity_is_a_value() {
+    #[safe_math]
+    fn bump(x: i64) -> Result<i64, SafeMathError> {
+        // Assigns to a temporary, not to `x`.
+        let copy = &mut (x + 0);
+        *copy += 1;
+        Ok(x)
+    }
+
+    assert_eq!(bump(41), Ok(41));
+}
+
+#[test]
+fn test_identities_around_checked_operations() {
+    #[safe_math]
+    fn scaled(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        Ok((a + b) * 1 + 0)
+    }
+
+    assert_eq!(scaled(100, 50), Ok(150));
+    assert_eq!(scaled(200, 56), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_folded_constants() {
+    assert_eq!(constants(), Ok((3600, -5, 4)));
+}
@@ -0,0 +1,58 @@
+use safe_math::{SafeIteratorExt, SafeMathError};
+
+#[derive(Debug, PartialEq)]
+enum StreamError {
+    BadRecord(usize),
+    Math(SafeMathError),
+}
+
+impl From<SafeMathError> for StreamError {
+    fn from(err: SafeMathError) -> Self {
+        StreamError::Math(err)
+    }
+}
+
+fn parse<'a>(records: &'a [&'a str]) -> impl Iterator<Item = Result<u32, StreamError>> + 'a {
+    records
+        .iter()
+        .enumerate()
+        .map(|(i, r)| r.parse().map_err(|_| StreamError::BadRecord(i)))
+}
+
+#[test]
+fn test_try_safe_sum_success() {
+    assert_eq!(parse(&["1", "2", "3"]).try_safe_sum(), Ok(6));
+    assert_eq!(parse(&[]).try_safe_sum(), Ok(0));
+}
+
+#[test]
+fn test_try_safe_sum_overflow_after_valid_items() {
+    let max = u32::MAX.to_string();
+    let records = ["5", "7", &max, "x"];
+    // The overflow on the third item stops the fold before the bad record.
+    assert_eq!(
+        parse(&records).try_safe_sum(),
+        Err(StreamError::Math(SafeMathError::Overflow))
+    );
+}
+
+#[test]
+fn test_try_safe_sum_item_error() {
+    assert_eq!(
+        parse(&["5", "x", "7"]).try_safe_sum(),
+        Err(StreamError::BadRecord(1))
+    );
+}
+
+#[test]
+fn test_try_safe_sum_stops_consuming() {
+    let mut seen = 0;
+    let items = [Ok(200u8), Ok(100), Ok(1)]
+        .into_iter()
+        .inspect(|_| seen += 1);
+    assert_eq!(
+        items.try_safe_sum(),
+        Err::<u8, SafeMathError>(SafeMathError::Overflow)
+    );
+    assert_eq!(seen, 2);
+}
@@ -0,0 +1,67 @@
+use safe_math::{konst, SafeMathError};
+
+const PAGE: usize = 4096;
+const PAGES: usize = 16;
+
+const BUFFER_SIZE: usize = match konst::usize::safe_mul(PAGE, PAGES) {
+    Ok(size) => size,
+    Err(_) => panic!("buffer size overflows usize"),
+};
+
+#[test]
+fn test_const_item() {
+    static BUFFER: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+    assert_eq!(BUFFER.len(), 65536);
+}
+
+#[test]
+fn test_const_blocks() {
+    assert_eq!(const { konst::u32::safe_add(1, 2) }, Ok(3));
+    assert_eq!(
+        const { konst::u8::safe_add(255, 1) },
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        const { konst::i8::safe_sub(-128, 1) },
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        const { konst::u64::safe_mul(1 << 32, 1 << 31) },
+        Ok(1 << 63)
+    );
+    assert_eq!(const { konst::i128::safe_div(7, -2) }, Ok(-3));
+    assert_eq!(const { konst::u16::safe_rem(7, 4) }, Ok(3));
+}
+
+#[test]
+fn test_const_division_errors() {
+    assert_eq!(
+        const { konst::u32::safe_div(1, 0) },
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(
+        const { konst::isize::safe_rem(1, 0) },
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(
+        const { konst::i32::safe_div(i32::MIN, -1) },
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        const { konst::i64::safe_rem(i64::MIN, -1) },
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_const_matches_generic_functions() {
+    for a in [0u8, 1, 100, 200, 255] {
+        for b in [0u8, 1, 2, 100, 255] {
+            assert_eq!(konst::u8::safe_add(a, b), safe_math::safe_add(a, b));
+            assert_eq!(konst::u8::safe_sub(a, b), safe_math::safe_sub(a, b));
+            assert_eq!(konst::u8::safe_mul(a, b), safe_math::safe_mul(a, b));
+            assert_eq!(konst::u8::safe_div(a, b), safe_math::safe_div(a, b));
+            assert_eq!(konst::u8::safe_rem(a, b), safe_math::safe_rem(a, b));
+        }
+    }
+}
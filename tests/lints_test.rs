@@ -0,0 +1,77 @@
+//! The expansion of `#[safe_math]` must not trigger lints in the user's crate: this
+//! file denies a wide set of them and is linted by `cargo clippy --all-targets`.
+#![deny(
+    warnings,
+    unused_qualifications,
+    unused_results,
+    clippy::all,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::arithmetic_side_effects,
+    clippy::integer_division,
+    clippy::indexing_slicing,
+    clippy::mixed_read_write_in_expression,
+    clippy::question_mark_used
+)]
+
+use safe_math::{safe_assert_no_overflow, safe_math, safe_math_block, SafeMathError};
+
+#[safe_math]
+fn sum(a: u32, b: u32) -> Result<u32, SafeMathError> {
+    Ok(a + b)
+}
+
+#[safe_math]
+fn compound(mut acc: u32, values: [u32; 2], k: u32) -> Result<u32, SafeMathError> {
+    let mut copy = values;
+    if let Some(first) = copy.first_mut() {
+        *first -= 1;
+    }
+    for v in copy {
+        acc += v;
+        acc *= (k);
+    }
+    acc /= k + 1;
+    Ok(acc % 7)
+}
+
+#[safe_math]
+fn optional(a: u8, b: u8) -> Option<u8> {
+    Some(match a * b {
+        0 => 0,
+        n => n - 1,
+    })
+}
+
+#[safe_math]
+fn markers(a: u8, b: u8) -> Result<u8, SafeMathError> {
+    let mixed = saturating!(a * b) / 2;
+    Ok(wrapping!(mixed + b))
+}
+
+#[safe_math(signed_sub)]
+fn delta(a: u32, b: u32) -> Result<i64, SafeMathError> {
+    Ok(a - b)
+}
+
+#[safe_math(mode = panic)]
+fn doubled(a: u32) -> u32 {
+    a * 2
+}
+
+fn block(a: u32) -> Result<u32, SafeMathError> {
+    let r = safe_math_block!({ a + 1 });
+    Ok(r)
+}
+
+#[test]
+fn test_lint_clean_expansion() {
+    assert_eq!(sum(1, 2), Ok(3));
+    assert_eq!(compound(0, [1, 2], 2), Ok(1));
+    assert_eq!(optional(2, 3), Some(5));
+    assert_eq!(markers(200, 2), Ok(129));
+    assert_eq!(delta(1, 2), Ok(-1));
+    assert_eq!(doubled(2), 4);
+    assert_eq!(block(1), Ok(2));
+    assert_eq!(safe_assert_no_overflow!(2u8 * 3), 6);
+}
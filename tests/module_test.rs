@@ -0,0 +1,118 @@
+use safe_math::safe_math;
+
+#[safe_math]
+mod billing {
+    use safe_math::SafeMathError;
+
+    pub fn total(price: u8, quantity: u8) -> Result<u8, SafeMathError> {
+        Ok(price * quantity)
+    }
+
+    pub mod discounts {
+        use safe_math::SafeMathError;
+
+        pub fn discounted(price: u8, discount: u8) -> Result<u8, SafeMathError> {
+            Ok(price - discount)
+        }
+
+        pub mod nested {
+            pub fn halve(value: u32, parts: u32) -> Option<u32> {
+                Some(value / parts)
+            }
+        }
+    }
+
+    /// Keeps its own arguments rather than the module's.
+    #[safe_math::safe_math(mode = panic)]
+    pub fn scaled(value: u8, factor: u8) -> u8 {
+        value * factor
+    }
+
+    /// `const fn`s are left unchanged.
+    pub const fn wrapping_double(value: u8) -> u8 {
+        value.wrapping_mul(2)
+    }
+
+    pub struct Cart {
+        pub items: u8,
+    }
+
+    impl Cart {
+        pub fn add(&mut self, count: u8) -> Result<(), SafeMathError> {
+            self.items += count;
+            Ok(())
+        }
+
+        /// Can't report an error, so it is left unchanged.
+        pub fn wrapping_len(&self) -> u8 {
+            self.items.wrapping_add(0)
+        }
+    }
+
+    pub trait Priced {
+        fn unit_price(&self) -> u8;
+
+        fn price(&self, quantity: u8) -> Result<u8, SafeMathError> {
+            Ok(self.unit_price() * quantity)
+        }
+    }
+
+    impl Priced for Cart {
+        fn unit_price(&self) -> u8 {
+            self.items
+        }
+    }
+
+    /// Returns a plain value, so it is left unchanged rather than rejected.
+    pub fn helper(value: u8) -> u8 {
+        value / 2
+    }
+
+    #[test]
+    fn test_inside_module() {
+        assert_eq!(total(2, 3), Ok(6));
+        assert_eq!(helper(7), 3);
+    }
+}
+
+#[test]
+fn test_module_functions() {
+    use safe_math::SafeMathError;
+
+    assert_eq!(billing::total(20, 3), Ok(60));
+    assert_eq!(billing::total(20, 13), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_nested_modules() {
+    use safe_math::SafeMathError;
+
+    assert_eq!(billing::discounts::discounted(20, 5), Ok(15));
+    assert_eq!(
+        billing::discounts::discounted(5, 20),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(billing::discounts::nested::halve(10, 2), Some(5));
+    assert_eq!(billing::discounts::nested::halve(10, 0), None);
+}
+
+#[test]
+fn test_module_skipped_functions() {
+    assert_eq!(billing::scaled(20, 3), 60);
+    assert!(std::panic::catch_unwind(|| billing::scaled(20, 13)).is_err());
+    assert_eq!(billing::wrapping_double(200), 144);
+}
+
+#[test]
+fn test_module_methods() {
+    use billing::{Cart, Priced};
+    use safe_math::SafeMathError;
+
+    let mut cart = Cart { items: 250 };
+    assert_eq!(cart.add(5), Ok(()));
+    assert_eq!(cart.add(1), Err(SafeMathError::Overflow));
+    assert_eq!(cart.items, 255);
+    assert_eq!(cart.wrapping_len(), 255);
+    assert_eq!(Cart { items: 20 }.price(3), Ok(60));
+    assert_eq!(cart.price(2), Err(SafeMathError::Overflow));
+}
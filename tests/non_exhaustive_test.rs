@@ -0,0 +1,20 @@
+//! Matches on `SafeMathError` from outside the crate, which must include a wildcard arm
+//! (see `tests/ui/exhaustive_error_match.rs` for the exhaustive match being rejected).
+
+use safe_math::{safe_add, safe_div, safe_parse, SafeMathError};
+
+fn exit_code(err: SafeMathError) -> u8 {
+    match err {
+        SafeMathError::Overflow => 10,
+        SafeMathError::DivisionByZero => 11,
+        // Required: new variants may be added in minor releases.
+        _ => 1,
+    }
+}
+
+#[test]
+fn test_external_match_with_wildcard() {
+    assert_eq!(safe_add(u8::MAX, 1).map_err(exit_code), Err(10));
+    assert_eq!(safe_div(1u8, 0).map_err(exit_code), Err(11));
+    assert_eq!(safe_parse::<u8>("x", 10).map_err(exit_code), Err(1));
+}
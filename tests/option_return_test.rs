@@ -0,0 +1,50 @@
+use safe_math::safe_math;
+
+#[safe_math]
+fn mean(values: &[u8]) -> Option<u8> {
+    let mut sum = 0u8;
+    for &v in values {
+        sum += v;
+    }
+    Some(sum / values.len() as u8)
+}
+
+#[safe_math]
+fn first_plus(values: &[u32], delta: u32) -> Option<u32> {
+    // A user `?` on an `Option` next to the rewritten arithmetic.
+    let first = values.first()?;
+    Some(*first + delta)
+}
+
+#[test]
+fn test_option_success() {
+    assert_eq!(mean(&[1, 2, 3]), Some(2));
+    assert_eq!(first_plus(&[1, 2], 10), Some(11));
+}
+
+#[test]
+fn test_option_overflow_is_none() {
+    assert_eq!(mean(&[200, 100]), None);
+    assert_eq!(first_plus(&[u32::MAX], 1), None);
+}
+
+#[test]
+fn test_option_division_by_zero_is_none() {
+    assert_eq!(mean(&[]), None);
+}
+
+#[test]
+fn test_option_user_question_mark() {
+    assert_eq!(first_plus(&[], 1), None);
+}
+
+#[test]
+fn test_option_qualified_path() {
+    #[safe_math]
+    fn half_sum(a: i8, b: i8) -> std::option::Option<i8> {
+        Some((a + b) / 2)
+    }
+
+    assert_eq!(half_sum(10, 20), Some(15));
+    assert_eq!(half_sum(i8::MAX, 1), None);
+}
@@ -0,0 +1,110 @@
+use safe_math::safe_math;
+
+#[safe_math(mode = panic)]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[safe_math(mode = panic)]
+fn average(values: &[u32]) -> u32 {
+    let mut sum = 0u32;
+    for v in values {
+        sum += *v;
+    }
+    sum / values.len() as u32
+}
+
+#[test]
+fn test_panic_mode_success() {
+    assert_eq!(add(10, 20), 30);
+    assert_eq!(average(&[1, 2, 3]), 2);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow in `a + b`")]
+fn test_panic_mode_overflow() {
+    add(255, 1);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow in `sum += * v`")]
+fn test_panic_mode_compound_assignment_overflow() {
+    average(&[u32::MAX, 1]);
+}
+
+#[test]
+#[should_panic(expected = "division by zero in `sum / values.len() as u32`")]
+fn test_panic_mode_division_by_zero() {
+    average(&[]);
+}
+
+#[test]
+fn test_checked_mode_is_explicit_default() {
+    #[safe_math(mode = checked)]
+    fn sub(a: u8, b: u8) -> Result<u8, safe_math::SafeMathError> {
+        Ok(a - b)
+    }
+
+    assert_eq!(sub(1, 2), Err(safe_math::SafeMathError::Overflow));
+}
+
+#[test]
+fn test_assert_no_overflow_returns_value() {
+    let (a, b) = (200u8, 50u8);
+    assert_eq!(safe_math::safe_assert_no_overflow!(a + b), 250);
+    assert_eq!(safe_math::safe_assert_no_overflow!((a - b) * 1 / 3), 50);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow in `a * b`")]
+fn test_assert_no_overflow_panics() {
+    let (a, b) = (16u8, 16u8);
+    safe_math::safe_assert_no_overflow!(a * b);
+}
+
+#[test]
+#[should_panic(expected = "division by zero in `a / b`")]
+fn test_assert_no_overflow_statement() {
+    let (a, b) = (1u32, 0u32);
+    safe_math::safe_assert_no_overflow!({
+        let _ = a / b;
+    });
+}
+
+#[safe_math(mode = panic_verbose)]
+fn scale(a: u8, b: u8, c: u8) -> u8 {
+    a * b / c
+}
+
+#[safe_math(mode = panic_verbose)]
+fn total(values: &[i32]) -> i32 {
+    let mut sum = 0;
+    for v in values {
+        sum += *v;
+    }
+    sum
+}
+
+#[test]
+fn test_panic_verbose_mode_success() {
+    assert_eq!(scale(10, 20, 4), 50);
+    assert_eq!(total(&[-1, 2, 3]), 4);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow in `a * b`: safe_mul(200, 2)")]
+fn test_panic_verbose_mode_overflow() {
+    scale(200, 2, 4);
+}
+
+#[test]
+#[should_panic(expected = "division by zero in `a * b / c`: safe_div(20, 0)")]
+fn test_panic_verbose_mode_division_by_zero() {
+    scale(10, 2, 0);
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow in `sum += * v`: safe_add(2147483647, 1)")]
+fn test_panic_verbose_mode_compound_assignment_overflow() {
+    total(&[i32::MAX, 1]);
+}
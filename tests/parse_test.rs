@@ -0,0 +1,33 @@
+use safe_math::*;
+
+#[test]
+fn test_safe_parse() {
+    assert_eq!(safe_parse::<u8>("255", 10), Ok(255));
+    assert_eq!(safe_parse::<i64>("-ff", 16), Ok(-255));
+    assert_eq!(safe_parse::<u32>("+101", 2), Ok(5));
+}
+
+#[test]
+fn test_safe_parse_overflow() {
+    assert_eq!(safe_parse::<u8>("300", 10), Err(SafeMathError::Overflow));
+    assert_eq!(safe_parse::<i8>("-129", 10), Err(SafeMathError::Overflow));
+    assert_eq!(
+        safe_parse::<u64>("10000000000000000", 16),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_safe_parse_invalid() {
+    assert_eq!(safe_parse::<u8>("abc", 10), Err(SafeMathError::ParseError));
+    assert_eq!(safe_parse::<u8>("", 10), Err(SafeMathError::ParseError));
+    assert_eq!(safe_parse::<u8>("-1", 10), Err(SafeMathError::ParseError));
+    assert_eq!(safe_parse::<u8>("12", 2), Err(SafeMathError::ParseError));
+}
+
+#[test]
+fn test_safe_parse_invalid_radix() {
+    assert_eq!(safe_parse::<u8>("1", 1), Err(SafeMathError::ParseError));
+    assert_eq!(safe_parse::<u8>("1", 37), Err(SafeMathError::ParseError));
+    assert_eq!(safe_parse::<u8>("z", 36), Ok(35));
+}
@@ -0,0 +1,140 @@
+use safe_math::{safe_math, safe_math_block, SafeMathError};
+
+#[safe_math]
+fn score(base: u8, bonus: u8, factor: u8) -> Result<u8, SafeMathError> {
+    let boosted = saturating!(bonus * factor);
+    Ok(base + boosted)
+}
+
+#[safe_math]
+fn checksum(values: &[u8]) -> Result<u8, SafeMathError> {
+    let mut sum = 0u8;
+    for &v in values {
+        wrapping!(sum += v * 31);
+    }
+    Ok(sum / 2)
+}
+
+#[test]
+fn test_saturating_marker_mixed_with_checked_addition() {
+    assert_eq!(score(1, 2, 3), Ok(7));
+    // The multiplication saturates at 255 instead of failing...
+    assert_eq!(score(0, 100, 100), Ok(255));
+    // ...but the addition outside of the marker is still checked.
+    assert_eq!(score(1, 100, 100), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_wrapping_marker_in_compound_assignment() {
+    let expected = [200u8, 100, 7]
+        .iter()
+        .fold(0u8, |sum, &v| sum.wrapping_add(v.wrapping_mul(31)));
+    assert_eq!(checksum(&[200, 100, 7]), Ok(expected / 2));
+}
+
+#[test]
+fn test_marker_keeps_division_checked() {
+    #[safe_math]
+    fn ratio(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        Ok(saturating!(a * a / b))
+    }
+
+    assert_eq!(ratio(20, 2), Ok(127));
+    assert_eq!(ratio(20, 0), Err(SafeMathError::DivisionByZero));
+}
+
+#[test]
+fn test_nested_markers() {
+    #[safe_math]
+    fn mixed(a: i8, b: i8) -> Result<i8, SafeMathError> {
+        Ok(saturating!(a + wrapping!(b * 2)))
+    }
+
+    // `b * 2` wraps to -2, then `127 + -2` fits.
+    assert_eq!(mixed(127, 127), Ok(125));
+    assert_eq!(mixed(127, 1), Ok(127));
+}
+
+#[test]
+fn test_marker_in_block() {
+    fn clamp_sum(a: u32, b: u32) -> Result<u32, SafeMathError> {
+        Ok(safe_math_block!(saturating!(a + b) - 1))
+    }
+
+    assert_eq!(clamp_sum(u32::MAX, 1), Ok(u32::MAX - 1));
+    assert_eq!(clamp_sum(0, 0), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_unchecked_marker_is_left_raw() {
+    #[safe_math]
+    fn hash_step(h: u32, c: u32, k: u32) -> Result<u32, SafeMathError> {
+        let mixed = unchecked!(h.wrapping_mul(31).wrapping_add(c));
+        Ok(mixed / k + 1)
+    }
+
+    assert_eq!(hash_step(1, 2, 1), Ok(34));
+    // The raw wrapping arithmetic doesn't fail...
+    assert_eq!(
+        hash_step(u32::MAX, 0, 2),
+        Ok(u32::MAX.wrapping_mul(31) / 2 + 1)
+    );
+    // ...while the surrounding operators are still checked.
+    assert_eq!(hash_step(1, 2, 0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(hash_step(u32::MAX, 30, 1), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_unchecked_marker_statement() {
+    #[safe_math]
+    fn fill(buf: &mut [u8], start: u8) -> Result<u8, SafeMathError> {
+        let mut next = start;
+        for slot in buf.iter_mut() {
+            *slot = next;
+            unchecked!(next = next.wrapping_add(100));
+        }
+        Ok(next - start)
+    }
+
+    let mut buf = [0; 3];
+    assert_eq!(fill(&mut buf, 10), Ok(44));
+    assert_eq!(buf, [10, 110, 210]);
+    assert_eq!(fill(&mut buf, 220), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_outer_operator_checks_clamped_value() {
+    #[safe_math]
+    fn clamp_then_add(a: u8, b: u8, c: u8) -> Result<u8, SafeMathError> {
+        Ok(saturating!(a * b) + c)
+    }
+
+    // The product clamps to 255 without an error...
+    assert_eq!(clamp_then_add(16, 16, 0), Ok(255));
+    // ...and the checked addition then fails on the clamped value.
+    assert_eq!(clamp_then_add(16, 16, 1), Err(SafeMathError::Overflow));
+    assert_eq!(clamp_then_add(10, 10, 155), Ok(255));
+}
+
+#[test]
+#[should_panic(expected = "arithmetic overflow")]
+fn test_outer_operator_follows_panic_mode() {
+    #[safe_math(mode = panic)]
+    fn clamp_then_add(a: u8, b: u8, c: u8) -> u8 {
+        saturating!(a * b) + c
+    }
+
+    assert_eq!(clamp_then_add(16, 16, 0), 255);
+    clamp_then_add(16, 16, 1);
+}
+
+#[test]
+fn test_innermost_marker_wins() {
+    #[safe_math]
+    fn mixed(a: u8, b: u8, c: u8) -> Result<u8, SafeMathError> {
+        Ok(wrapping!(saturating!(a * b) + c))
+    }
+
+    // 16 * 16 clamps to 255, then the wrapping addition goes around.
+    assert_eq!(mixed(16, 16, 2), Ok(1));
+}
@@ -1,20 +1,42 @@
 use proptest::prelude::*;
+use safe_math::SafeMathError;
 
 mod common;
 use common::{
     test_add_macro, test_div_macro, test_mul_macro, test_rem_macro, test_sub_macro, Expression,
 };
 
+/// Strategy biased towards the values where checked arithmetic fails: zero,
+/// the type bounds and `-1` (which wraps to `MAX` for unsigned types).
+macro_rules! edge_biased {
+    ($t:ty) => {
+        prop_oneof![
+            1 => Just(0 as $t),
+            1 => Just(<$t>::MIN),
+            1 => Just(<$t>::MAX),
+            1 => Just((0 as $t).wrapping_sub(1)),
+            6 => any::<$t>(),
+        ]
+    };
+}
+
 macro_rules! test_numeric_types {
     ($(($name:ident, $t:ty)),*) => {
         $(
             proptest! {
                 #[test]
-                fn $name(a in any::<$t>(), b in any::<$t>()) {
-                        prop_assert_eq!(test_add_macro(a, b), a.checked_add(b).ok_or(()));
-                        prop_assert_eq!(test_sub_macro(a, b), a.checked_sub(b).ok_or(()));
-                        prop_assert_eq!(test_mul_macro(a, b), a.checked_mul(b).ok_or(()));
-                        prop_assert_eq!(test_div_macro(a, b), a.checked_div(b).ok_or(()));
+                fn $name(a in edge_biased!($t), b in edge_biased!($t)) {
+                        // std only reports `None`; recover the expected cause from the operands.
+                        let div_err = if b == 0 {
+                            SafeMathError::DivisionByZero
+                        } else {
+                            SafeMathError::Overflow
+                        };
+                        prop_assert_eq!(test_add_macro(a, b), a.checked_add(b).ok_or(SafeMathError::Overflow));
+                        prop_assert_eq!(test_sub_macro(a, b), a.checked_sub(b).ok_or(SafeMathError::Overflow));
+                        prop_assert_eq!(test_mul_macro(a, b), a.checked_mul(b).ok_or(SafeMathError::Overflow));
+                        prop_assert_eq!(test_div_macro(a, b), a.checked_div(b).ok_or(div_err));
+                        prop_assert_eq!(test_rem_macro(a, b), a.checked_rem(b).ok_or(div_err));
                 }
             }
         )*
@@ -42,12 +64,12 @@ macro_rules! test_float_types {
             proptest! {
                 #[test]
                 fn $name(a in any::<$t>(), b in any::<$t>()) {
-
-                    prop_assert_eq!(test_add_macro(a, b), ((a+b).is_finite()).then(|| (a+b)).ok_or(()));
-                    prop_assert_eq!(test_sub_macro(a, b), ((a-b).is_finite()).then(|| (a-b)).ok_or(()));
-                    prop_assert_eq!(test_mul_macro(a, b), ((a*b).is_finite()).then(|| (a*b)).ok_or(()));
-                    prop_assert_eq!(test_div_macro(a, b), ((a/b).is_finite()).then(|| (a/b)).ok_or(()));
-                    prop_assert_eq!(test_rem_macro(a, b), ((a%b).is_finite()).then(|| (a%b)).ok_or(()));
+                    let err = SafeMathError::InfiniteOrNaN;
+                    prop_assert_eq!(test_add_macro(a, b), ((a+b).is_finite()).then(|| (a+b)).ok_or(err));
+                    prop_assert_eq!(test_sub_macro(a, b), ((a-b).is_finite()).then(|| (a-b)).ok_or(err));
+                    prop_assert_eq!(test_mul_macro(a, b), ((a*b).is_finite()).then(|| (a*b)).ok_or(err));
+                    prop_assert_eq!(test_div_macro(a, b), ((a/b).is_finite()).then(|| (a/b)).ok_or(err));
+                    prop_assert_eq!(test_rem_macro(a, b), ((a%b).is_finite()).then(|| (a%b)).ok_or(err));
                 }
             }
         )*
@@ -0,0 +1,39 @@
+use safe_math::{safe_add, safe_math, SafeMathError, SafeResultExt};
+
+#[test]
+fn test_chain_success() {
+    assert_eq!(safe_add(10u32, 5).safe_mul(4).safe_sub(20), Ok(40));
+    assert_eq!(safe_add(10u32, 5).safe_div(4).safe_rem(2), Ok(1));
+    assert_eq!(Ok(1.5f64).safe_mul(2.0).safe_add(1.0), Ok(4.0));
+}
+
+#[test]
+fn test_chain_overflow_in_middle_step() {
+    // 200 + 50 fits, * 2 overflows, the trailing steps are skipped.
+    assert_eq!(
+        safe_add(200u8, 50).safe_mul(2).safe_sub(100).safe_div(2),
+        Err(SafeMathError::Overflow)
+    );
+    // A later division by zero is reported as such.
+    assert_eq!(
+        safe_add(1i32, 1).safe_sub(2).safe_div(0),
+        Err(SafeMathError::DivisionByZero)
+    );
+    // The first error wins even if later steps would fail differently.
+    assert_eq!(
+        safe_add(i8::MAX, 1).safe_div(0),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_chain_inside_safe_math_fn() {
+    #[safe_math]
+    fn scaled(a: u16, b: u16) -> Result<u16, SafeMathError> {
+        let base = a + b;
+        Ok(base).safe_mul(100).safe_add(1)
+    }
+
+    assert_eq!(scaled(1, 2), Ok(301));
+    assert_eq!(scaled(600, 100), Err(SafeMathError::Overflow));
+}
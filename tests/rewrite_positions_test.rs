@@ -0,0 +1,496 @@
+//! Checks that `#[safe_math]` rewrites arithmetic wherever it appears in an
+//! expression tree, not only in plain `let` initializers and return values.
+
+use safe_math::{safe_math, SafeMathError};
+
+#[test]
+fn test_range_bounds_are_checked() {
+    #[safe_math]
+    #[allow(clippy::needless_range_loop)]
+    fn sum_window(vals: &[u8], start: usize, len: usize) -> Result<u8, SafeMathError> {
+        let mut sum = 0u8;
+        for i in start..start + len {
+            sum += vals[i];
+        }
+        Ok(sum)
+    }
+
+    assert_eq!(sum_window(&[1, 2, 3, 4], 1, 2), Ok(5));
+    // The upper bound overflows before the loop starts.
+    assert_eq!(sum_window(&[], usize::MAX, 1), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_inclusive_range_bounds_are_checked() {
+    #[safe_math]
+    fn count(lo: u8, hi: u8) -> Result<u32, SafeMathError> {
+        let mut n = 0u32;
+        for _ in lo - 1..=hi + 1 {
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    assert_eq!(count(1, 2), Ok(4));
+    assert_eq!(count(0, 2), Err(SafeMathError::Overflow));
+    assert_eq!(count(1, u8::MAX), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_loop_body_is_checked() {
+    #[safe_math]
+    fn sum_all(vals: &[u8]) -> Result<u8, SafeMathError> {
+        let mut sum = 0u8;
+        for v in vals {
+            sum += *v;
+        }
+        Ok(sum)
+    }
+
+    assert_eq!(sum_all(&[100, 100, 50]), Ok(250));
+    // `?` leaves the whole function, not just the current iteration.
+    assert_eq!(sum_all(&[200, 100, 1]), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_while_body_is_checked() {
+    #[safe_math]
+    fn double_until(mut x: u8, limit: u8) -> Result<u8, SafeMathError> {
+        while x < limit {
+            x = x * 2;
+        }
+        Ok(x)
+    }
+
+    assert_eq!(double_until(3, 100), Ok(192));
+    assert_eq!(double_until(3, 200), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_while_condition_is_checked() {
+    #[safe_math]
+    fn fill(step: u8, limit: u8) -> Result<(u8, u32), SafeMathError> {
+        let (mut total, mut iterations) = (0u8, 0u32);
+        while total + step <= limit {
+            total += step;
+            iterations += 1;
+        }
+        Ok((total, iterations))
+    }
+
+    // The loop ends because the condition is false...
+    assert_eq!(fill(30, 100), Ok((90, 3)));
+    assert_eq!(fill(50, 200), Ok((200, 4)));
+    // ...or the function returns as soon as it overflows, here in the third iteration.
+    assert_eq!(fill(100, 255), Err(SafeMathError::Overflow));
+    assert_eq!(fill(255, 255), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_while_let_scrutinee_is_checked() {
+    #[safe_math]
+    fn countdown(start: u8, step: u8) -> Result<u32, SafeMathError> {
+        let mut remaining = start;
+        let mut ticks = 0;
+        while let Some(next) = checked_even(remaining - step) {
+            remaining = next;
+            ticks += 1;
+        }
+        Ok(ticks)
+    }
+
+    // Stops at the first odd value, or fails going below zero.
+    assert_eq!(countdown(9, 2), Ok(0));
+    assert_eq!(countdown(10, 2), Err(SafeMathError::Overflow));
+}
+
+fn checked_even(x: u8) -> Option<u8> {
+    (x % 2 == 0).then_some(x)
+}
+
+#[test]
+fn test_let_else_scrutinee_is_checked() {
+    #[safe_math]
+    fn half_of_sum(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        let Some(sum) = checked_even(a + b) else {
+            return Ok(0);
+        };
+        Ok(sum / 2)
+    }
+
+    assert_eq!(half_of_sum(3, 5), Ok(4));
+    assert_eq!(half_of_sum(3, 4), Ok(0));
+    assert_eq!(half_of_sum(250, 10), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_let_else_diverging_branch_is_checked() {
+    #[safe_math]
+    fn fallback(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        let Some(x) = checked_even(a) else {
+            return Ok(a * b);
+        };
+        Ok(x)
+    }
+
+    assert_eq!(fallback(4, 100), Ok(4));
+    assert_eq!(fallback(3, 2), Ok(6));
+    assert_eq!(fallback(3, 100), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_if_let_scrutinee_is_checked() {
+    #[safe_math]
+    fn describe(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        if let Some(x) = checked_even(a * b) {
+            Ok(x)
+        } else {
+            Ok(1)
+        }
+    }
+
+    assert_eq!(describe(2, 3), Ok(6));
+    assert_eq!(describe(1, 3), Ok(1));
+    // The scrutinee overflows, so neither branch runs.
+    assert_eq!(describe(16, 16), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_if_condition_is_checked() {
+    #[safe_math]
+    fn exceeds(a: u8, b: u8, limit: u8) -> Result<bool, SafeMathError> {
+        if a + b > limit {
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    assert_eq!(exceeds(1, 2, 2), Ok(true));
+    assert_eq!(exceeds(1, 1, 2), Ok(false));
+    assert_eq!(exceeds(255, 1, 2), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_match_scrutinee_is_checked() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static ARMS_RUN: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn arm(value: u8) -> u8 {
+        ARMS_RUN.with(|n| n.set(n.get() + 1));
+        value
+    }
+
+    #[safe_math]
+    fn classify(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        match a + b {
+            0 => Ok(arm(0)),
+            n if n < 10 => Ok(arm(1)),
+            n => Ok(arm(n)),
+        }
+    }
+
+    assert_eq!(classify(0, 0), Ok(0));
+    assert_eq!(classify(2, 3), Ok(1));
+    assert_eq!(classify(200, 50), Ok(250));
+    assert_eq!(ARMS_RUN.with(Cell::get), 3);
+    // The scrutinee overflows, so no arm runs.
+    assert_eq!(classify(200, 100), Err(SafeMathError::Overflow));
+    assert_eq!(ARMS_RUN.with(Cell::get), 3);
+}
+
+#[test]
+fn test_match_guards_and_arms_are_checked() {
+    #[safe_math]
+    fn bucket(a: u8, b: u8) -> Result<u8, SafeMathError> {
+        match a {
+            0 => Ok(b),
+            n if n * b > 100 => Ok(100),
+            n => Ok(n + b),
+        }
+    }
+
+    assert_eq!(bucket(0, 7), Ok(7));
+    assert_eq!(bucket(20, 10), Ok(100));
+    assert_eq!(bucket(2, 3), Ok(5));
+    // The guard overflows before the arm is chosen.
+    assert_eq!(bucket(16, 16), Err(SafeMathError::Overflow));
+    assert_eq!(bucket(1, 255), Ok(100));
+}
+
+#[test]
+fn test_match_arm_bodies_and_bindings_are_checked() {
+    #[safe_math]
+    fn next(value: Option<u8>, step: u8) -> Result<u8, SafeMathError> {
+        match value {
+            None => Ok(0),
+            Some(small @ 0..=9) => Ok(small * step),
+            Some(n @ 10..=99) if n + step > 100 => Ok(100),
+            Some(n) => Ok(n + 1),
+        }
+    }
+
+    assert_eq!(next(None, 3), Ok(0));
+    assert_eq!(next(Some(5), 3), Ok(15));
+    assert_eq!(next(Some(50), 60), Ok(100));
+    assert_eq!(next(Some(50), 10), Ok(51));
+    assert_eq!(next(Some(200), 0), Ok(201));
+    // The `@`-bound value overflows in the arm body...
+    assert_eq!(next(Some(9), 100), Err(SafeMathError::Overflow));
+    // ...and in the guard, which returns rather than trying the last arm.
+    assert_eq!(next(Some(99), 200), Err(SafeMathError::Overflow));
+    // The last arm's body overflows.
+    assert_eq!(next(Some(255), 0), Err(SafeMathError::Overflow));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    x: u8,
+    y: u8,
+    z: u8,
+}
+
+#[test]
+fn test_struct_field_initializers_are_checked() {
+    #[safe_math]
+    fn make(a: u8, b: u8) -> Result<Point, SafeMathError> {
+        Ok(Point {
+            x: a + b,
+            y: a * b,
+            z: 0,
+        })
+    }
+
+    assert_eq!(make(2, 3), Ok(Point { x: 5, y: 6, z: 0 }));
+    assert_eq!(make(200, 100), Err(SafeMathError::Overflow));
+    // Only the second field overflows.
+    assert_eq!(make(16, 16), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_struct_update_base_is_checked() {
+    fn origin(z: u8) -> Point {
+        Point { x: 0, y: 0, z }
+    }
+
+    #[safe_math]
+    fn shift(base: Point, dx: u8) -> Result<Point, SafeMathError> {
+        Ok(Point {
+            x: base.x + dx,
+            ..base
+        })
+    }
+
+    #[safe_math]
+    fn lifted(x: u8, dz: u8) -> Result<Point, SafeMathError> {
+        Ok(Point {
+            x,
+            ..origin(dz * 2)
+        })
+    }
+
+    let base = Point { x: 1, y: 2, z: 3 };
+    assert_eq!(shift(base, 4), Ok(Point { x: 5, y: 2, z: 3 }));
+    assert_eq!(shift(base, 255), Err(SafeMathError::Overflow));
+    assert_eq!(lifted(7, 10), Ok(Point { x: 7, y: 0, z: 20 }));
+    assert_eq!(lifted(7, 200), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_break_value_is_checked() {
+    #[safe_math]
+    fn double_after(limit: u8, start: u8) -> Result<u8, SafeMathError> {
+        let mut acc = start;
+        let result = loop {
+            if acc >= limit {
+                break acc * 2;
+            }
+            acc += 1;
+        };
+        Ok(result)
+    }
+
+    assert_eq!(double_after(10, 3), Ok(20));
+    assert_eq!(double_after(128, 100), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_plain_break_is_untouched() {
+    #[safe_math]
+    fn count_until(limit: u8) -> Result<u8, SafeMathError> {
+        let mut n = 0;
+        loop {
+            if n == limit {
+                break;
+            }
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    assert_eq!(count_until(5), Ok(5));
+    assert_eq!(count_until(255), Ok(255));
+}
+
+#[test]
+fn test_index_is_checked() {
+    #[safe_math]
+    fn next(vals: &[u8], i: usize) -> Result<u8, SafeMathError> {
+        Ok(vals[i + 1])
+    }
+
+    assert_eq!(next(&[1, 2, 3], 1), Ok(3));
+    // `i + 1` overflows before `vals` is indexed, so there is no bounds-check panic.
+    assert_eq!(next(&[1, 2, 3], usize::MAX), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_compound_assignment_index_is_checked() {
+    #[safe_math]
+    fn bump_next(vals: &mut [u8], i: usize) -> Result<(), SafeMathError> {
+        vals[i + 1] += 1;
+        Ok(())
+    }
+
+    let mut vals = [1, 2, 3];
+    assert_eq!(bump_next(&mut vals, 0), Ok(()));
+    assert_eq!(vals, [1, 3, 3]);
+    assert_eq!(
+        bump_next(&mut vals, usize::MAX),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(vals, [1, 3, 3]);
+}
+
+#[test]
+fn test_const_contexts_are_untouched() {
+    const N: usize = 3;
+
+    fn len<const M: usize>() -> usize {
+        M
+    }
+
+    #[safe_math]
+    fn padded(data: [u8; N], pad: u8) -> Result<usize, SafeMathError> {
+        const EXTRA: usize = N - 1;
+        let mut out: [u8; N + 1] = [pad; N + 1];
+        out[..N].copy_from_slice(&data);
+        let total = out.len() + len::<{ N * 2 }>() + const { EXTRA * 10 };
+        Ok(total + usize::from(out[N]))
+    }
+
+    assert_eq!(padded([1, 2, 3], 5), Ok(4 + 6 + 20 + 5));
+}
+
+#[test]
+fn test_call_arguments_are_checked_before_the_call() {
+    use std::cell::Cell;
+
+    thread_local! {
+        static CALLS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn process(x: u8, y: u8) -> u16 {
+        CALLS.with(|n| n.set(n.get() + 1));
+        u16::from(x) + u16::from(y)
+    }
+
+    struct Sink;
+
+    impl Sink {
+        fn process(&self, x: u8, y: u8) -> u16 {
+            process(x, y)
+        }
+    }
+
+    #[safe_math]
+    fn via_path(a: u8, b: u8, c: u8, d: u8) -> Result<u16, SafeMathError> {
+        Ok(process(a + b, c * d))
+    }
+
+    #[safe_math]
+    fn via_method(a: u8, b: u8, c: u8, d: u8) -> Result<u16, SafeMathError> {
+        Ok(Sink.process(a + b, c * d))
+    }
+
+    #[safe_math]
+    fn via_closure(a: u8, b: u8, c: u8, d: u8) -> Result<u16, SafeMathError> {
+        let f = |x: u8, y: u8| process(x, y);
+        Ok(f(a + b, c * d))
+    }
+
+    for f in [via_path, via_method, via_closure] {
+        assert_eq!(f(1, 2, 3, 4), Ok(15));
+        let calls = CALLS.with(Cell::get);
+        // Either argument overflowing returns before the callee runs.
+        assert_eq!(f(255, 1, 3, 4), Err(SafeMathError::Overflow));
+        assert_eq!(f(1, 2, 16, 16), Err(SafeMathError::Overflow));
+        assert_eq!(CALLS.with(Cell::get), calls);
+    }
+}
+
+#[test]
+fn test_std_macro_arguments_are_checked() {
+    #[safe_math]
+    fn render(a: u8, b: u8) -> Result<String, SafeMathError> {
+        assert!(a + b > 0, "empty sum of {} and {}", a, b);
+        Ok(format!("{} = {}", a * b, a * b / 2))
+    }
+
+    #[safe_math]
+    fn buffers(len: usize, n: usize) -> Result<Vec<Vec<u8>>, SafeMathError> {
+        Ok(vec![vec![0; len + 1]; n * 2])
+    }
+
+    assert_eq!(render(2, 3), Ok("6 = 3".to_string()));
+    // The assertion doesn't panic: its condition overflows first.
+    assert_eq!(render(255, 1), Err(SafeMathError::Overflow));
+    assert_eq!(render(16, 16), Err(SafeMathError::Overflow));
+    assert_eq!(buffers(1, 1), Ok(vec![vec![0; 2]; 2]));
+    assert_eq!(buffers(usize::MAX, 1), Err(SafeMathError::Overflow));
+    assert_eq!(buffers(1, usize::MAX), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_other_macros_are_untouched() {
+    #[safe_math]
+    fn source() -> Result<&'static str, SafeMathError> {
+        Ok(stringify!(a + b))
+    }
+
+    assert_eq!(source(), Ok("a + b"));
+}
+
+#[test]
+fn test_nested_wrappers_are_checked() {
+    #[safe_math]
+    fn nested_some(a: u8, b: u8) -> Result<Option<u8>, SafeMathError> {
+        Ok(Some(a + b))
+    }
+
+    #[safe_math]
+    fn pair(a: u8, b: u8, c: u8, d: u8) -> Result<(u8, u8), SafeMathError> {
+        Ok((a + b, c * d))
+    }
+
+    #[safe_math]
+    fn early_return(a: u8, b: u8) -> Result<Option<(u8, u8)>, SafeMathError> {
+        if a > b {
+            return Ok(Some((a - b, a / b)));
+        }
+        Ok(None)
+    }
+
+    assert_eq!(nested_some(1, 2), Ok(Some(3)));
+    assert_eq!(nested_some(255, 1), Err(SafeMathError::Overflow));
+    assert_eq!(pair(1, 2, 3, 4), Ok((3, 12)));
+    // The first element is fine, but the second overflowing fails the whole tuple.
+    assert_eq!(pair(1, 2, 16, 16), Err(SafeMathError::Overflow));
+    assert_eq!(pair(255, 1, 3, 4), Err(SafeMathError::Overflow));
+    assert_eq!(early_return(7, 2), Ok(Some((5, 3))));
+    assert_eq!(early_return(2, 7), Ok(None));
+    assert_eq!(early_return(1, 0), Err(SafeMathError::DivisionByZero));
+}
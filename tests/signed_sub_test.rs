@@ -0,0 +1,65 @@
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math(signed_sub)]
+fn delta(before: u32, after: u32) -> Result<i64, SafeMathError> {
+    Ok(after - before)
+}
+
+#[safe_math(signed_sub)]
+fn net_change(deposits: u32, withdrawals: u32, fees: u32) -> Result<i64, SafeMathError> {
+    // The addition is unaffected, the subtraction widens its result.
+    Ok(deposits - (withdrawals + fees))
+}
+
+#[test]
+fn test_signed_sub() {
+    assert_eq!(delta(10, 3), Ok(-7i64));
+    assert_eq!(delta(3, 10), Ok(7));
+    assert_eq!(delta(u32::MAX, 0), Ok(-(u32::MAX as i64)));
+    assert_eq!(net_change(100, 30, 5), Ok(65));
+    assert_eq!(net_change(0, 30, 5), Ok(-35));
+    assert_eq!(net_change(0, u32::MAX, 1), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_signed_sub_literals() {
+    #[safe_math(signed_sub)]
+    fn below_zero() -> Result<i16, SafeMathError> {
+        Ok(3u8 - 10u8)
+    }
+
+    assert_eq!(below_zero(), Ok(-7));
+}
+
+#[test]
+fn test_signed_sub_compound_assignment_is_unchanged() {
+    #[safe_math(signed_sub)]
+    fn spend(mut balance: u32, cost: u32) -> Result<u32, SafeMathError> {
+        balance -= cost;
+        Ok(balance)
+    }
+
+    assert_eq!(spend(10, 3), Ok(7));
+    assert_eq!(spend(3, 10), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_signed_sub_u128() {
+    #[safe_math(signed_sub)]
+    fn delta(a: u128, b: u128) -> Result<i128, SafeMathError> {
+        Ok(a - b)
+    }
+
+    assert_eq!(delta(3, 10), Ok(-7));
+    assert_eq!(delta(u128::MAX, 0), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_signed_sub_panic_mode() {
+    #[safe_math(mode = panic, signed_sub)]
+    fn delta(a: u8, b: u8) -> i16 {
+        a - b
+    }
+
+    assert_eq!(delta(3, 10), -7);
+}
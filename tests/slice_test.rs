@@ -0,0 +1,88 @@
+use safe_math::*;
+
+#[test]
+fn test_slice_ops() {
+    assert_eq!(safe_add_slices(&[1u8, 2, 3], &[4, 5, 6]), Ok(vec![5, 7, 9]));
+    assert_eq!(safe_sub_slices(&[10i32, 0], &[3, 5]), Ok(vec![7, -5]));
+    assert_eq!(safe_mul_slices(&[2u16, 3], &[4, 5]), Ok(vec![8, 15]));
+    assert_eq!(safe_div_slices(&[9u32, 8], &[3, 2]), Ok(vec![3, 4]));
+    assert_eq!(safe_rem_slices(&[9u32, 8], &[4, 3]), Ok(vec![1, 2]));
+    assert_eq!(safe_add_slices::<u8>(&[], &[]), Ok(vec![]));
+}
+
+#[test]
+fn test_slice_element_error() {
+    assert_eq!(
+        safe_add_slices(&[1u8, 200, 255], &[1, 100, 1]),
+        Err(SliceError::Element {
+            index: 1,
+            error: SafeMathError::Overflow
+        })
+    );
+    assert_eq!(
+        safe_div_slices(&[4u8, 4], &[2, 0]),
+        Err(SliceError::Element {
+            index: 1,
+            error: SafeMathError::DivisionByZero
+        })
+    );
+    assert_eq!(
+        SliceError::Element {
+            index: 1,
+            error: SafeMathError::Overflow
+        }
+        .to_string(),
+        "arithmetic overflow at index 1"
+    );
+}
+
+#[test]
+fn test_slice_length_mismatch() {
+    assert_eq!(
+        safe_mul_slices(&[1u8, 2, 3], &[1, 2]),
+        Err(SliceError::LengthMismatch { left: 3, right: 2 })
+    );
+}
+
+#[test]
+fn test_array_ops() {
+    assert_eq!(
+        safe_add_arrays([1u8, 2, 3, 4], [4, 5, 6, 7]),
+        Ok([5, 7, 9, 11])
+    );
+    assert_eq!(safe_sub_arrays([10i32, 0], [3, 5]), Ok([7, -5]));
+    assert_eq!(safe_mul_arrays([2u16, 3], [4, 5]), Ok([8, 15]));
+    assert_eq!(safe_div_arrays([9u32, 8], [3, 2]), Ok([3, 4]));
+    assert_eq!(safe_rem_arrays([9u32, 8], [4, 3]), Ok([1, 2]));
+    assert_eq!(
+        safe_add_arrays([1.5f64, 2.0, 3.0], [0.5; 3]),
+        Ok([2.0, 2.5, 3.5])
+    );
+    assert_eq!(safe_add_arrays::<u8, 0>([], []), Ok([]));
+}
+
+#[test]
+fn test_array_element_error() {
+    // The third element overflows; the fourth would too, but the first failure wins.
+    assert_eq!(
+        safe_add_arrays([1u8, 2, 200, 255], [1, 2, 100, 1]),
+        Err(SliceError::Element {
+            index: 2,
+            error: SafeMathError::Overflow
+        })
+    );
+    assert_eq!(
+        safe_div_arrays([4u8, 4, 4], [2, 0, 1]),
+        Err(SliceError::Element {
+            index: 1,
+            error: SafeMathError::DivisionByZero
+        })
+    );
+    assert_eq!(
+        safe_mul_arrays([f64::MAX, 1.0, 1.0], [2.0; 3]),
+        Err(SliceError::Element {
+            index: 0,
+            error: SafeMathError::InfiniteOrNaN
+        })
+    );
+}
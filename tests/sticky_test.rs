@@ -0,0 +1,33 @@
+use safe_math::{Checked, SafeMathError};
+
+#[test]
+fn test_sticky_flag_stays_set() {
+    let step = Checked::new(250u8).safe_add(10);
+    assert_eq!(
+        step,
+        Checked {
+            value: 4,
+            overflowed: true
+        }
+    );
+
+    // Later operations don't overflow, but the flag is kept.
+    let total = step.safe_mul(2).safe_sub(3);
+    assert_eq!(total.value, 5);
+    assert!(total.overflowed);
+    assert_eq!(total.into_result(), Err(SafeMathError::Overflow));
+}
+
+#[test]
+fn test_sticky_without_overflow() {
+    let total = Checked::from(10i32).safe_sub(20).safe_mul(-3).safe_add(1);
+    assert_eq!(total, Checked::new(31));
+    assert_eq!(total.into_result(), Ok(31));
+}
+
+#[test]
+fn test_sticky_signed_overflow() {
+    let total = Checked::new(i64::MIN).safe_sub(1).safe_add(1);
+    assert_eq!(total.value, i64::MIN);
+    assert!(total.overflowed);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "strict")]
+
+use safe_math::safe_math;
+
+#[safe_math(mode = strict)]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+#[safe_math(mode = strict)]
+fn average(values: &[u32]) -> u32 {
+    let mut sum = 0u32;
+    for v in values {
+        sum += *v;
+    }
+    sum / values.len() as u32
+}
+
+#[safe_math(mode = strict)]
+fn remainder(a: i32, b: i32) -> i32 {
+    a % b
+}
+
+#[test]
+fn test_strict_mode_success() {
+    assert_eq!(add(10, 20), 30);
+    assert_eq!(average(&[1, 2, 3]), 2);
+    assert_eq!(remainder(-7, 3), -1);
+}
+
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn test_strict_mode_overflow() {
+    add(255, 1);
+}
+
+#[test]
+#[should_panic(expected = "attempt to add with overflow")]
+fn test_strict_mode_compound_assignment_overflow() {
+    average(&[u32::MAX, 1]);
+}
+
+#[test]
+#[should_panic(expected = "attempt to divide by zero")]
+fn test_strict_mode_division_by_zero() {
+    average(&[]);
+}
+
+#[test]
+#[should_panic(expected = "attempt to calculate the remainder with overflow")]
+fn test_strict_mode_remainder_overflow() {
+    remainder(i32::MIN, -1);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "tracing")]
+
+use safe_math::{safe_add, safe_math, SafeMathError};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+type Fields = Vec<(String, String)>;
+
+/// Records the level and fields of every event.
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<(Level, Fields)>>>,
+}
+
+struct FieldVisitor(Fields);
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_owned(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push((field.name().to_owned(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldVisitor(Vec::new());
+        event.record(&mut visitor);
+        let level = *event.metadata().level();
+        self.events.lock().unwrap().push((level, visitor.0));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+fn field<'a>(fields: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    fields
+        .iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn test_overflow_event() {
+    #[safe_math]
+    fn total(price: u8, quantity: u8) -> Result<u8, SafeMathError> {
+        Ok(price * quantity)
+    }
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!(total(20, 3), Ok(60));
+        assert_eq!(total(20, 13), Err(SafeMathError::Overflow));
+    });
+
+    let events = recorder.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let (level, fields) = &events[0];
+    assert_eq!(*level, Level::WARN);
+    assert_eq!(field(fields, "operation"), Some("safe_mul"));
+    assert_eq!(field(fields, "error"), Some("Overflow"));
+}
+
+#[test]
+fn test_event_per_failure() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let _ = safe_add(u32::MAX, 1);
+        let _ = safe_math::safe_div(1i64, 0);
+    });
+
+    let events = recorder.events.lock().unwrap();
+    let errors: Vec<_> = events
+        .iter()
+        .map(|(_, fields)| (field(fields, "operation"), field(fields, "error")))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (Some("safe_add"), Some("Overflow")),
+            (Some("safe_div"), Some("DivisionByZero")),
+        ]
+    );
+}
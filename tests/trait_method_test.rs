@@ -0,0 +1,56 @@
+use safe_math::{safe_math, SafeMathError};
+
+trait Account {
+    fn balance(&self) -> u32;
+    fn fee(&self) -> u32;
+
+    // Signature-only methods are left untouched.
+    #[safe_math]
+    fn limit(&self) -> Result<u32, SafeMathError>;
+
+    #[safe_math]
+    fn balance_after_fee(&self, times: u32) -> Result<u32, SafeMathError> {
+        Ok(self.balance() - self.fee() * times)
+    }
+}
+
+struct Basic {
+    balance: u32,
+}
+
+impl Account for Basic {
+    fn balance(&self) -> u32 {
+        self.balance
+    }
+
+    fn fee(&self) -> u32 {
+        10
+    }
+
+    #[safe_math]
+    fn limit(&self) -> Result<u32, SafeMathError> {
+        Ok(self.balance * 2)
+    }
+}
+
+#[test]
+fn test_default_method_is_checked() {
+    let account = Basic { balance: 100 };
+    assert_eq!(account.balance_after_fee(3), Ok(70));
+    // The implementor relies on the default body, which still checks underflow...
+    assert_eq!(account.balance_after_fee(11), Err(SafeMathError::Overflow));
+    // ...and overflow.
+    assert_eq!(
+        account.balance_after_fee(u32::MAX),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_impl_method_is_checked() {
+    assert_eq!(Basic { balance: 100 }.limit(), Ok(200));
+    assert_eq!(
+        Basic { balance: u32::MAX }.limit(),
+        Err(SafeMathError::Overflow)
+    );
+}
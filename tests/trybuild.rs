@@ -4,11 +4,31 @@ use trybuild::TestCases;
 fn ui() {
     let t = TestCases::new();
     t.compile_fail("tests/ui/bad_return_type.rs");
+    t.compile_fail("tests/ui/const_fn.rs");
+    t.compile_fail("tests/ui/extern_fn_checked.rs");
+    t.compile_fail("tests/ui/inference_hint.rs");
+    t.compile_fail("tests/ui/literal_overflow.rs");
+    t.compile_fail("tests/ui/tail_arithmetic.rs");
+    // With `derive`, the error would also list the `NotImplemented` variant.
+    #[cfg(not(feature = "derive"))]
+    t.compile_fail("tests/ui/exhaustive_error_match.rs");
+    #[cfg(not(feature = "analysis"))]
+    t.compile_fail("tests/ui/analysis_disabled.rs");
+    // The unknown mode error lists the modes enabled by features.
+    #[cfg(not(any(feature = "analysis", feature = "strict")))]
+    t.compile_fail("tests/ui/bad_safe_math_args.rs");
+    #[cfg(all(feature = "analysis", feature = "strict"))]
+    t.compile_fail("tests/ui/bad_safe_math_mode_features.rs");
+    #[cfg(not(feature = "strict"))]
+    t.compile_fail("tests/ui/strict_disabled.rs");
+    #[cfg(not(feature = "unit-error"))]
+    t.compile_fail("tests/ui/unit_error_disabled.rs");
     #[cfg(feature = "derive")]
     {
         t.compile_fail("tests/ui/bad_derive.rs");
         t.compile_fail("tests/ui/bad_derive_missing_checked_trait_unused.rs");
         t.compile_fail("tests/ui/bad_derive_missing_checked_trait_used.rs");
         t.compile_fail("tests/ui/bad_derive_missing_attributes.rs");
+        t.pass("tests/ui/derive_inherent_checked_method.rs");
     }
 }
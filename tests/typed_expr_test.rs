@@ -0,0 +1,28 @@
+use safe_math::{safe, SafeMathError};
+
+// Without the type, neither `into()` nor the literals have a type by the time of the
+// method call (see tests/ui/inference_hint.rs).
+fn capped_square<T: Copy + Into<u32> + Into<u64>>(x: T, cap: u64) -> Result<u64, SafeMathError> {
+    Ok(safe!(x.into() * x.into(); u64).min(cap))
+}
+
+fn literal_bits() -> Result<u32, SafeMathError> {
+    Ok(safe!(200 + 100; u8).count_ones())
+}
+
+fn scaled<T: Into<u32>>(x: T, scale: u32) -> Result<u32, SafeMathError> {
+    let mut total = safe!(x.into() + 0; u32);
+    safe!(total *= scale; u32);
+    Ok(total)
+}
+
+#[test]
+fn test_typed_expr() {
+    assert_eq!(capped_square(3u8, 100), Ok(9));
+    assert_eq!(capped_square(u32::MAX, 1000), Ok(1000));
+    assert_eq!(capped_square(u16::MAX, u64::MAX), Ok(4_294_836_225));
+    // `300` would fit the default `i32`, but not the `u8` every operation is done in.
+    assert_eq!(literal_bits(), Err(SafeMathError::Overflow));
+    assert_eq!(scaled(7u8, 3), Ok(21));
+    assert_eq!(scaled(u16::MAX, 1 << 17), Err(SafeMathError::Overflow));
+}
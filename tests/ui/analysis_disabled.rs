@@ -0,0 +1,9 @@
+use safe_math::safe_math;
+
+// `mode = analysis` is only available with the `analysis` feature
+#[safe_math(mode = analysis)]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn main() {}
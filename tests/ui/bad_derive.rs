@@ -14,4 +14,36 @@ struct Bar(i32);
 #[derive(SafeMathOps)]
 struct Baz(i32);
 
+// `checked_sum` without `add`
+#[derive(SafeMathOps)]
+#[SafeMathOps(sub, checked_sum)]
+struct Qux(i32);
+
+// `scale` on an enum
+#[derive(SafeMathOps)]
+#[SafeMathOps(scale)]
+enum Quux {
+    A(i32),
+}
+
+// `float` with `by_ref`
+#[derive(SafeMathOps)]
+#[SafeMathOps(add, float, by_ref)]
+struct Corge(f64);
+
+// `float` on a unit struct
+#[derive(SafeMathOps)]
+#[SafeMathOps(add, float)]
+struct Grault;
+
+// `wrapping` without `add`, `sub` or `mul`
+#[derive(SafeMathOps)]
+#[SafeMathOps(div, wrapping)]
+struct Garply(i32);
+
+// `saturating` with `float`
+#[derive(SafeMathOps)]
+#[SafeMathOps(add, float, saturating)]
+struct Waldo(f64);
+
 fn main() {} 
\ No newline at end of file
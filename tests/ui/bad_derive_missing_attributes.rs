@@ -21,7 +21,7 @@ impl CheckedSub for Foo {
 
 
 #[safe_math]
-fn test_add(a: Foo, b: Foo) -> Result<Foo, ()> {
+fn test_add(a: Foo, b: Foo) -> Result<Foo, safe_math::SafeMathError> {
     Ok(a + b)
 }
 
@@ -14,7 +14,7 @@ impl Add for Foo {
 
 
 #[safe_math]
-fn test_add(a: Foo, b: Foo) -> Result<Foo, ()> {
+fn test_add(a: Foo, b: Foo) -> Result<Foo, safe_math::SafeMathError> {
     Ok(a + b)
 }
 
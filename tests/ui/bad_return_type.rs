@@ -5,4 +5,9 @@ fn wrong_return_type() -> i32 { // should return Result<_, _>
     1 + 2
 }
 
+#[safe_math]
+fn unit_return_type() -> () {
+    let _ = 1 + 2;
+}
+
 fn main() {} 
\ No newline at end of file
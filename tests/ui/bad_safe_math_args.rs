@@ -0,0 +1,13 @@
+use safe_math::safe_math;
+
+#[safe_math(mode = unknown)]
+fn unknown_mode(a: u8, b: u8) -> Result<u8, ()> {
+    Ok(a + b)
+}
+
+#[safe_math(fast)]
+fn unknown_argument(a: u8, b: u8) -> Result<u8, ()> {
+    Ok(a + b)
+}
+
+fn main() {}
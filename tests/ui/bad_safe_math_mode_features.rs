@@ -0,0 +1,8 @@
+use safe_math::safe_math;
+
+#[safe_math(mode = unknown)]
+fn unknown_mode(a: u8, b: u8) -> Result<u8, safe_math::SafeMathError> {
+    Ok(a + b)
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use safe_math_macros::safe_math;
+
+#[safe_math]
+const fn const_add(a: u32, b: u32) -> Result<u32, safe_math::SafeMathError> {
+    Ok(a + b)
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use num_traits::CheckedAdd;
+use safe_math::{safe_math, SafeMathError, SafeMathOps};
+use std::ops::Add;
+
+#[derive(Debug, Copy, Clone, PartialEq, SafeMathOps)]
+#[SafeMathOps(add)]
+struct Foo(i32);
+
+impl Foo {
+    // Inherent method with the same name as the `CheckedAdd` one but a different
+    // signature. Method-call syntax would pick this one over the trait method.
+    #[allow(dead_code)]
+    fn checked_add(self, rhs: i32) -> Option<Foo> {
+        self.0.checked_add(rhs).map(Foo)
+    }
+}
+
+impl Add for Foo {
+    type Output = Foo;
+    fn add(self, other: Foo) -> Foo {
+        Foo(self.0 + other.0)
+    }
+}
+
+impl CheckedAdd for Foo {
+    fn checked_add(&self, other: &Foo) -> Option<Foo> {
+        self.0.checked_add(other.0).map(Foo)
+    }
+}
+
+#[safe_math]
+fn add(a: Foo, b: Foo) -> Result<Foo, SafeMathError> {
+    Ok(a + b)
+}
+
+fn main() {
+    assert_eq!(add(Foo(1), Foo(2)), Ok(Foo(3)));
+    assert_eq!(add(Foo(i32::MAX), Foo(1)), Err(SafeMathError::Overflow));
+}
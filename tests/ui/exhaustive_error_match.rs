@@ -0,0 +1,17 @@
+use safe_math::SafeMathError;
+
+// `SafeMathError` is `#[non_exhaustive]`: listing every variant isn't enough.
+fn describe(err: SafeMathError) -> &'static str {
+    match err {
+        SafeMathError::Overflow => "overflow",
+        SafeMathError::DivisionByZero => "division by zero",
+        SafeMathError::InfiniteOrNaN => "infinite or NaN",
+        SafeMathError::ShiftOverflow => "shift overflow",
+        SafeMathError::ParseError => "parse error",
+        SafeMathError::InvalidBounds => "invalid bounds",
+    }
+}
+
+fn main() {
+    let _ = describe(SafeMathError::Overflow);
+}
@@ -0,0 +1,8 @@
+use safe_math_macros::safe_math;
+
+#[safe_math]
+extern "C" fn ffi_add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use safe_math::{safe_math_block, SafeMathError};
+
+fn capped_square<T: Copy + Into<u32> + Into<u64>>(x: T, cap: u64) -> Result<u64, SafeMathError> {
+    Ok(safe_math_block!(x.into() * x.into()).min(cap))
+}
+
+fn literal_bits() -> Result<u32, SafeMathError> {
+    Ok(safe_math_block!(200 + 100).count_ones())
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use safe_math::safe_math;
+
+#[safe_math]
+fn always_overflows() -> Result<u8, safe_math::SafeMathError> {
+    Ok(255u8 + 1u8)
+}
+
+#[safe_math]
+fn always_divides_by_zero(a: i32) -> Result<i32, safe_math::SafeMathError> {
+    Ok(a + 10i32 / 0)
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use safe_math::safe_math;
+
+// `mode = strict` is only available with the `strict` feature
+#[safe_math(mode = strict)]
+fn add(a: u8, b: u8) -> u8 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use safe_math::safe_math;
+
+#[safe_math]
+fn sum(a: u8, b: u8) -> Result<u8, safe_math::SafeMathError> {
+    a + b
+}
+
+#[safe_math]
+fn bump(counter: &mut u8) -> Result<(), safe_math::SafeMathError> {
+    *counter += 1
+}
+
+fn main() {}
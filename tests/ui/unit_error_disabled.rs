@@ -0,0 +1,9 @@
+use safe_math::safe_math;
+
+// Without the `unit-error` feature, `SafeMathError` no longer converts into `()`
+#[safe_math]
+fn add(a: u8, b: u8) -> Result<u8, ()> {
+    Ok(a + b)
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+#![cfg(feature = "unit-error")]
+
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math]
+fn add_unit(a: u8, b: u8) -> Result<u8, ()> {
+    Ok(a + b)
+}
+
+#[test]
+fn test_question_mark_into_unit() {
+    assert_eq!(add_unit(1, 2), Ok(3));
+    assert_eq!(add_unit(255, 1), Err(()));
+}
+
+#[test]
+fn test_from_safe_math_error_for_unit() {
+    let converted: () = SafeMathError::DivisionByZero.into();
+    assert_eq!(converted, ());
+}
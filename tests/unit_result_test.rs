@@ -0,0 +1,55 @@
+//! `#[safe_math]` functions returning `Result<(), E>`, where arithmetic is only
+//! used for its side effects.
+
+use safe_math::{safe_math, SafeMathError};
+
+#[safe_math]
+fn bump(counter: &mut u8) -> Result<(), SafeMathError> {
+    *counter += 1;
+    Ok(())
+}
+
+#[safe_math]
+fn bump_by(counter: &mut u8, step: u8, double: bool) -> Result<(), SafeMathError> {
+    // Compound assignments as the tail of inner blocks evaluate to `()` as well.
+    if double {
+        *counter += step * 2
+    } else {
+        *counter += step
+    }
+    Ok(())
+}
+
+#[safe_math]
+fn validate_sum(a: u8, b: u8) -> Result<(), SafeMathError> {
+    let _ = a + b;
+    Ok(())
+}
+
+#[test]
+fn test_compound_assignment_statement() {
+    let mut counter = 254;
+    assert_eq!(bump(&mut counter), Ok(()));
+    assert_eq!(counter, 255);
+    assert_eq!(bump(&mut counter), Err(SafeMathError::Overflow));
+    assert_eq!(counter, 255);
+}
+
+#[test]
+fn test_compound_assignment_block_tail() {
+    let mut counter = 0;
+    assert_eq!(bump_by(&mut counter, 3, false), Ok(()));
+    assert_eq!(bump_by(&mut counter, 3, true), Ok(()));
+    assert_eq!(counter, 9);
+    assert_eq!(
+        bump_by(&mut counter, 200, true),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(counter, 9);
+}
+
+#[test]
+fn test_discarded_arithmetic_is_checked() {
+    assert_eq!(validate_sum(1, 2), Ok(()));
+    assert_eq!(validate_sum(255, 1), Err(SafeMathError::Overflow));
+}
@@ -37,3 +37,419 @@ fn test_safe_functions_directly() {
     assert_eq!(safe_div(30u8, 6u8), Ok(5u8));
     assert!(safe_div(10u8, 0u8).is_err());
 }
+
+#[test]
+fn test_signed_diff() {
+    assert_eq!(3u32.safe_sub_signed(10), Ok(-7i64));
+    assert_eq!(10u32.safe_sub_signed(3), Ok(7i64));
+    assert_eq!(0u8.safe_sub_signed(u8::MAX), Ok(-255i16));
+    assert_eq!(safe_sub_signed(0u64, u64::MAX), Ok(-(u64::MAX as i128)));
+
+    assert_eq!(3u32.safe_signed_diff(10), Ok(7u32));
+    assert_eq!(safe_signed_diff(10u32, 3u32), Ok(7u32));
+
+    // `u128` has no wider signed type, so large differences are reported.
+    assert_eq!(0u128.safe_sub_signed(1u128 << 127), Ok(i128::MIN));
+    assert_eq!(u128::MAX.safe_sub_signed(0), Err(SafeMathError::Overflow));
+    assert_eq!(
+        0u128.safe_sub_signed(u128::MAX),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_inc_dec() {
+    assert_eq!(254u8.safe_inc(), Ok(u8::MAX));
+    assert_eq!(u8::MAX.safe_inc(), Err(SafeMathError::Overflow));
+    assert_eq!(1u8.safe_dec(), Ok(0));
+    assert_eq!(0u8.safe_dec(), Err(SafeMathError::Overflow));
+    assert_eq!(i32::MIN.safe_dec(), Err(SafeMathError::Overflow));
+    assert_eq!((-1i32).safe_inc(), Ok(0));
+    assert_eq!(1.5f64.safe_inc(), Ok(2.5));
+
+    // Ring buffer cursor: advancing stops at the type's boundary instead of wrapping.
+    let mut cursor = 250u8;
+    while let Ok(next) = cursor.safe_inc() {
+        cursor = next;
+    }
+    assert_eq!(cursor, u8::MAX);
+}
+
+#[test]
+fn test_float_rem_by_zero() {
+    // `x % 0.0` is NaN, reported like any other non-finite result.
+    assert_eq!(safe_rem(5.0f64, 0.0), Err(SafeMathError::InfiniteOrNaN));
+    assert_eq!(
+        safe_rem_nonzero(5.0f64, 0.0),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(
+        safe_rem_nonzero(5.0f32, -0.0),
+        Err(SafeMathError::DivisionByZero)
+    );
+    assert_eq!(safe_rem_nonzero(5.0f64, 3.0), Ok(2.0));
+    assert_eq!(safe_rem_nonzero(7u8, 0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(safe_rem_nonzero(i32::MIN, -1), Err(SafeMathError::Overflow));
+
+    // Other non-finite results are still reported as such.
+    assert_eq!(
+        safe_rem_nonzero(f64::INFINITY, 2.0),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(safe_div(5.0f64, 0.0), Err(SafeMathError::InfiniteOrNaN));
+}
+
+#[test]
+fn test_shift() {
+    assert_eq!(1u8.safe_shl(7), Ok(128));
+    assert_eq!(1u8.safe_shl(8), Err(SafeMathError::ShiftOverflow));
+    assert_eq!(safe_shl(1u8, 9), Err(SafeMathError::ShiftOverflow));
+    assert_eq!(safe_shr(u64::MAX, 63), Ok(1));
+    assert_eq!(safe_shr(-1i64, 64), Err(SafeMathError::ShiftOverflow));
+    assert_eq!(
+        SafeMathError::ShiftOverflow.to_string(),
+        "shift amount out of range"
+    );
+}
+
+#[test]
+fn test_next_power_of_two() {
+    assert_eq!(0usize.safe_next_power_of_two(), Ok(1));
+    assert_eq!(1usize.safe_next_power_of_two(), Ok(1));
+    assert_eq!(safe_next_power_of_two(1000usize), Ok(1024));
+    assert_eq!(safe_next_power_of_two(200u8), Err(SafeMathError::Overflow));
+
+    let largest = 1usize << (usize::BITS - 1);
+    assert_eq!(safe_next_power_of_two(largest - 1), Ok(largest));
+    assert_eq!(safe_next_power_of_two(largest), Ok(largest));
+    assert_eq!(
+        safe_next_power_of_two(largest + 1),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        usize::MAX.safe_next_power_of_two(),
+        Err(SafeMathError::Overflow)
+    );
+}
+
+#[test]
+fn test_ilog() {
+    assert_eq!(8u32.safe_ilog2(), Ok(3));
+    assert_eq!(9u32.safe_ilog2(), Ok(3));
+    assert_eq!(0u32.safe_ilog2(), Err(SafeMathError::DivisionByZero));
+    assert_eq!(safe_ilog10(1000u64), Ok(3));
+    assert_eq!(safe_ilog10(0u64), Err(SafeMathError::DivisionByZero));
+    assert_eq!(safe_ilog(u8::MAX, 2), Ok(7));
+    assert_eq!(safe_ilog2(usize::MAX), Ok(usize::BITS - 1));
+    assert_eq!(0u16.safe_ilog(3), Err(SafeMathError::DivisionByZero));
+    assert_eq!(10u16.safe_ilog(1), Err(SafeMathError::DivisionByZero));
+    assert_eq!(10u16.safe_ilog(0), Err(SafeMathError::DivisionByZero));
+}
+
+#[test]
+fn test_mul_add() {
+    assert_eq!(3u32.safe_mul_add(4, 5), Ok(17));
+    assert_eq!(safe_mul_add(-3i8, 4, 5), Ok(-7));
+    // The multiplication overflows even though `b` would bring the result back in range.
+    assert_eq!(safe_mul_add(64i8, 2, -1), Err(SafeMathError::Overflow));
+    assert_eq!(safe_mul_add(100u8, 2, 56), Err(SafeMathError::Overflow));
+    assert_eq!(safe_mul_add(2.5f32, 2.0, 0.5), Ok(5.5));
+    assert_eq!(
+        safe_mul_add(f32::MAX, 2.0, 0.0),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    assert_eq!(
+        safe_mul_add(f64::INFINITY, 0.0, 1.0),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+    // A single rounding: the exact result of `0.1 * 10 - 1` is not zero.
+    assert_ne!(safe_mul_add(0.1f64, 10.0, -1.0), Ok(0.0));
+}
+
+#[test]
+fn test_mul_div() {
+    // The naive `value * num` overflows, but the result fits.
+    assert_eq!(safe_mul(u64::MAX, 3), Err(SafeMathError::Overflow));
+    assert_eq!(safe_mul_div(u64::MAX, 3, 4), Ok(u64::MAX / 4 * 3 + 2));
+    assert_eq!(safe_mul(200u8, 200), Err(SafeMathError::Overflow));
+    assert_eq!(200u8.safe_mul_div(200, 250), Ok(160));
+    assert_eq!(safe_mul_div(i32::MIN, 2, -4), Ok(1 << 30));
+    assert_eq!(safe_mul_div(-7i64, 1, 2), Ok(-3));
+    assert_eq!(safe_mul_div(usize::MAX, 10, 100), Ok(usize::MAX / 10));
+    assert_eq!(safe_mul_div(i8::MIN, 1, -1), Err(SafeMathError::Overflow));
+    assert_eq!(safe_mul_div(u16::MAX, 2, 1), Err(SafeMathError::Overflow));
+    assert_eq!(safe_mul_div(5u32, 2, 0), Err(SafeMathError::DivisionByZero));
+    // 128-bit values have no wider type, so their product must fit.
+    assert_eq!(safe_mul_div(u128::MAX / 2, 2, 2), Ok(u128::MAX / 2));
+    assert_eq!(safe_mul_div(u128::MAX, 2, 4), Err(SafeMathError::Overflow));
+    assert_eq!(
+        safe_mul_div(0i128, 1, 0),
+        Err(SafeMathError::DivisionByZero)
+    );
+}
+
+#[test]
+fn test_div_round() {
+    use Rounding::{Ceil, Floor, Round, Trunc};
+
+    // (a, b, trunc, floor, ceil, round)
+    let cases: [(i32, i32, i32, i32, i32, i32); 8] = [
+        (7, 2, 3, 3, 4, 4),
+        (-7, 2, -3, -4, -3, -4),
+        (7, -2, -3, -4, -3, -4),
+        (-7, -2, 3, 3, 4, 4),
+        (8, 3, 2, 2, 3, 3),
+        (7, 3, 2, 2, 3, 2),
+        (-7, 3, -2, -3, -2, -2),
+        (6, 3, 2, 2, 2, 2),
+    ];
+    for (a, b, trunc, floor, ceil, round) in cases {
+        assert_eq!(safe_div_round(a, b, Trunc), Ok(trunc), "{a} / {b}");
+        assert_eq!(safe_div_round(a, b, Floor), Ok(floor), "{a} / {b}");
+        assert_eq!(safe_div_round(a, b, Ceil), Ok(ceil), "{a} / {b}");
+        assert_eq!(safe_div_round(a, b, Round), Ok(round), "{a} / {b}");
+    }
+}
+
+#[test]
+fn test_div_round_limits() {
+    use Rounding::{Ceil, Floor, Round, Trunc};
+
+    // `(a + b / 2) / b` would overflow here, the remainder-based rounding doesn't.
+    assert_eq!(safe_div_round(u8::MAX, 2, Round), Ok(128));
+    assert_eq!(safe_div_round(u64::MAX, 2, Ceil), Ok(1 << 63));
+    assert_eq!(safe_div_round(i8::MAX, 2, Round), Ok(64));
+    assert_eq!(safe_div_round(i8::MIN, 3, Floor), Ok(-43));
+    assert_eq!(safe_div_round(i8::MIN, i8::MIN, Round), Ok(1));
+    assert_eq!(safe_div_round(-1i8, i8::MIN, Round), Ok(0));
+    assert_eq!(
+        safe_div_round(i8::MIN, -1, Trunc),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        safe_div_round(i8::MIN, -1, Round),
+        Err(SafeMathError::Overflow)
+    );
+    for mode in [Trunc, Floor, Ceil, Round] {
+        assert_eq!(
+            safe_div_round(1u32, 0, mode),
+            Err(SafeMathError::DivisionByZero)
+        );
+        assert_eq!(
+            1i64.safe_div_round(0, mode),
+            Err(SafeMathError::DivisionByZero)
+        );
+    }
+}
+
+#[test]
+fn test_midpoint() {
+    // `(a + b) / 2` overflows for all of these.
+    assert_eq!(safe_midpoint(u32::MAX, u32::MAX), Ok(u32::MAX));
+    assert_eq!(safe_midpoint(u32::MAX, u32::MAX - 2), Ok(u32::MAX - 1));
+    assert_eq!(safe_midpoint(u32::MAX - 1, u32::MAX), Ok(u32::MAX - 1));
+    assert_eq!(safe_midpoint(u128::MAX, 1), Ok(1 << 127));
+    assert_eq!(safe_midpoint(i64::MAX, i64::MAX), Ok(i64::MAX));
+    assert_eq!(safe_midpoint(i64::MIN, i64::MIN), Ok(i64::MIN));
+    assert_eq!(safe_midpoint(i64::MIN, i64::MAX), Ok(0));
+    assert_eq!(safe_midpoint(f32::MAX, f32::MAX), Ok(f32::MAX));
+    assert_eq!(safe_midpoint(f64::MAX, -f64::MAX), Ok(0.0));
+
+    assert_eq!(safe_midpoint(2u8, 7), Ok(4));
+    assert_eq!(7u8.safe_midpoint(2), Ok(4));
+    // Signed averages round toward zero, like the standard `midpoint`.
+    assert_eq!(safe_midpoint(-7i8, 2), Ok(-2));
+    assert_eq!(safe_midpoint(-1i8, 0), Ok(0));
+    assert_eq!(safe_midpoint(7i8, -2), Ok(2));
+    assert_eq!(safe_midpoint(1.0f64, 2.0), Ok(1.5));
+    assert_eq!(
+        safe_midpoint(f64::INFINITY, 0.0),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+}
+
+#[test]
+fn test_range_len() {
+    assert_eq!(safe_range_len(3usize, 10), Ok(7));
+    assert_eq!(safe_range_len(5u8, 5), Ok(0));
+    assert_eq!(safe_range_len(-5i32, 5), Ok(10));
+    // Reversed ranges are rejected instead of wrapping around.
+    assert_eq!(
+        safe_range_len(10usize, 3),
+        Err(SafeMathError::InvalidBounds)
+    );
+    assert_eq!(5i64.safe_range_len(-5), Err(SafeMathError::InvalidBounds));
+    // Spans that don't fit in the operand type itself.
+    assert_eq!(safe_range_len(i8::MIN, i8::MAX), Ok(255));
+    assert_eq!(safe_range_len(isize::MIN, isize::MAX), Ok(usize::MAX));
+    // Spans that don't fit in `usize`.
+    assert_eq!(
+        safe_range_len(0u128, u128::MAX),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(safe_range_len(i128::MIN, 0), Err(SafeMathError::Overflow));
+    assert_eq!(safe_range_len(7u128, 9), Ok(2));
+}
+
+#[test]
+fn test_error_display() {
+    assert_eq!(SafeMathError::Overflow.to_string(), "arithmetic overflow");
+    assert_eq!(
+        SafeMathError::DivisionByZero.to_string(),
+        "division by zero"
+    );
+    assert_eq!(
+        SafeMathError::InfiniteOrNaN.to_string(),
+        "infinite or NaN value"
+    );
+    assert_eq!(SafeMathError::ParseError.to_string(), "invalid number");
+
+    let err: Box<dyn std::error::Error> = Box::new(SafeMathError::ParseError);
+    assert_eq!(err.to_string(), "invalid number");
+}
+
+#[test]
+fn test_error_code_round_trip() {
+    let errors = [
+        SafeMathError::Overflow,
+        SafeMathError::DivisionByZero,
+        SafeMathError::InfiniteOrNaN,
+        SafeMathError::ShiftOverflow,
+        SafeMathError::ParseError,
+        SafeMathError::InvalidBounds,
+        #[cfg(feature = "derive")]
+        SafeMathError::NotImplemented,
+    ];
+    for (i, err) in errors.into_iter().enumerate() {
+        // Codes are part of the FFI contract: they must not change.
+        assert_eq!(err.code(), i as i32 + 1);
+        assert_eq!(SafeMathError::from_code(err.code()), Some(err));
+    }
+    assert_eq!(SafeMathError::TypeMismatch.code(), 8);
+    assert_eq!(
+        SafeMathError::from_code(8),
+        Some(SafeMathError::TypeMismatch)
+    );
+    assert_eq!(SafeMathError::from_code(0), None);
+    assert_eq!(SafeMathError::from_code(-1), None);
+    assert_eq!(SafeMathError::from_code(9), None);
+}
+
+#[test]
+fn test_error_category() {
+    let categories = [
+        (SafeMathError::Overflow, Category::Range),
+        (SafeMathError::OverflowWith { wrapped: -1 }, Category::Range),
+        (SafeMathError::ShiftOverflow, Category::Range),
+        (SafeMathError::DivisionByZero, Category::DivByZero),
+        (SafeMathError::InfiniteOrNaN, Category::NotFinite),
+        (SafeMathError::ParseError, Category::Other),
+        (SafeMathError::InvalidBounds, Category::Other),
+        (SafeMathError::TypeMismatch, Category::Other),
+        #[cfg(feature = "derive")]
+        (SafeMathError::NotImplemented, Category::Other),
+    ];
+    for (err, category) in categories {
+        assert_eq!(err.kind_category(), category, "{err:?}");
+    }
+
+    assert_eq!(
+        safe_div(1.0f64, 0.0).map_err(|err| err.kind_category()),
+        Err(Category::NotFinite)
+    );
+    assert_eq!(
+        safe_rem(1u32, 0).map_err(|err| err.kind_category()),
+        Err(Category::DivByZero)
+    );
+}
+
+#[test]
+fn test_error_severity() {
+    assert_eq!(SafeMathError::Overflow.severity(), 1);
+    assert_eq!(SafeMathError::InfiniteOrNaN.severity(), 1);
+    assert_eq!(SafeMathError::ParseError.severity(), 1);
+    assert_eq!(SafeMathError::DivisionByZero.severity(), 2);
+    assert_eq!(SafeMathError::ShiftOverflow.severity(), 2);
+    assert_eq!(SafeMathError::InvalidBounds.severity(), 3);
+    #[cfg(feature = "derive")]
+    assert_eq!(SafeMathError::NotImplemented.severity(), 3);
+
+    let batch = [
+        safe_add(255u8, 1),
+        safe_div(1u8, 0),
+        safe_sub(0u8, 1),
+        safe_add(1u8, 1),
+    ];
+    let worst = batch
+        .iter()
+        .filter_map(|result| result.err())
+        .max_by_key(SafeMathError::severity);
+    assert_eq!(worst, Some(SafeMathError::DivisionByZero));
+}
+
+#[test]
+fn test_error_hash() {
+    use std::collections::HashMap;
+
+    let mut counts: HashMap<SafeMathError, usize> = HashMap::new();
+    for result in [safe_add(255u8, 1), safe_div(1u8, 0), safe_mul(16u8, 16)] {
+        if let Err(err) = result {
+            *counts.entry(err).or_default() += 1;
+        }
+    }
+    assert_eq!(counts[&SafeMathError::Overflow], 2);
+    assert_eq!(counts[&SafeMathError::DivisionByZero], 1);
+    assert_eq!(counts.get(&SafeMathError::InfiniteOrNaN), None);
+}
+
+#[test]
+fn test_clamp() {
+    assert_eq!(safe_clamp(5u8, 1, 10), Ok(5));
+    assert_eq!(safe_clamp(0u8, 1, 10), Ok(1));
+    assert_eq!(safe_clamp(255u8, 1, 10), Ok(10));
+    assert_eq!(safe_clamp(-3i32, -2, -2), Ok(-2));
+    assert_eq!(safe_clamp(1.5f64, 0.0, 1.0), Ok(1.0));
+
+    assert_eq!(safe_clamp(5u8, 10, 1), Err(SafeMathError::InvalidBounds));
+    assert_eq!(
+        safe_clamp(0.5f64, f64::NAN, 1.0),
+        Err(SafeMathError::InvalidBounds)
+    );
+    assert_eq!(SafeMathError::InvalidBounds.to_string(), "invalid bounds");
+}
+
+#[test]
+fn test_clamping_cast() {
+    // Unsigned targets
+    assert_eq!(clamping_cast::<u32, u8>(300), u8::MAX);
+    assert_eq!(clamping_cast::<i32, u8>(-5), 0);
+    assert_eq!(clamping_cast::<i64, u16>(i64::MIN), 0);
+    assert_eq!(clamping_cast::<u32, u8>(42), 42);
+
+    // Signed targets
+    assert_eq!(clamping_cast::<i32, i8>(1000), i8::MAX);
+    assert_eq!(clamping_cast::<i32, i8>(-1000), i8::MIN);
+    assert_eq!(clamping_cast::<u64, i32>(u64::MAX), i32::MAX);
+    assert_eq!(clamping_cast::<u8, i8>(200), i8::MAX);
+    assert_eq!(clamping_cast::<i16, i8>(-7), -7);
+
+    // Widening never clamps
+    assert_eq!(clamping_cast::<i8, i64>(i8::MIN), i8::MIN as i64);
+}
+
+#[test]
+fn test_sign_cast() {
+    // Signed to unsigned
+    assert_eq!(safe_sign_cast(0i32), Ok(0u32));
+    assert_eq!(safe_sign_cast(i32::MAX), Ok(i32::MAX as u32));
+    assert_eq!(safe_sign_cast(-1i32), Err(SafeMathError::Overflow));
+    assert_eq!(safe_sign_cast(i64::MIN), Err(SafeMathError::Overflow));
+    assert_eq!((-5isize).safe_sign_cast(), Err(SafeMathError::Overflow));
+
+    // Unsigned to signed
+    assert_eq!(safe_sign_cast(127u8), Ok(127i8));
+    assert_eq!(safe_sign_cast(200u8), Err(SafeMathError::Overflow));
+    assert_eq!(safe_sign_cast(u128::MAX), Err(SafeMathError::Overflow));
+    assert_eq!(u16::MAX.safe_sign_cast(), Err(SafeMathError::Overflow));
+    assert_eq!(42usize.safe_sign_cast(), Ok(42isize));
+}
@@ -0,0 +1,45 @@
+use safe_math::{Safe, SafeMathError};
+
+#[test]
+fn test_operators() {
+    assert_eq!(Safe(2u32) + Safe(3), Ok(Safe(5)));
+    assert_eq!(Safe(5i32) - Safe(7), Ok(Safe(-2)));
+    assert_eq!(Safe(6u64) * Safe(7), Ok(Safe(42)));
+    assert_eq!(Safe(7i8) / Safe(-2), Ok(Safe(-3)));
+    assert_eq!(Safe(7u16) % Safe(4), Ok(Safe(3)));
+    assert_eq!(Safe(1.5f64) * Safe(2.0), Ok(Safe(3.0)));
+}
+
+#[test]
+fn test_overflow() {
+    assert_eq!(Safe(255u8) + Safe(1), Err(SafeMathError::Overflow));
+    assert_eq!(Safe(0u8) - Safe(1), Err(SafeMathError::Overflow));
+    assert_eq!(Safe(i32::MIN) / Safe(-1), Err(SafeMathError::Overflow));
+    assert_eq!(Safe(1u32) % Safe(0), Err(SafeMathError::DivisionByZero));
+    assert_eq!(
+        Safe(f32::MAX) * Safe(2.0),
+        Err(SafeMathError::InfiniteOrNaN)
+    );
+}
+
+#[test]
+fn test_chain_short_circuits() {
+    assert_eq!(Safe(10u8) * Safe(5) + Safe(5) - Safe(15), Ok(Safe(40)));
+    // The addition overflows: the later division by zero is not reached.
+    assert_eq!(
+        (Safe(250u8) + Safe(10)) / Safe(0),
+        Err(SafeMathError::Overflow)
+    );
+    assert_eq!(
+        (Safe(100u8) + Safe(100)) / Safe(0),
+        Err(SafeMathError::DivisionByZero)
+    );
+}
+
+#[test]
+fn test_conversions() {
+    let a: Safe<u32> = 40.into();
+    assert_eq!(Safe::from(2u32), Safe(2));
+    assert_eq!((a + Safe(2)).map(Safe::into_inner), Ok(42));
+    assert_eq!(a.to_string(), "40");
+}